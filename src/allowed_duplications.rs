@@ -0,0 +1,158 @@
+use std::path::Path;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::entities::{Translation, TranslationValue};
+use crate::extendable_file::{read_extendable_entries, ExtendableFileError};
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::settings::ValueNormalization;
+
+/// A value allowed to be duplicated between the keys and packages matching the patterns,
+/// a missing pattern allowing every key or package
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AllowedDuplication {
+    pub value: String,
+    pub key_pattern: Option<String>,
+    /// Matched against package paths as `packages/manager/apps/zimbra`
+    pub package_pattern: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum AllowlistError {
+    #[error("Unable to read allowlist file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid allowlist file: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("Allowlist entry {0} has neither `value` nor `value_hash`")]
+    EmptyEntry(usize),
+
+    #[error("Allowlist {0} extends itself through its `extends` chain")]
+    ExtendsCycle(String),
+}
+
+impl From<ExtendableFileError> for AllowlistError {
+    fn from(error: ExtendableFileError) -> Self {
+        match error {
+            ExtendableFileError::UnableToReadPath(path, e) => AllowlistError::UnableToReadPath(path, e),
+            ExtendableFileError::InvalidFormat(path, e) => AllowlistError::InvalidFormat(path, e),
+            ExtendableFileError::ExtendsCycle(path) => AllowlistError::ExtendsCycle(path),
+        }
+    }
+}
+
+/// A value, optionally restricted to a key, never reported as duplication
+/// `value_hash` is the cluster id shown in the reports, an alternative to writing the value
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AllowlistEntry {
+    pub key: Option<String>,
+    pub value: Option<String>,
+    pub value_hash: Option<String>,
+}
+
+struct CompiledAllowedDuplication {
+    normalized_value: String,
+    key_regex: Option<Regex>,
+    package_regex: Option<Regex>,
+}
+
+struct CompiledAllowlistEntry {
+    key: Option<String>,
+    normalized_value: Option<String>,
+    value_hash: Option<String>,
+}
+
+/// Allowed duplications of the settings and allowlist, ready to be evaluated during classification
+#[derive(Default)]
+pub struct AllowedDuplications {
+    scopes: Vec<CompiledAllowedDuplication>,
+    allowlist: Vec<CompiledAllowlistEntry>,
+}
+
+/// Reads the allowlist file, a missing file meaning an empty allowlist
+/// The entries of the allowlists it `extends` come first, as an upstream monorepo allowlist
+pub fn get_allowlist(allowlist_file_path: &Path) -> Result<Vec<AllowlistEntry>, AllowlistError> {
+    let entries: Vec<AllowlistEntry> = read_extendable_entries(allowlist_file_path)?;
+
+    match entries.iter().position(|entry| entry.value.is_none() && entry.value_hash.is_none()) {
+        Some(index) => Err(AllowlistError::EmptyEntry(index)),
+        None => Ok(entries),
+    }
+}
+
+impl AllowedDuplications {
+    pub fn compile(allowed_duplications: &[AllowedDuplication], normalization: &ValueNormalization) -> Result<Self, regex::Error> {
+        let compile_pattern = |pattern: &Option<String>| pattern.as_deref().map(Regex::new).transpose();
+
+        allowed_duplications
+            .iter()
+            .map(|allowed| {
+                Ok(CompiledAllowedDuplication {
+                    normalized_value: TranslationValue::String(allowed.value.clone()).normalized(normalization),
+                    key_regex: compile_pattern(&allowed.key_pattern)?,
+                    package_regex: compile_pattern(&allowed.package_pattern)?,
+                })
+            })
+            .collect::<Result<Vec<_>, regex::Error>>()
+            .map(|scopes| Self { scopes, allowlist: Vec::new() })
+    }
+
+    pub fn with_allowlist(mut self, allowlist: &[AllowlistEntry], normalization: &ValueNormalization) -> Self {
+        self.allowlist = allowlist
+            .iter()
+            .map(|entry| CompiledAllowlistEntry {
+                key: entry.key.clone(),
+                normalized_value: entry
+                    .value
+                    .as_ref()
+                    .map(|value| TranslationValue::String(value.clone()).normalized(normalization)),
+                value_hash: entry.value_hash.as_ref().map(|value_hash| value_hash.trim_start_matches('#').to_string()),
+            })
+            .collect();
+        self
+    }
+
+    /// Whether the translation is on the allowlist, and must never be reported as duplication
+    pub fn is_allowlisted(&self, translation: &Translation, normalized_value: &str, cluster_id: &str) -> bool {
+        self.allowlist.iter().any(|entry| {
+            entry.key.as_ref().is_none_or(|key| *key == translation.key)
+                && entry.normalized_value.as_ref().is_none_or(|value| value == normalized_value)
+                && entry.value_hash.as_ref().is_none_or(|value_hash| value_hash == cluster_id)
+        })
+    }
+
+    /// Occurrences of the normalized value still counting as duplicates of `translation`,
+    /// the occurrences sharing an allowed scope with it being left out
+    pub fn filter_occurrences<'a>(
+        &self,
+        translation: &Translation,
+        normalized_value: &str,
+        occurrences: &[&'a Translation],
+        package_path_pattern: &PackagePathPattern,
+    ) -> Vec<&'a Translation> {
+        let scopes: Vec<&CompiledAllowedDuplication> = self
+            .scopes
+            .iter()
+            .filter(|allowed| allowed.normalized_value == normalized_value && allowed.matches(translation, package_path_pattern))
+            .collect();
+
+        occurrences
+            .iter()
+            .filter(|occurrence| {
+                std::ptr::eq(**occurrence, translation) || !scopes.iter().any(|allowed| allowed.matches(occurrence, package_path_pattern))
+            })
+            .copied()
+            .collect()
+    }
+}
+
+impl CompiledAllowedDuplication {
+    fn matches(&self, translation: &Translation, package_path_pattern: &PackagePathPattern) -> bool {
+        self.key_regex.as_ref().is_none_or(|key_regex| key_regex.is_match(&translation.key))
+            && self
+                .package_regex
+                .as_ref()
+                .is_none_or(|package_regex| package_regex.is_match(&package_path_pattern.package_path(&translation.path.to_string_lossy())))
+    }
+}
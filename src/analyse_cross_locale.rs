@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+use crate::collation::compare_text;
+use crate::entities::{Translation, TranslationValue};
+use crate::locales::{group_by_key_across_locales, LocaleMatcher};
+
+pub struct IdenticalAcrossLocalesReport {
+    pub folder: PathBuf,
+    pub key: String,
+    pub value: TranslationValue,
+    pub locales: Vec<String>,
+}
+
+/// Reports keys having the same value in several locales of a folder, likely untranslated copies
+pub fn analyse_identical_across_locales(
+    translations: &[&Translation],
+    locale_matcher: &LocaleMatcher,
+) -> Vec<IdenticalAcrossLocalesReport> {
+    let mut reports: Vec<IdenticalAcrossLocalesReport> = Vec::new();
+
+    for ((folder, key), localized_values) in group_by_key_across_locales(translations, locale_matcher) {
+        let mut remaining: Vec<(&String, &&Translation)> = localized_values.iter().collect();
+
+        while let Some((locale, translation)) = remaining.pop() {
+            let (identical, different): (Vec<_>, Vec<_>) = remaining
+                .into_iter()
+                .partition(|(_, other)| other.translations == translation.translations);
+            remaining = different;
+
+            if identical.is_empty() || translation.text().is_none_or(|text| text.trim().is_empty()) {
+                continue;
+            }
+
+            let mut locales: Vec<String> = identical.into_iter().map(|(locale, _)| locale.clone()).collect();
+            locales.push(locale.clone());
+            locales.sort();
+
+            reports.push(IdenticalAcrossLocalesReport {
+                folder: folder.clone(),
+                key: key.clone(),
+                value: translation.translations.clone(),
+                locales,
+            });
+        }
+    }
+
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| compare_text(&a.key, &b.key)));
+    reports
+}
+
+pub fn print_identical_across_locales_report(monorepo_path: &Path, reports: &[IdenticalAcrossLocalesReport]) {
+    println!("Identical values across locales :");
+    println!("Keys with identical values : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} : {} in {}",
+            report.folder.strip_prefix(monorepo_path).unwrap_or(&report.folder).to_string_lossy(),
+            report.key,
+            report.value,
+            report.locales.join(", ")
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::scan_source_files::SourceStringLiteral;
+use crate::settings::ValueNormalization;
+use crate::suggest_common_translations::{is_common_translation, CommonModulePath};
+
+/// Reports the keys of the common translations modules that no source file references
+/// and whose value no other package duplicates, so the shared modules can be pruned
+pub fn analyse_dead_common_translations<'a>(
+    translations: &[&'a Translation],
+    all_translations: &HashMap<String, Vec<&Translation>>,
+    literals: &[SourceStringLiteral],
+    normalization: &ValueNormalization,
+    common_modules_path: &[CommonModulePath],
+) -> Vec<&'a Translation> {
+    let referenced_keys: HashSet<&str> = literals.iter().map(|literal| literal.value.as_str()).collect();
+
+    let mut dead_translations: Vec<&'a Translation> = translations
+        .iter()
+        .filter(|translation| is_common_translation(&translation.path, common_modules_path))
+        .filter(|translation| !referenced_keys.contains(translation.key.as_str()))
+        .filter(|translation| {
+            all_translations
+                .get(&translation.translations.normalized(normalization))
+                .is_none_or(|occurrences| {
+                    occurrences.iter().all(|occurrence| is_common_translation(&occurrence.path, common_modules_path))
+                })
+        })
+        .copied()
+        .collect();
+
+    dead_translations.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| compare_text(&a.key, &b.key)));
+    dead_translations
+}
+
+pub fn print_dead_common_translations_report(monorepo_path: &Path, dead_translations: &[&Translation]) {
+    println!("Dead common translations report :");
+    println!("Common keys neither referenced nor duplicated : {}", dead_translations.len());
+
+    for translation in dead_translations {
+        println!(
+            "{} - {} : {}",
+            translation.path.strip_prefix(monorepo_path).unwrap_or(&translation.path).to_string_lossy(),
+            translation.key,
+            translation.translations
+        );
+    }
+}
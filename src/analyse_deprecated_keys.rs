@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::scan_source_files::SourceStringLiteral;
+
+#[derive(Error, Debug)]
+pub enum DeprecationsFileError {
+    #[error("Unable to read deprecations file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid deprecations file: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+}
+
+/// A deprecated key, `deadline` being an ISO date (`YYYY-MM-DD`) after which usages fail the check
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Deprecation {
+    pub key: String,
+    pub replacement: Option<String>,
+    pub deadline: Option<String>,
+}
+
+pub struct DeprecatedKeyUsage<'a> {
+    pub deprecation: &'a Deprecation,
+    pub usage: &'a SourceStringLiteral,
+    pub expired: bool,
+}
+
+/// Reads the deprecations sidecar file, a missing file meaning no deprecation
+pub fn get_deprecations(deprecations_file_path: &Path) -> Result<Vec<Deprecation>, DeprecationsFileError> {
+    match fs::read_to_string(deprecations_file_path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            DeprecationsFileError::InvalidFormat(deprecations_file_path.to_string_lossy().to_string(), e)
+        }),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(DeprecationsFileError::UnableToReadPath(
+            deprecations_file_path.to_string_lossy().to_string(),
+            e,
+        )),
+    }
+}
+
+/// Finds the source usages of deprecated keys, flagging those past their deadline
+/// A deadline that is not a valid date never expires, `validate_settings` reporting it
+pub fn analyse_deprecated_keys<'a>(
+    deprecations: &'a [Deprecation],
+    literals: &'a [SourceStringLiteral],
+    today: IsoDate,
+) -> Vec<DeprecatedKeyUsage<'a>> {
+    let deprecations_by_key: HashMap<&str, &Deprecation> =
+        deprecations.iter().map(|deprecation| (deprecation.key.as_str(), deprecation)).collect();
+
+    let mut usages: Vec<DeprecatedKeyUsage> = literals
+        .iter()
+        .filter_map(|usage| {
+            let deprecation = *deprecations_by_key.get(usage.value.as_str())?;
+            let expired = deprecation.deadline.as_deref().and_then(IsoDate::parse).is_some_and(|deadline| deadline < today);
+            Some(DeprecatedKeyUsage { deprecation, usage, expired })
+        })
+        .collect();
+
+    usages.sort_by(|a, b| a.usage.path.cmp(&b.usage.path).then(a.usage.line.cmp(&b.usage.line)));
+    usages
+}
+
+pub fn print_deprecated_keys_report(monorepo_path: &Path, usages: &[DeprecatedKeyUsage]) {
+    println!("Deprecated keys report :");
+    println!("Usages of deprecated keys : {}", usages.len());
+    println!("Usages after deadline : {}", usages.iter().filter(|usage| usage.expired).count());
+
+    for usage in usages {
+        println!(
+            "{}{}:{} - {} (replacement : {}, deadline : {})",
+            if usage.expired { "!! " } else { "" },
+            usage.usage.path.strip_prefix(monorepo_path).unwrap_or(&usage.usage.path).to_string_lossy(),
+            usage.usage.line,
+            usage.deprecation.key,
+            usage.deprecation.replacement.as_deref().unwrap_or("none"),
+            usage.deprecation.deadline.as_deref().unwrap_or("none"),
+        );
+    }
+}
+
+/// Calendar date written as `YYYY-MM-DD`, ordered chronologically
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IsoDate {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl IsoDate {
+    /// Parses a `YYYY-MM-DD` date, `None` when it is not one or the day does not exist, as `2025-02-30`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.split('-');
+        let (Some(year), Some(month), Some(day), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return None;
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 || !text.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            return None;
+        }
+
+        let date = Self { year: year.parse().ok()?, month: month.parse().ok()?, day: day.parse().ok()? };
+        let leap_year = date.year % 4 == 0 && (date.year % 100 != 0 || date.year % 400 == 0);
+        let days_in_month = match date.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap_year => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month).contains(&date.day).then_some(date)
+    }
+
+    /// Current UTC date
+    pub fn today() -> Self {
+        let days = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs() / 86_400) as i64;
+
+        // Civil date from days since epoch (Howard Hinnant's algorithm)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self { year, month: month as u32, day: day as u32 }
+    }
+}
+
+impl fmt::Display for IsoDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
@@ -0,0 +1,16 @@
+use std::path::Path;
+use crate::load_translations::DuplicateKey;
+
+pub fn print_duplicate_keys_report(monorepo_path: &Path, duplicate_keys: &[DuplicateKey]) {
+    println!("Duplicate keys report :");
+    println!("Keys defined several times in a file : {}", duplicate_keys.len());
+
+    for duplicate_key in duplicate_keys {
+        println!(
+            "{} - {} : {}",
+            duplicate_key.path.strip_prefix(monorepo_path).unwrap_or(&duplicate_key.path).to_string_lossy(),
+            duplicate_key.key,
+            duplicate_key.values.join(" / ")
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::Path;
+use serde_json::Value;
+use crate::entities::Translation;
+
+const HTML_DOCUMENT_MARKERS: [&str; 4] = ["<!doctype html", "<html", "<head", "<body"];
+
+#[derive(PartialEq, Debug)]
+pub enum EmbeddedBlobType {
+    Json,
+    HtmlDocument,
+}
+
+pub struct EmbeddedBlobReport<'a> {
+    pub translation: &'a Translation,
+    pub blob_type: EmbeddedBlobType,
+}
+
+/// Finds values that embed a JSON structure or a full HTML document
+pub fn analyse_embedded_blobs<'a>(translations: &[&'a Translation]) -> Vec<EmbeddedBlobReport<'a>> {
+    translations
+        .iter()
+        .filter_map(|translation| {
+            let text = translation.text()?;
+            detect_blob_type(text).map(|blob_type| EmbeddedBlobReport { translation, blob_type })
+        })
+        .collect()
+}
+
+pub fn detect_blob_type(text: &str) -> Option<EmbeddedBlobType> {
+    let trimmed = text.trim();
+
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && matches!(serde_json::from_str::<Value>(trimmed), Ok(Value::Object(_) | Value::Array(_)))
+    {
+        return Some(EmbeddedBlobType::Json);
+    }
+
+    let lowercase = trimmed.to_lowercase();
+    if HTML_DOCUMENT_MARKERS.iter().any(|marker| lowercase.contains(marker)) {
+        return Some(EmbeddedBlobType::HtmlDocument);
+    }
+
+    None
+}
+
+pub fn print_embedded_blobs_report(monorepo_path: &Path, reports: &[EmbeddedBlobReport]) {
+    let count_json = reports.iter().filter(|report| report.blob_type == EmbeddedBlobType::Json).count();
+    let count_html = reports.iter().filter(|report| report.blob_type == EmbeddedBlobType::HtmlDocument).count();
+
+    println!("Embedded blobs report :");
+    println!("Embedded JSON : {}", count_json);
+    println!("Embedded HTML documents : {}", count_html);
+
+    for report in reports {
+        println!(
+            "{:?} {} - {}",
+            report.blob_type,
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::collation::compare_text;
+use crate::entities::Translation;
+
+const REPLACEMENT_CHARACTER: char = '\u{fffd}';
+
+/// Characters of Windows-1252 bytes 0x80 to 0x9F, which Latin-1 leaves as control characters
+const WINDOWS_1252_HIGH_CHARACTERS: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+#[derive(Debug)]
+pub enum EncodingIssue {
+    ReplacementCharacter,
+    /// UTF-8 decoded as Windows-1252 or Latin-1, as `Ã©` for `é`, with the original text
+    DoubleEncoded(String, String),
+    ControlCharacter(char),
+}
+
+pub struct EncodingReport<'a> {
+    pub translation: &'a Translation,
+    pub issues: Vec<EncodingIssue>,
+}
+
+/// Finds replacement characters, double-encoded UTF-8 sequences and control characters
+/// other than new lines and tabulations
+pub fn detect_encoding_issues(text: &str) -> Vec<EncodingIssue> {
+    let mut issues: Vec<EncodingIssue> = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+
+    if chars.contains(&REPLACEMENT_CHARACTER) {
+        issues.push(EncodingIssue::ReplacementCharacter);
+    }
+
+    let mut index = 0;
+    while index < chars.len() {
+        match double_encoded_sequence(&chars[index..]) {
+            Some((length, decoded)) => {
+                issues.push(EncodingIssue::DoubleEncoded(chars[index..index + length].iter().collect(), decoded));
+                index += length;
+            }
+            None => {
+                let c = chars[index];
+                if c.is_control() && !matches!(c, '\n' | '\t') {
+                    issues.push(EncodingIssue::ControlCharacter(c));
+                }
+                index += 1;
+            }
+        }
+    }
+
+    issues
+}
+
+/// Length and decoded text of the UTF-8 sequence starting the chars when they are its single byte decoding
+fn double_encoded_sequence(chars: &[char]) -> Option<(usize, String)> {
+    let lead = single_byte_value(*chars.first()?)?;
+    let length = match lead {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+
+    let bytes: Vec<u8> = chars.get(..length)?.iter().map(|c| single_byte_value(*c)).collect::<Option<Vec<u8>>>()?;
+    if !bytes[1..].iter().all(|byte| (0x80..=0xBF).contains(byte)) {
+        return None;
+    }
+
+    std::str::from_utf8(&bytes).ok().map(|decoded| (length, decoded.to_string()))
+}
+
+/// Byte of a character in Windows-1252, falling back to Latin-1 for its undefined bytes
+fn single_byte_value(c: char) -> Option<u8> {
+    if let Some(position) = WINDOWS_1252_HIGH_CHARACTERS.iter().position(|high| *high == c) {
+        return Some(0x80 + position as u8);
+    }
+    u8::try_from(u32::from(c)).ok()
+}
+
+/// Reports the values with encoding issues, grouped by file
+pub fn analyse_encoding<'a>(translations: &[&'a Translation]) -> BTreeMap<PathBuf, Vec<EncodingReport<'a>>> {
+    let mut reports_by_path: BTreeMap<PathBuf, Vec<EncodingReport<'a>>> = BTreeMap::new();
+
+    for translation in translations {
+        let Some(text) = translation.text() else {
+            continue;
+        };
+        let issues = detect_encoding_issues(text);
+        if !issues.is_empty() {
+            reports_by_path.entry(translation.path.clone()).or_default().push(EncodingReport { translation, issues });
+        }
+    }
+
+    for reports in reports_by_path.values_mut() {
+        reports.sort_by(|a, b| compare_text(&a.translation.key, &b.translation.key));
+    }
+    reports_by_path
+}
+
+pub fn print_encoding_report(monorepo_path: &Path, reports_by_path: &BTreeMap<PathBuf, Vec<EncodingReport>>) {
+    println!("Encoding report :");
+    println!("Values with encoding issues : {}", reports_by_path.values().map(Vec::len).sum::<usize>());
+
+    for (path, reports) in reports_by_path {
+        println!("\n");
+        println!(" ========= {} ==========", path.strip_prefix(monorepo_path).unwrap_or(path).to_string_lossy());
+        for report in reports {
+            let issues: Vec<String> = report
+                .issues
+                .iter()
+                .map(|issue| match issue {
+                    EncodingIssue::ReplacementCharacter => "replacement character �".to_string(),
+                    EncodingIssue::DoubleEncoded(sequence, decoded) => format!("double-encoded {} for {}", sequence, decoded),
+                    EncodingIssue::ControlCharacter(c) => format!("control character U+{:04X}", u32::from(*c)),
+                })
+                .collect();
+            println!("{} : {}", report.translation.key, issues.join(", "));
+        }
+    }
+}
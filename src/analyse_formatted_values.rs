@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{translation_locale, LocaleMatcher};
+
+/// Locale key of the patterns applied to every locale
+pub const ALL_LOCALES: &str = "*";
+
+static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{[^{}]*\}\}|\{\d+\}|%(\d+\$)?[sdif@]").unwrap()
+});
+
+pub struct FormattedValueReport<'a> {
+    pub translation: &'a Translation,
+    pub locale: String,
+    pub formatted_values: Vec<String>,
+}
+
+/// Default patterns of numbers, amounts and dates formatted for a locale, indexed by locale
+pub fn default_formatted_value_patterns() -> BTreeMap<String, Vec<String>> {
+    let patterns = |patterns: &[&str]| patterns.iter().map(|pattern| pattern.to_string()).collect::<Vec<String>>();
+
+    BTreeMap::from([
+        (ALL_LOCALES.to_string(), patterns(&[
+            r"\b\d{1,2}[/.-]\d{1,2}[/.-]\d{2,4}\b",
+            r"\b\d{4}-\d{2}-\d{2}\b",
+            r"\d(?:[\d\s.,]*\d)?\s?[€$£]|[€$£]\s?\d",
+        ])),
+        ("fr_FR".to_string(), patterns(&[
+            r"\b\d{1,3}(?:[\u{a0}\u{202f} ]\d{3})+(?:,\d+)?\b",
+            r"\b\d+,\d+\b",
+        ])),
+        ("en_GB".to_string(), patterns(&[
+            r"\b\d{1,3}(?:,\d{3})+(?:\.\d+)?\b",
+        ])),
+    ])
+}
+
+/// Finds values embedding numbers or dates formatted for a locale instead of using placeholders,
+/// using the patterns of the value locale and those of every locale
+pub fn analyse_formatted_values<'a>(
+    translations: &[&'a Translation],
+    patterns: &BTreeMap<String, Vec<String>>,
+    locale_matcher: &LocaleMatcher,
+) -> Result<Vec<FormattedValueReport<'a>>, regex::Error> {
+    let compiled_patterns: BTreeMap<&str, Vec<Regex>> = patterns
+        .iter()
+        .map(|(locale, patterns)| Ok((locale.as_str(), patterns.iter().map(|pattern| Regex::new(pattern)).collect::<Result<Vec<Regex>, regex::Error>>()?)))
+        .collect::<Result<_, regex::Error>>()?;
+    let all_locales_patterns = compiled_patterns.get(ALL_LOCALES).map(Vec::as_slice).unwrap_or_default();
+
+    let mut reports: Vec<FormattedValueReport<'a>> = translations
+        .iter()
+        .filter_map(|translation| {
+            let text = translation.text()?;
+            let text = PLACEHOLDER_REGEX.replace_all(text, "");
+            let locale = translation_locale(translation, locale_matcher).unwrap_or_default();
+            let locale_patterns = compiled_patterns.get(locale.as_str()).map(Vec::as_slice).unwrap_or_default();
+
+            let mut formatted_values: Vec<String> = all_locales_patterns
+                .iter()
+                .chain(locale_patterns)
+                .flat_map(|pattern| pattern.find_iter(&text).map(|found| found.as_str().trim().to_string()))
+                .collect();
+            formatted_values.sort();
+            formatted_values.dedup();
+
+            (!formatted_values.is_empty()).then_some(FormattedValueReport { translation, locale, formatted_values })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.translation.path.cmp(&b.translation.path).then_with(|| compare_text(&a.translation.key, &b.translation.key)));
+    Ok(reports)
+}
+
+pub fn print_formatted_values_report(monorepo_path: &Path, reports: &[FormattedValueReport]) {
+    println!("Formatted values report :");
+    println!("Values embedding formatted numbers or dates : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} ({}) : {} in {}",
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key,
+            report.locale,
+            report.formatted_values.join(", "),
+            report.translation.translations
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{translation_locale, LocaleMatcher};
+
+#[derive(Error, Debug)]
+pub enum GlossaryError {
+    #[error("No glossary configured, set `glossary_file_path` in the settings")]
+    NotConfigured,
+
+    #[error("Unable to read glossary: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid glossary: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("Invalid glossary variant `{0}`: {1}")]
+    InvalidVariant(String, #[source] regex::Error),
+}
+
+/// Approved translation of a term in a locale, and the variants to replace with it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GlossaryTranslation {
+    pub approved: String,
+    pub variants: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GlossaryTerm {
+    pub term: String,
+    pub locales: BTreeMap<String, GlossaryTranslation>,
+}
+
+/// A value using a non-approved variant of a glossary term
+pub struct TerminologyReport<'a> {
+    pub translation: &'a Translation,
+    pub locale: String,
+    pub term: &'a str,
+    pub variant: String,
+    pub approved: &'a str,
+}
+
+pub fn get_glossary(glossary_file_path: &Path) -> Result<Vec<GlossaryTerm>, GlossaryError> {
+    let content = fs::read_to_string(glossary_file_path)
+        .map_err(|e| GlossaryError::UnableToReadPath(glossary_file_path.to_string_lossy().to_string(), e))?;
+
+    serde_json::from_str(&content).map_err(|e| GlossaryError::InvalidFormat(glossary_file_path.to_string_lossy().to_string(), e))
+}
+
+/// Finds the values of each locale containing a variant of a glossary term, as a whole word whatever its case
+pub fn analyse_glossary<'a>(
+    translations: &[&'a Translation],
+    glossary: &'a [GlossaryTerm],
+    locale_matcher: &LocaleMatcher,
+) -> Result<Vec<TerminologyReport<'a>>, GlossaryError> {
+    let mut variants_by_locale: BTreeMap<&str, Vec<(&'a GlossaryTerm, &'a GlossaryTranslation, Regex)>> = BTreeMap::new();
+    for term in glossary {
+        for (locale, glossary_translation) in &term.locales {
+            for variant in &glossary_translation.variants {
+                let variant_regex = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(variant)))
+                    .map_err(|e| GlossaryError::InvalidVariant(variant.clone(), e))?;
+                variants_by_locale.entry(locale).or_default().push((term, glossary_translation, variant_regex));
+            }
+        }
+    }
+
+    let mut reports: Vec<TerminologyReport<'a>> = Vec::new();
+    for translation in translations {
+        let Some(locale) = translation_locale(translation, locale_matcher) else {
+            continue;
+        };
+        let (Some(variants), Some(text)) = (variants_by_locale.get(locale.as_str()), translation.text()) else {
+            continue;
+        };
+
+        for (term, glossary_translation, variant_regex) in variants {
+            if let Some(found) = variant_regex.find(text) {
+                reports.push(TerminologyReport {
+                    translation,
+                    locale: locale.clone(),
+                    term: &term.term,
+                    variant: found.as_str().to_string(),
+                    approved: &glossary_translation.approved,
+                });
+            }
+        }
+    }
+
+    reports.sort_by(|a, b| a.translation.path.cmp(&b.translation.path).then_with(|| compare_text(&a.translation.key, &b.translation.key)));
+    Ok(reports)
+}
+
+pub fn print_glossary_report(monorepo_path: &Path, reports: &[TerminologyReport]) {
+    println!("Glossary report :");
+    println!("Values using non-approved terminology : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} ({}) : `{}` instead of `{}` for {}",
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key,
+            report.locale,
+            report.variant,
+            report.approved,
+            report.term
+        );
+    }
+}
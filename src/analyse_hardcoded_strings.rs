@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::scan_source_files::{LineIndex, SourceFile};
+
+pub const JSX_FILE_EXTENSIONS: [&str; 2] = ["jsx", "tsx"];
+
+/// Opening, self-closing and closing JSX tags and fragments, attributes holding `{}` expressions
+static JSX_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?P<open><[A-Za-z][\w.:-]*(?:\s(?:[^<>{}]|\{[^{}]*\})*)?(?P<self_closing>/)?>|<>)|(?P<close></[A-Za-z][\w.:-]*\s*>|</>)").unwrap()
+});
+
+/// `{}` expressions of a JSX text, as `{count}`
+static JSX_EXPRESSION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^{}]*\}").unwrap());
+
+static USER_FACING_ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:title|alt|placeholder|label|aria-label)="([^"]*\p{L}[^"]*)""#).unwrap()
+});
+
+pub struct HardcodedStringReport {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+}
+
+/// Finds JSX texts and user-facing attributes written as literals instead of going through translations
+pub fn analyse_hardcoded_strings(source_files: &[SourceFile]) -> Vec<HardcodedStringReport> {
+    let mut reports: Vec<HardcodedStringReport> = source_files
+        .iter()
+        .filter(|source_file| {
+            source_file
+                .path
+                .extension()
+                .is_some_and(|extension| JSX_FILE_EXTENSIONS.contains(&extension.to_string_lossy().as_ref()))
+        })
+        .flat_map(|source_file| {
+            let texts = find_jsx_texts(&source_file.content).into_iter();
+            let attributes = USER_FACING_ATTRIBUTE_REGEX.captures_iter(&source_file.content).filter_map(|captures| captures.get(1)).map(|text| (text.start(), text.as_str()));
+            let line_index = LineIndex::new(&source_file.content);
+            texts
+                .chain(attributes)
+                .map(|(start, text)| HardcodedStringReport {
+                    path: source_file.path.clone(),
+                    line: line_index.line_number(start),
+                    text: text.trim().to_string(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    reports
+}
+
+/// Texts written between JSX tags with letters outside of their `{}` expressions, with their position
+/// Tags are followed to know whether a text is inside an element, the code between two elements being left out
+fn find_jsx_texts(content: &str) -> Vec<(usize, &str)> {
+    let mut texts = Vec::new();
+    let mut depth: usize = 0;
+    let mut text_start: Option<usize> = None;
+
+    for captures in JSX_TAG_REGEX.captures_iter(content) {
+        let (tag, opens) = match (captures.name("open"), captures.name("close")) {
+            // The `<` of TypeScript generics, as `Array<string>`, and of comparisons, as `a<b`, follows an operand
+            (Some(tag), _) if content[..tag.start()].ends_with(|c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | ')' | ']')) => continue,
+            (Some(tag), _) => (tag, captures.name("self_closing").is_none()),
+            (None, Some(tag)) => (tag, false),
+            (None, None) => continue,
+        };
+
+        if let Some(start) = text_start.filter(|_| depth > 0) {
+            let text = &content[start..tag.start()];
+            let outside_expressions = JSX_EXPRESSION_REGEX.replace_all(text, "");
+            if !outside_expressions.contains(['{', '}', '<', '>']) && outside_expressions.chars().any(char::is_alphabetic) {
+                let leading_spaces = text.len() - text.trim_start().len();
+                texts.push((start + leading_spaces, text));
+            }
+        }
+
+        if captures.name("close").is_some() {
+            depth = depth.saturating_sub(1);
+        } else if opens {
+            depth += 1;
+        }
+        text_start = Some(tag.end());
+    }
+
+    texts
+}
+
+pub fn render_hardcoded_strings_report(monorepo_path: &Path, reports: &[HardcodedStringReport]) -> String {
+    let mut rendered = String::from("Hardcoded strings report :\n");
+    rendered.push_str(&format!("Hardcoded strings : {}\n", reports.len()));
+
+    for report in reports {
+        rendered.push_str(&format!(
+            "{}:{} - {}\n",
+            report.path.strip_prefix(monorepo_path).unwrap_or(&report.path).to_string_lossy(),
+            report.line,
+            report.text
+        ));
+    }
+    rendered
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+use crate::entities::Translation;
+use crate::icu_message_format::{validate_icu_message, IcuSyntaxError};
+
+pub struct IcuSyntaxReport<'a> {
+    pub translation: &'a Translation,
+    pub error: IcuSyntaxError,
+}
+
+/// Parses every string value as ICU MessageFormat and reports the syntax errors
+pub fn analyse_icu_syntax<'a>(translations: &[&'a Translation]) -> Vec<IcuSyntaxReport<'a>> {
+    translations
+        .iter()
+        .filter_map(|translation| {
+            let text = translation.text()?;
+            validate_icu_message(text).err().map(|error| IcuSyntaxReport { translation, error })
+        })
+        .collect()
+}
+
+pub fn print_icu_syntax_report(monorepo_path: &Path, reports: &[IcuSyntaxReport]) {
+    println!("ICU MessageFormat report :");
+    println!("Invalid messages : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} : {}",
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key,
+            report.error
+        );
+    }
+}
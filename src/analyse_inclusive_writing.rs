@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, BTreeSet};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::entities::Translation;
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// Locales checked, inclusive writing styles being specific to French
+const FRENCH_LOCALE_PREFIX: &str = "fr";
+
+/// Feminine endings written after the masculine word, as `utilisateur·rice` or `connecté(e)`
+const FEMININE_ENDINGS: &str = "e|es|rice|rices|trice|trices|euse|euses|ne|nes|le|les|ère|ères|ive|ives|se|ses";
+
+static INCLUSIVE_FORM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"\b(\p{{L}}+?)(·|•|\.|\(|/)({})(?:[·•.](s))?\)?(?:\b|$)", FEMININE_ENDINGS)).unwrap()
+});
+
+static WORD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\p{L}+").unwrap());
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum InclusiveWritingStyle {
+    /// `utilisateur·rice`
+    MiddleDot,
+    /// `utilisateur.rice`
+    Period,
+    /// `utilisateur(rice)`
+    Parentheses,
+    /// `utilisateur/rice`
+    Slash,
+    /// `utilisateur`, masculine only
+    Masculine,
+}
+
+pub struct InclusiveWritingCluster<'a> {
+    /// Masculine word the styles are written from
+    pub word: String,
+    pub occurrences_by_style: BTreeMap<InclusiveWritingStyle, Vec<&'a Translation>>,
+}
+
+/// Finds words written with several inclusive writing styles in French values, masculine-only forms included
+pub fn analyse_inclusive_writing<'a>(translations: &[&'a Translation], locale_matcher: &LocaleMatcher) -> Vec<InclusiveWritingCluster<'a>> {
+    let french_values: Vec<(&'a Translation, &'a str)> = translations
+        .iter()
+        .filter(|translation| {
+            translation_locale(translation, locale_matcher).is_some_and(|locale| locale.starts_with(FRENCH_LOCALE_PREFIX))
+        })
+        .filter_map(|translation| Some((*translation, translation.text()?)))
+        .collect();
+
+    let mut clusters: BTreeMap<String, BTreeMap<InclusiveWritingStyle, Vec<&'a Translation>>> = BTreeMap::new();
+    for (translation, text) in &french_values {
+        for captures in INCLUSIVE_FORM_REGEX.captures_iter(text) {
+            let style = match &captures[2] {
+                "·" | "•" => InclusiveWritingStyle::MiddleDot,
+                "." => InclusiveWritingStyle::Period,
+                "(" => InclusiveWritingStyle::Parentheses,
+                _ => InclusiveWritingStyle::Slash,
+            };
+            clusters.entry(captures[1].to_lowercase()).or_default().entry(style).or_default().push(translation);
+        }
+    }
+
+    // Masculine-only forms of the words written inclusively elsewhere
+    let inclusive_words: BTreeSet<String> = clusters.keys().cloned().collect();
+    for (translation, text) in &french_values {
+        let inclusive_spans: Vec<(usize, usize)> = INCLUSIVE_FORM_REGEX.find_iter(text).map(|found| (found.start(), found.end())).collect();
+        for word in WORD_REGEX.find_iter(text) {
+            if inclusive_spans.iter().any(|(start, end)| word.start() >= *start && word.end() <= *end) {
+                continue;
+            }
+            let lowercase_word = word.as_str().to_lowercase();
+            let singular = lowercase_word.strip_suffix('s').unwrap_or(&lowercase_word);
+            let masculine = if inclusive_words.contains(&lowercase_word) { &lowercase_word } else { singular };
+            if let Some(styles) = clusters.get_mut(masculine) {
+                styles.entry(InclusiveWritingStyle::Masculine).or_default().push(translation);
+            }
+        }
+    }
+
+    clusters
+        .into_iter()
+        .filter(|(_, occurrences_by_style)| occurrences_by_style.len() > 1)
+        .map(|(word, occurrences_by_style)| InclusiveWritingCluster { word, occurrences_by_style })
+        .collect()
+}
+
+pub fn print_inclusive_writing_report(clusters: &[InclusiveWritingCluster], package_path_pattern: &PackagePathPattern) {
+    println!("Inclusive writing report :");
+    println!("Words written with several styles : {}", clusters.len());
+
+    for cluster in clusters {
+        println!("\n");
+        println!(" ========= {} ==========", cluster.word);
+        for (style, occurrences) in &cluster.occurrences_by_style {
+            let packages: BTreeSet<String> = occurrences
+                .iter()
+                .map(|occurrence| package_path_pattern.package_path(&occurrence.path.to_string_lossy()))
+                .collect();
+            println!(
+                "{:?} : {} values in {}",
+                style,
+                occurrences.len(),
+                packages.into_iter().collect::<Vec<String>>().join(", ")
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+
+pub struct KeyCasingCollisionReport<'a> {
+    pub keys: BTreeSet<&'a str>,
+    pub packages: BTreeSet<String>,
+    pub occurrences: Vec<&'a Translation>,
+}
+
+/// Reports keys differing only by their case, within a package or across packages
+/// When a package is given, only the collisions involving one of its keys are kept
+pub fn analyse_key_casing<'a>(translations: &[&'a Translation], package_path: Option<&str>, package_path_pattern: &PackagePathPattern) -> Vec<KeyCasingCollisionReport<'a>> {
+    let mut keys_by_lowercase: BTreeMap<String, Vec<&'a Translation>> = BTreeMap::new();
+    for translation in translations {
+        keys_by_lowercase.entry(translation.key.to_lowercase()).or_default().push(translation);
+    }
+
+    let mut reports: Vec<KeyCasingCollisionReport<'a>> = keys_by_lowercase
+        .into_values()
+        .filter_map(|mut occurrences| {
+            let keys: BTreeSet<&'a str> = occurrences.iter().map(|translation| translation.key.as_str()).collect();
+            if keys.len() < 2 {
+                return None;
+            }
+
+            let packages: BTreeSet<String> = occurrences
+                .iter()
+                .map(|translation| package_path_pattern.package_path(&translation.path.to_string_lossy()))
+                .collect();
+            if package_path.is_some_and(|package_path| !packages.contains(package_path)) {
+                return None;
+            }
+
+            occurrences.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.key.cmp(&b.key)));
+            Some(KeyCasingCollisionReport { keys, packages, occurrences })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| compare_text(a.keys.first().unwrap_or(&""), b.keys.first().unwrap_or(&"")));
+    reports
+}
+
+pub fn print_key_casing_report(monorepo_path: &Path, reports: &[KeyCasingCollisionReport]) {
+    let count_across_packages = reports.iter().filter(|report| report.packages.len() > 1).count();
+
+    println!("Key casing collisions report :");
+    println!("Collisions within a package : {}", reports.len() - count_across_packages);
+    println!("Collisions across packages : {}", count_across_packages);
+
+    for report in reports {
+        println!("\n");
+        println!(" ========= {} ==========", report.keys.iter().copied().collect::<Vec<&str>>().join(" / "));
+
+        for occurrence in &report.occurrences {
+            println!(
+                "{} - {}",
+                occurrence.path.strip_prefix(monorepo_path).unwrap_or(&occurrence.path).to_string_lossy(),
+                occurrence.key
+            );
+        }
+    }
+}
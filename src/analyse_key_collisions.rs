@@ -0,0 +1,57 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::map_translations_by_project::PackagePathPattern;
+
+pub struct KeyCollisionReport<'a> {
+    pub key: String,
+    pub locale: String,
+    /// Definitions of the key, one per package
+    pub definitions: BTreeMap<String, &'a Translation>,
+}
+
+/// Reports keys defined with different values by several packages of a same locale,
+/// which overwrite each other when their bundles are merged at runtime
+pub fn analyse_key_collisions<'a>(translations: &[&'a Translation], locale_matcher: &LocaleMatcher, package_path_pattern: &PackagePathPattern) -> Vec<KeyCollisionReport<'a>> {
+    let mut definitions_by_key: BTreeMap<(String, String), BTreeMap<String, &'a Translation>> = BTreeMap::new();
+
+    for translation in translations {
+        let locale = translation_locale(translation, locale_matcher).unwrap_or_default();
+        definitions_by_key
+            .entry((translation.key.clone(), locale))
+            .or_default()
+            .entry(package_path_pattern.package_path(&translation.path.to_string_lossy()))
+            .or_insert(translation);
+    }
+
+    let mut reports: Vec<KeyCollisionReport<'a>> = definitions_by_key
+        .into_iter()
+        .filter(|(_, definitions)| {
+            let values: BTreeSet<String> = definitions.values().map(|translation| translation.translations.to_string()).collect();
+            values.len() > 1
+        })
+        .map(|((key, locale), definitions)| KeyCollisionReport { key, locale, definitions })
+        .collect();
+
+    reports.sort_by(|a, b| compare_text(&a.key, &b.key).then_with(|| a.locale.cmp(&b.locale)));
+    reports
+}
+
+pub fn print_key_collisions_report(monorepo_path: &Path, reports: &[KeyCollisionReport]) {
+    println!("Key collisions report :");
+    println!("Keys defined with different values by several packages : {}", reports.len());
+
+    for report in reports {
+        println!("\n");
+        println!(" ========= {} - {} ==========", report.key, report.locale);
+        for translation in report.definitions.values() {
+            println!(
+                "{} : {}",
+                translation.path.strip_prefix(monorepo_path).unwrap_or(&translation.path).to_string_lossy(),
+                translation.translations
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+
+pub struct KeyPrefixesReport {
+    /// Distinct keys count indexed by package path then prefix, keys without separator having an empty prefix
+    pub keys_by_package_prefix: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Prefixes used by several packages, with those packages
+    pub shared_prefixes: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Aggregates the keys of each package by their first segment, to show namespace sprawl and collisions
+pub fn analyse_key_prefixes(translations: &[&Translation], segment_separator: &str, package_path_pattern: &PackagePathPattern) -> KeyPrefixesReport {
+    let mut keys_by_package: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for translation in translations {
+        keys_by_package
+            .entry(package_path_pattern.package_path(&translation.path.to_string_lossy()))
+            .or_default()
+            .insert(&translation.key);
+    }
+
+    let mut keys_by_package_prefix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    let mut packages_by_prefix: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for (package_path, keys) in keys_by_package {
+        let prefixes = keys_by_package_prefix.entry(package_path.clone()).or_default();
+        for key in keys {
+            let prefix = match key.split_once(segment_separator) {
+                Some((prefix, _)) if !segment_separator.is_empty() => prefix,
+                _ => "",
+            };
+            *prefixes.entry(prefix.to_string()).or_default() += 1;
+            if !prefix.is_empty() {
+                packages_by_prefix.entry(prefix.to_string()).or_default().insert(package_path.clone());
+            }
+        }
+    }
+
+    let shared_prefixes = packages_by_prefix.into_iter().filter(|(_, packages)| packages.len() > 1).collect();
+
+    KeyPrefixesReport { keys_by_package_prefix, shared_prefixes }
+}
+
+pub fn print_key_prefixes_report(report: &KeyPrefixesReport) {
+    println!("Key prefixes report :");
+    println!("Prefixes shared between packages : {}", report.shared_prefixes.len());
+
+    for (package_path, prefixes) in &report.keys_by_package_prefix {
+        println!("\n");
+        println!(" ========= {} - {} prefixes ==========", package_path, prefixes.len());
+        let mut prefixes: Vec<(&String, &usize)> = prefixes.iter().collect();
+        prefixes.sort_by(|(prefix_a, count_a), (prefix_b, count_b)| count_b.cmp(count_a).then_with(|| prefix_a.cmp(prefix_b)));
+        for (prefix, count) in prefixes {
+            let shared = report.shared_prefixes.contains_key(prefix.as_str());
+            println!(
+                "{}{} : {} keys",
+                if shared { "!! " } else { "" },
+                if prefix.is_empty() { "(no prefix)" } else { prefix },
+                count
+            );
+        }
+    }
+
+    if !report.shared_prefixes.is_empty() {
+        println!("\n");
+        println!(" ========= Shared prefixes ==========");
+        for (prefix, packages) in &report.shared_prefixes {
+            println!("{} : {}", prefix, packages.iter().cloned().collect::<Vec<String>>().join(", "));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{group_by_key_across_locales, LocaleMatcher};
+
+/// Values shorter than this, in both locales, are too short for a meaningful length ratio
+const MIN_COMPARED_LENGTH: usize = 8;
+
+pub struct LengthAnomalyReport {
+    pub folder: PathBuf,
+    pub key: String,
+    pub reference_value: String,
+    pub locale: String,
+    pub value: String,
+    /// Length of the value divided by the length of the reference value
+    pub ratio: f64,
+}
+
+/// Reports values drastically longer or shorter than the reference locale value of the same key,
+/// `max_ratio` bounding the accepted ratio in both directions
+pub fn analyse_length_anomalies(
+    translations: &[&Translation],
+    locale_matcher: &LocaleMatcher,
+    reference_locale: &str,
+    max_ratio: f64,
+) -> Vec<LengthAnomalyReport> {
+    let mut reports: Vec<LengthAnomalyReport> = Vec::new();
+
+    for ((folder, key), localized_values) in group_by_key_across_locales(translations, locale_matcher) {
+        let Some(reference_value) = localized_values.get(reference_locale).and_then(|translation| translation.text()) else {
+            continue;
+        };
+        let reference_length = reference_value.chars().count();
+
+        for (locale, translation) in &localized_values {
+            if locale == reference_locale {
+                continue;
+            }
+            let Some(value) = translation.text() else {
+                continue;
+            };
+            let length = value.chars().count();
+            if reference_length.max(length) < MIN_COMPARED_LENGTH {
+                continue;
+            }
+
+            let ratio = length as f64 / reference_length.max(1) as f64;
+            if ratio > max_ratio || ratio * max_ratio < 1.0 {
+                reports.push(LengthAnomalyReport {
+                    folder: folder.clone(),
+                    key: key.clone(),
+                    reference_value: reference_value.to_string(),
+                    locale: locale.clone(),
+                    value: value.to_string(),
+                    ratio,
+                });
+            }
+        }
+    }
+
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| compare_text(&a.key, &b.key)).then_with(|| a.locale.cmp(&b.locale)));
+    reports
+}
+
+pub fn print_length_anomalies_report(monorepo_path: &Path, reference_locale: &str, reports: &[LengthAnomalyReport]) {
+    println!("Length anomaly report :");
+    println!("Values with an unexpected length compared to {} : {}", reference_locale, reports.len());
+
+    for report in reports {
+        println!("\n");
+        println!(
+            " ========= {} - {} ==========",
+            report.folder.strip_prefix(monorepo_path).unwrap_or(&report.folder).to_string_lossy(),
+            report.key
+        );
+        println!("{} : {}", reference_locale, report.reference_value);
+        println!("{} (x{:.2}) : {}", report.locale, report.ratio, report.value);
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use crate::locales::capture_locale;
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// Language, then optional script and region subtags, separated by `_` or `-`
+static LOCALE_SHAPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[a-zA-Z]{2,3}([_-]([a-zA-Z]{4}|[a-zA-Z]{2}|[0-9]{3}))*$").unwrap()
+});
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocaleAmbiguityKind {
+    /// A bare language next to regional variants of it, as `fr` with `fr_FR`
+    LanguageAndRegions,
+    /// Spellings of one locale differing by case or separator, as `fr_FR` with `fr-fr`
+    SpellingVariants,
+    /// A capture group match not shaped as a locale, the regex probably matching more files than intended
+    UnexpectedCapture,
+}
+
+/// Locales captured in a package by `locales_file_regex` that may not be what the regex author meant
+#[derive(Serialize, Debug)]
+pub struct LocaleAmbiguity {
+    pub package_path: String,
+    pub kind: LocaleAmbiguityKind,
+    pub locales: BTreeSet<String>,
+}
+
+impl LocaleAmbiguity {
+    /// What the analyses do with the locales involved
+    pub fn resolution(&self) -> &'static str {
+        match self.kind {
+            LocaleAmbiguityKind::LanguageAndRegions | LocaleAmbiguityKind::SpellingVariants => "kept as separate locales",
+            LocaleAmbiguityKind::UnexpectedCapture => "used as a locale",
+        }
+    }
+}
+
+impl fmt::Display for LocaleAmbiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            LocaleAmbiguityKind::LanguageAndRegions => "language next to its regional variants",
+            LocaleAmbiguityKind::SpellingVariants => "spellings of a same locale",
+            LocaleAmbiguityKind::UnexpectedCapture => "not shaped as a locale",
+        };
+        let locales: Vec<&str> = self.locales.iter().map(String::as_str).collect();
+        write!(f, "{} : {} ({}), {}", self.package_path, locales.join(", "), description, self.resolution())
+    }
+}
+
+/// Finds, in each package, the captured locales that are ambiguous between themselves or not locales at all
+/// `locale_capture_group` is the capture group of the locale in the regex, as the setting of the same name
+pub fn analyse_locale_ambiguities<'a>(paths: impl IntoIterator<Item = &'a Path>, locale_regex: &Regex, locale_capture_group: Option<&str>, package_path_pattern: &PackagePathPattern) -> Vec<LocaleAmbiguity> {
+    let mut locales_by_package: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for path in paths {
+        if let Some(locale) = capture_locale(path, locale_regex, locale_capture_group) {
+            locales_by_package.entry(package_path_pattern.package_path(&path.to_string_lossy())).or_default().insert(locale);
+        }
+    }
+
+    let mut ambiguities: Vec<LocaleAmbiguity> = Vec::new();
+    for (package_path, locales) in locales_by_package {
+        let mut ambiguity = |kind: LocaleAmbiguityKind, locales: BTreeSet<String>| {
+            ambiguities.push(LocaleAmbiguity { package_path: package_path.clone(), kind, locales });
+        };
+
+        let (locale_shaped, unexpected): (BTreeSet<String>, BTreeSet<String>) =
+            locales.into_iter().partition(|locale| LOCALE_SHAPE_REGEX.is_match(locale));
+        for locale in unexpected {
+            ambiguity(LocaleAmbiguityKind::UnexpectedCapture, BTreeSet::from([locale]));
+        }
+
+        let mut spellings: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for locale in &locale_shaped {
+            spellings.entry(locale.to_lowercase().replace('-', "_")).or_default().insert(locale.clone());
+        }
+        for (normalized_locale, variants) in &spellings {
+            if variants.len() > 1 {
+                ambiguity(LocaleAmbiguityKind::SpellingVariants, variants.clone());
+            }
+
+            if !normalized_locale.contains('_') {
+                let regional_locales: BTreeSet<String> = spellings
+                    .iter()
+                    .filter(|(other, _)| other.strip_prefix(normalized_locale.as_str()).is_some_and(|rest| rest.starts_with('_')))
+                    .flat_map(|(_, other_variants)| other_variants.iter().cloned())
+                    .collect();
+                if !regional_locales.is_empty() {
+                    ambiguity(LocaleAmbiguityKind::LanguageAndRegions, variants.iter().cloned().chain(regional_locales).collect());
+                }
+            }
+        }
+    }
+
+    ambiguities
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{group_by_key_across_locales, LocaleMatcher};
+
+static MARKUP_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<\s*(/?)\s*([a-zA-Z][a-zA-Z0-9-]*)[^<>]*?(/?)\s*>").unwrap()
+});
+
+pub struct MarkupTagMismatchReport {
+    pub folder: PathBuf,
+    pub key: String,
+    pub tags_by_locale: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+/// Counts the tags of a value, as `<strong>`, `</strong>` or `<br/>`, attributes being ignored
+pub fn extract_markup_tags(text: &str) -> BTreeMap<String, usize> {
+    let mut tags: BTreeMap<String, usize> = BTreeMap::new();
+
+    for captures in MARKUP_TAG_REGEX.captures_iter(text) {
+        let tag = format!("<{}{}{}>", &captures[1], captures[2].to_lowercase(), &captures[3]);
+        *tags.entry(tag).or_default() += 1;
+    }
+
+    tags
+}
+
+/// Reports keys whose markup tags differ between the locales of a same folder
+pub fn analyse_markup_tags(translations: &[&Translation], locale_matcher: &LocaleMatcher) -> Vec<MarkupTagMismatchReport> {
+    let mut reports: Vec<MarkupTagMismatchReport> = group_by_key_across_locales(translations, locale_matcher)
+        .into_iter()
+        .filter(|(_, localized_values)| localized_values.len() > 1)
+        .filter_map(|((folder, key), localized_values)| {
+            let tags_by_locale: BTreeMap<String, BTreeMap<String, usize>> = localized_values
+                .into_iter()
+                .map(|(locale, translation)| (locale, extract_markup_tags(translation.text().unwrap_or_default())))
+                .collect();
+
+            let mut tag_sets = tags_by_locale.values();
+            let first = tag_sets.next()?;
+            tag_sets
+                .any(|tags| tags != first)
+                .then_some(MarkupTagMismatchReport { folder, key, tags_by_locale })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| compare_text(&a.key, &b.key)));
+    reports
+}
+
+pub fn print_markup_tags_report(monorepo_path: &Path, reports: &[MarkupTagMismatchReport]) {
+    println!("Markup tag mismatch report :");
+    println!("Keys with mismatching markup tags : {}", reports.len());
+
+    for report in reports {
+        println!("\n");
+        println!(
+            " ========= {} - {} ==========",
+            report.folder.strip_prefix(monorepo_path).unwrap_or(&report.folder).to_string_lossy(),
+            report.key
+        );
+        for (locale, tags) in &report.tags_by_locale {
+            let tags: Vec<String> = tags
+                .iter()
+                .map(|(tag, count)| if *count > 1 { format!("{} x{}", tag, count) } else { tag.clone() })
+                .collect();
+            println!("{} : {}", locale, tags.join(", "));
+        }
+    }
+}
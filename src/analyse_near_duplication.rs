@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::path::Path;
+use rayon::prelude::*;
+use crate::collation::compare_text;
+use crate::cluster_id::{cluster_id, translation_cluster_id};
+use crate::entities::Translation;
+use crate::normalize_value::normalize_text;
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::render_diff::{diff_chars, render_diff, DiffFormat};
+use crate::settings::{SimilarityAlgorithm, ValueNormalization};
+
+pub struct NearDuplicationReport<'a> {
+    pub translation: &'a Translation,
+    pub similar_value: &'a str,
+    pub similarity: f64,
+    pub similar_translations: &'a [&'a Translation],
+    pub cluster_id: String,
+    pub similar_cluster_id: String,
+}
+
+/// Compares every normalized value of the project against all other distinct values of the monorepo
+/// Returns the pairs whose similarity reaches the threshold without being exact duplicates
+pub fn analyse_near_duplication<'a>(
+    translations_to_check: &[&'a Translation],
+    all_translations: &'a HashMap<String, Vec<&'a Translation>>,
+    algorithm: SimilarityAlgorithm,
+    threshold: f64,
+    normalization: &ValueNormalization,
+    locale_matcher: &LocaleMatcher,
+) -> Vec<NearDuplicationReport<'a>> {
+    let candidates: Vec<(String, &'a str, &'a [&'a Translation])> = all_translations
+        .iter()
+        .filter_map(|(value, translations)| {
+            serde_json::from_str::<String>(value)
+                .ok()
+                .map(|text| (text, value.as_str(), translations.as_slice()))
+        })
+        .collect();
+
+    let mut reports: Vec<NearDuplicationReport<'a>> = translations_to_check
+        .par_iter()
+        .flat_map_iter(|translation| {
+            let text = translation.text().map(|text| normalize_text(text, normalization)).unwrap_or_default();
+            let translation_cluster = translation_cluster_id(translation, normalization, locale_matcher);
+            candidates
+                .iter()
+                .filter(|(candidate, _, _)| !text.is_empty() && *candidate != text)
+                .filter_map(|(candidate, similar_value, similar_translations)| {
+                    let similarity = compute_similarity(algorithm, &text, candidate);
+                    if similarity < threshold {
+                        return None;
+                    }
+                    let similar_locale = similar_translations
+                        .first()
+                        .and_then(|similar| translation_locale(similar, locale_matcher))
+                        .unwrap_or_default();
+                    Some(NearDuplicationReport {
+                        translation,
+                        similar_value,
+                        similarity,
+                        similar_translations,
+                        cluster_id: translation_cluster.clone(),
+                        similar_cluster_id: cluster_id(similar_value, &similar_locale),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    reports.sort_by(|a, b| {
+        compare_text(&a.translation.key, &b.translation.key).then(b.similarity.total_cmp(&a.similarity))
+    });
+
+    reports
+}
+
+pub fn compute_similarity(algorithm: SimilarityAlgorithm, a: &str, b: &str) -> f64 {
+    match algorithm {
+        SimilarityAlgorithm::JaroWinkler => strsim::jaro_winkler(a, b),
+        SimilarityAlgorithm::Levenshtein => strsim::normalized_levenshtein(a, b),
+    }
+}
+
+pub fn render_near_duplication_report(monorepo_path: &Path, reports: &[NearDuplicationReport], diff_format: DiffFormat) -> String {
+    let mut rendered = String::from("Near-duplication report :\n");
+    rendered.push_str(&format!("Near-duplicates : {}\n", reports.len()));
+
+    for report in reports {
+        rendered.push_str("\n\n");
+        rendered.push_str(&format!(
+            " ========= Similarity : {:.2} - {} - cluster #{} ==========\n",
+            report.similarity, report.translation.key, report.cluster_id
+        ));
+        rendered.push_str(&format!(" ========= {} ==========\n", report.translation.translations));
+        rendered.push_str(&format!(" ========= {} - cluster #{} ==========\n", report.similar_value, report.similar_cluster_id));
+        rendered.push_str(&format!(
+            " ========= Diff : {} ==========\n",
+            render_diff(&diff_chars(report.translation.text().unwrap_or_default(), &serde_json::from_str::<String>(report.similar_value).unwrap_or_default()), diff_format)
+        ));
+
+        for similar_translation in report.similar_translations {
+            rendered.push_str(&format!(
+                "{} - {}\n",
+                similar_translation.path.strip_prefix(monorepo_path).unwrap_or(&similar_translation.path).to_string_lossy(),
+                similar_translation.key
+            ));
+        }
+    }
+    rendered
+}
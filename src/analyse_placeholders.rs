@@ -0,0 +1,87 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::locales::{group_by_key_across_locales, LocaleMatcher};
+
+static DOUBLE_BRACES_PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").unwrap()
+});
+
+static SINGLE_BRACE_PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\s*(\w+)\s*\}").unwrap()
+});
+
+static PRINTF_PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"%(\d+\$)?[sdif@]").unwrap()
+});
+
+pub struct PlaceholderMismatchReport {
+    pub folder: PathBuf,
+    pub key: String,
+    pub placeholders_by_locale: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Extracts `{{name}}`, `{0}` / `{name}` and `%s` style placeholders of a value
+pub fn extract_placeholders(text: &str) -> BTreeSet<String> {
+    let mut placeholders = BTreeSet::new();
+
+    for captures in DOUBLE_BRACES_PLACEHOLDER_REGEX.captures_iter(text) {
+        placeholders.insert(format!("{{{{{}}}}}", &captures[1]));
+    }
+
+    let without_double_braces = DOUBLE_BRACES_PLACEHOLDER_REGEX.replace_all(text, "");
+    for captures in SINGLE_BRACE_PLACEHOLDER_REGEX.captures_iter(&without_double_braces) {
+        placeholders.insert(format!("{{{}}}", &captures[1]));
+    }
+
+    for found in PRINTF_PLACEHOLDER_REGEX.find_iter(text) {
+        placeholders.insert(found.as_str().to_string());
+    }
+
+    placeholders
+}
+
+/// Reports keys whose placeholders differ between the locales of a same folder
+pub fn analyse_placeholders(translations: &[&Translation], locale_matcher: &LocaleMatcher) -> Vec<PlaceholderMismatchReport> {
+    let mut reports: Vec<PlaceholderMismatchReport> = group_by_key_across_locales(translations, locale_matcher)
+        .into_iter()
+        .filter(|(_, localized_values)| localized_values.len() > 1)
+        .filter_map(|((folder, key), localized_values)| {
+            let placeholders_by_locale: BTreeMap<String, BTreeSet<String>> = localized_values
+                .into_iter()
+                .map(|(locale, translation)| {
+                    (locale, extract_placeholders(translation.text().unwrap_or_default()))
+                })
+                .collect();
+
+            let mut placeholder_sets = placeholders_by_locale.values();
+            let first = placeholder_sets.next()?;
+            placeholder_sets
+                .any(|placeholders| placeholders != first)
+                .then_some(PlaceholderMismatchReport { folder, key, placeholders_by_locale })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| compare_text(&a.key, &b.key)));
+    reports
+}
+
+pub fn print_placeholders_report(monorepo_path: &Path, reports: &[PlaceholderMismatchReport]) {
+    println!("Placeholder mismatch report :");
+    println!("Keys with mismatching placeholders : {}", reports.len());
+
+    for report in reports {
+        println!("\n");
+        println!(
+            " ========= {} - {} ==========",
+            report.folder.strip_prefix(monorepo_path).unwrap_or(&report.folder).to_string_lossy(),
+            report.key
+        );
+        for (locale, placeholders) in &report.placeholders_by_locale {
+            println!("{} : {}", locale, placeholders.iter().cloned().collect::<Vec<String>>().join(", "));
+        }
+    }
+}
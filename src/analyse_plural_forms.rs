@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::i18next_keys::I18nextKeys;
+use crate::icu_message_format::{extract_plural_blocks, PLURAL_KEYWORDS};
+use crate::locales::{translation_locale, LocaleMatcher};
+
+pub enum PluralSource {
+    /// i18next `key_one` / `key_other` keys, the family being named after their base key
+    SuffixedKeys,
+    /// ICU `{count, plural, ...}` block of the value
+    IcuBlock,
+}
+
+pub struct MissingPluralFormsReport {
+    pub path: PathBuf,
+    pub key: String,
+    pub locale: String,
+    pub source: PluralSource,
+    pub missing_forms: Vec<&'static str>,
+}
+
+/// CLDR cardinal plural categories of a language, the `many` form of compact decimals
+/// (French, Spanish, Italian...) being left out since i18n libraries rarely require it
+pub fn required_plural_forms(language: &str) -> &'static [&'static str] {
+    match language {
+        "ja" | "zh" | "ko" | "vi" | "id" | "ms" | "th" | "lo" | "my" => &["other"],
+        "ro" | "hr" | "sr" | "bs" => &["one", "few", "other"],
+        "pl" | "ru" | "uk" | "be" | "cs" | "sk" | "lt" => &["one", "few", "many", "other"],
+        "sl" => &["one", "two", "few", "other"],
+        "he" => &["one", "two", "other"],
+        "lv" => &["zero", "one", "other"],
+        "ga" => &["one", "two", "few", "many", "other"],
+        "ar" | "cy" => &["zero", "one", "two", "few", "many", "other"],
+        _ => &["one", "other"],
+    }
+}
+
+/// Reports i18next plural key families and ICU plural blocks missing a form required by the language of their locale
+/// Ordinal families (`key_ordinal_one`) are left out, their categories not being the cardinal ones
+pub fn analyse_plural_forms(translations: &[&Translation], locale_matcher: &LocaleMatcher, i18next_keys: &I18nextKeys) -> Vec<MissingPluralFormsReport> {
+    let mut reports: Vec<MissingPluralFormsReport> = Vec::new();
+    let mut families: BTreeMap<(&Path, String, String), BTreeSet<&str>> = BTreeMap::new();
+
+    for translation in translations {
+        let Some(locale) = translation_locale(translation, locale_matcher) else {
+            continue;
+        };
+        let required_forms = required_plural_forms(language(&locale));
+
+        if let Some(form) = i18next_keys.parse(&translation.key).plural_form
+            && let Some(form) = PLURAL_KEYWORDS.iter().find(|keyword| **keyword == form)
+        {
+            // The family keeps the context of its keys, `friend_male_one` and `friend_female_one` being separate families
+            let family_key = &translation.key[..translation.key.len() - form.len() - 1];
+            families.entry((&translation.path, locale.clone(), family_key.to_string())).or_default().insert(form);
+        }
+
+        let plural_blocks = translation.text().and_then(|text| extract_plural_blocks(text).ok()).unwrap_or_default();
+        for cases in plural_blocks {
+            let missing_forms = missing_forms(required_forms, |form| cases.contains(form));
+            if !missing_forms.is_empty() {
+                reports.push(MissingPluralFormsReport {
+                    path: translation.path.clone(),
+                    key: translation.key.clone(),
+                    locale: locale.clone(),
+                    source: PluralSource::IcuBlock,
+                    missing_forms,
+                });
+            }
+        }
+    }
+
+    for ((path, locale, base_key), forms) in families {
+        // A lone `_other` key is more likely a regular key than a plural family
+        if forms.len() == 1 && forms.contains("other") {
+            continue;
+        }
+        let missing_forms = missing_forms(required_plural_forms(language(&locale)), |form| forms.contains(form));
+        if !missing_forms.is_empty() {
+            reports.push(MissingPluralFormsReport {
+                path: path.to_path_buf(),
+                key: base_key,
+                locale,
+                source: PluralSource::SuffixedKeys,
+                missing_forms,
+            });
+        }
+    }
+
+    reports.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| compare_text(&a.key, &b.key)));
+    reports
+}
+
+fn language(locale: &str) -> &str {
+    locale.split(['_', '-']).next().unwrap_or(locale)
+}
+
+fn missing_forms(required_forms: &[&'static str], has_form: impl Fn(&str) -> bool) -> Vec<&'static str> {
+    required_forms.iter().copied().filter(|form| !has_form(form)).collect()
+}
+
+pub fn print_plural_forms_report(monorepo_path: &Path, reports: &[MissingPluralFormsReport]) {
+    println!("Plural forms report :");
+    println!("Plurals missing required forms : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} ({}, {}) : missing {}",
+            report.path.strip_prefix(monorepo_path).unwrap_or(&report.path).to_string_lossy(),
+            report.key,
+            report.locale,
+            match report.source {
+                PluralSource::SuffixedKeys => "suffixed keys",
+                PluralSource::IcuBlock => "ICU plural",
+            },
+            report.missing_forms.join(", ")
+        );
+    }
+}
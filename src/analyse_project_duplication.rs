@@ -1,50 +1,203 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::allowed_duplications::AllowedDuplications;
+use crate::auto_triage::TriageTag;
+use crate::cluster_id::translation_cluster_id;
 use crate::entities::Translation;
+use crate::i18next_keys::I18nextKeys;
+use crate::map_translations_by_project::{map_translations_by_project, PackagePathPattern};
+use crate::package_dependencies::PackageDependencies;
+use crate::progress::{ProgressEvent, ProgressListener};
+use crate::suggest_common_translations::{find_common_module, CommonModulePath};
+use crate::settings::ValueNormalization;
+use crate::test_fixtures::{TestFixtureMode, TestFixtures};
+use crate::trace::trace_span;
+use crate::locales::LocaleMatcher;
 
-#[derive(PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum DuplicationType {
     InterPackage,
     CommonTranslation,
-    ExternalProjects
+    ExternalProjects,
+    /// Duplicated into or from test fixtures only
+    TestFixture,
 }
 pub struct DuplicationReport<'a> {
-    pub(crate) translation: &'a Translation,
-    pub(crate) duplication_type: DuplicationType
+    pub translation: &'a Translation,
+    pub duplication_type: DuplicationType,
+    pub cluster_id: String,
+    /// Key the duplicated value should be used under: the common translation key when there is one,
+    /// then the key in a package it depends on, the most used key of the value otherwise
+    pub canonical_key: String,
+    /// Estimated size of the duplication, as the value length times its occurrences
+    pub savings: usize,
+    /// Package the translation's package depends on which already has the value, whose key could be reused today
+    pub dependency_package: Option<String>,
+    /// Package path of the common translations module having the value, for common translation duplications
+    pub common_module: Option<String>,
+    /// Tag given by the first matching triage rule of the settings, set after the analysis
+    pub triage: Option<TriageTag>,
 }
 
-pub fn analyse_duplication<'a>(project_path: &str, translations_to_check: &[&'a Translation], all_translations: &HashMap<String, Vec<&Translation>>) -> Vec<DuplicationReport<'a>> {
+#[allow(clippy::too_many_arguments)]
+pub fn analyse_duplication<'a>(project_path: &str, translations_to_check: &[&'a Translation], all_translations: &HashMap<String, Vec<&Translation>>, normalization: &ValueNormalization, allowed_duplications: &AllowedDuplications, dependencies: &PackageDependencies, common_modules_path: &[CommonModulePath], test_fixtures: &TestFixtures, i18next_keys: &I18nextKeys, locale_matcher: &LocaleMatcher, package_path_pattern: &PackagePathPattern) -> Vec<DuplicationReport<'a>> {
     let mut duplications: Vec<DuplicationReport<'a>> = Vec::new();
     for translation in translations_to_check {
-        let translations_found = all_translations.get(&translation.translations).unwrap();
+        let own_fixture = test_fixtures.is_fixture(&translation.path);
+        if own_fixture && test_fixtures.mode == TestFixtureMode::Exclude {
+            continue
+        }
+        let normalized_value = translation.translations.normalized(normalization);
+        // Translations missing from the index have no occurrence to be compared with
+        let Some(occurrences) = all_translations.get(&normalized_value) else {
+            continue
+        };
+        let mut occurrences_found = allowed_duplications.filter_occurrences(translation, &normalized_value, occurrences, package_path_pattern);
+        // Plural and context variants of a key often share a value, as `item_one` and `item_other` in Japanese
+        occurrences_found.retain(|occurrence| std::ptr::eq(*occurrence, *translation) || !i18next_keys.are_variants(translation, occurrence));
+
+        if occurrences_found.len() == 1 {
+            continue
+        }
 
-        if translations_found.len() == 1 {
+        let cluster_id = translation_cluster_id(translation, normalization, locale_matcher);
+        if allowed_duplications.is_allowlisted(translation, &normalized_value, &cluster_id) {
             continue
         }
+        let value_length = translation.text().map_or(translation.translations.to_string().chars().count(), |text| text.chars().count());
+
+        // Copies in test fixtures do not make a value duplicated, a fixture being a duplication of its own kind
+        let translations_found: Vec<&Translation> = occurrences_found.iter().copied().filter(|t| !test_fixtures.is_fixture(&t.path)).collect();
+        if own_fixture || translations_found.len() == 1 {
+            if test_fixtures.mode == TestFixtureMode::Report {
+                let canonical_key = most_used_key(&occurrences_found).to_string();
+                duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::TestFixture, cluster_id, canonical_key, savings: value_length * occurrences_found.len(), dependency_package: None, common_module: None, triage: None });
+            }
+            continue
+        }
+
+        let savings = value_length * translations_found.len();
+        let dependency_translation = translations_found.iter().find(|t| dependencies.depends_on(project_path, &package_path_pattern.package_path(&t.path.to_string_lossy())));
+        let dependency_package = dependency_translation.map(|t| package_path_pattern.package_path(&t.path.to_string_lossy()));
+        let common_translation = translations_found.iter().find_map(|t| find_common_module(&t.path, common_modules_path).map(|common_module| (t, common_module)));
 
-        if translations_found.iter().find(|t| t.path.to_string_lossy().to_string().contains("common-translations")).is_some() {
-            duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::CommonTranslation });
+        if let Some((common_translation, common_module)) = common_translation {
+            let common_module = Some(common_module.configured.clone());
+            duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::CommonTranslation, cluster_id, canonical_key: common_translation.key.clone(), savings, dependency_package, common_module, triage: None });
             continue
         }
 
+        let canonical_key = dependency_translation.map_or_else(|| most_used_key(&translations_found), |t| t.key.as_str()).to_string();
+
         if translations_found.iter().filter(|t| t.path.to_string_lossy().to_string().contains(project_path)).count() > 1 {
-            duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::InterPackage });
+            duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::InterPackage, cluster_id, canonical_key, savings, dependency_package, common_module: None, triage: None });
             continue
         }
 
-    duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::ExternalProjects });
+    duplications.push(DuplicationReport { translation, duplication_type: DuplicationType::ExternalProjects, cluster_id, canonical_key, savings, dependency_package, common_module: None, triage: None });
     }
 
     duplications
 }
 
+/// Most used key of the occurrences, the shortest then first in alphabetical order on ties
+pub fn most_used_key<'a>(occurrences: &[&'a Translation]) -> &'a str {
+    let mut key_usages: HashMap<&str, usize> = HashMap::new();
+    for occurrence in occurrences {
+        *key_usages.entry(occurrence.key.as_str()).or_default() += 1;
+    }
+
+    key_usages
+        .into_iter()
+        .max_by(|(key_a, count_a), (key_b, count_b)| {
+            count_a.cmp(count_b).then_with(|| key_b.len().cmp(&key_a.len())).then_with(|| key_b.cmp(key_a))
+        })
+        .map_or("", |(key, _)| key)
+}
+
+/// Duplications of every package, or of the given one only, ordered by package path
+#[allow(clippy::too_many_arguments)]
+pub fn analyse_packages_duplication<'a>(translations: &'a [Translation], all_translations: &HashMap<String, Vec<&Translation>>, package_path: Option<&str>, normalization: &ValueNormalization, allowed_duplications: &AllowedDuplications, dependencies: &PackageDependencies, common_modules_path: &[CommonModulePath], test_fixtures: &TestFixtures, i18next_keys: &I18nextKeys, locale_matcher: &LocaleMatcher, package_path_pattern: &PackagePathPattern, progress: &dyn ProgressListener) -> BTreeMap<String, Vec<DuplicationReport<'a>>> {
+    let projects: Vec<(String, Vec<&Translation>)> = map_translations_by_project(translations, package_path_pattern)
+        .into_iter()
+        .filter(|(project_path, _)| package_path.is_none_or(|package_path| package_path == project_path.as_str()))
+        .collect();
+    let total = projects.len();
+    let mut findings = 0;
+
+    projects
+        .into_iter()
+        .enumerate()
+        .map(|(index, (project_path, project_translations))| {
+            let span = trace_span("analyze", project_path.as_str(), json!({ "translations": project_translations.len() }));
+            let duplications = analyse_duplication(&project_path, &project_translations, all_translations, normalization, allowed_duplications, dependencies, common_modules_path, test_fixtures, i18next_keys, locale_matcher, package_path_pattern);
+            drop(span);
+            findings += duplications.len();
+            progress.on_progress(ProgressEvent::PackageAnalyzed { package_path: project_path.clone(), analyzed: index + 1, total, findings });
+            (project_path, duplications)
+        })
+        .collect()
+}
+
+/// Duplication counts by type, shared by every output format
+#[derive(Serialize, Default, Clone, Copy, Debug)]
+pub struct DuplicationSummary {
+    pub inter_package: usize,
+    pub common_translation: usize,
+    pub external_projects: usize,
+    pub test_fixture: usize,
+}
+
+impl DuplicationSummary {
+    pub fn from_reports(duplications: &[DuplicationReport]) -> Self {
+        let count = |duplication_type: DuplicationType| duplications.iter().filter(|duplication| duplication.duplication_type == duplication_type).count();
+
+        Self {
+            inter_package: count(DuplicationType::InterPackage),
+            common_translation: count(DuplicationType::CommonTranslation),
+            external_projects: count(DuplicationType::ExternalProjects),
+            test_fixture: count(DuplicationType::TestFixture),
+        }
+    }
+
+    pub fn add(&mut self, other: &DuplicationSummary) {
+        self.inter_package += other.inter_package;
+        self.common_translation += other.common_translation;
+        self.external_projects += other.external_projects;
+        self.test_fixture += other.test_fixture;
+    }
+
+    /// Duplications to fix, test fixture ones being left out
+    pub fn total(&self) -> usize {
+        self.inter_package + self.common_translation + self.external_projects
+    }
+}
+
 pub fn print_global_duplication_report(duplications: &[DuplicationReport]) {
-    let count_inter_duplication = duplications.iter().filter(|duplication| duplication.duplication_type == DuplicationType::InterPackage).count();
-    let count_common_duplication = duplications.iter().filter(|duplication| duplication.duplication_type == DuplicationType::CommonTranslation).count();
-    let count_external_duplication = duplications.iter().filter(|duplication| duplication.duplication_type == DuplicationType::ExternalProjects).count();
-
-    println!("Global duplication report :");
-    println!("Inter-package duplication : {}", count_inter_duplication);
-    println!("Common-translation duplication : {}", count_common_duplication);
-    println!("External-projects duplication : {}", count_external_duplication);
-    println!("Total duplication : {}", count_inter_duplication + count_common_duplication + count_external_duplication);
-}
\ No newline at end of file
+    print!("{}", render_global_duplication_report(duplications));
+}
+
+pub fn render_global_duplication_report(duplications: &[DuplicationReport]) -> String {
+    let summary = DuplicationSummary::from_reports(duplications);
+
+    let mut report = String::from("Global duplication report :\n");
+    report.push_str(&format!("Inter-package duplication : {}\n", summary.inter_package));
+    report.push_str(&format!("Common-translation duplication : {}\n", summary.common_translation));
+    let mut by_common_module: BTreeMap<&str, usize> = BTreeMap::new();
+    for common_module in duplications.iter().filter_map(|duplication| duplication.common_module.as_deref()) {
+        *by_common_module.entry(common_module).or_default() += 1;
+    }
+    if by_common_module.len() > 1 {
+        for (common_module, count) in by_common_module {
+            report.push_str(&format!("  {} : {}\n", common_module, count));
+        }
+    }
+    report.push_str(&format!("External-projects duplication : {}\n", summary.external_projects));
+    report.push_str(&format!("Total duplication : {}\n", summary.total()));
+    if summary.test_fixture > 0 {
+        report.push_str(&format!("Test-fixture duplication (not counted) : {}\n", summary.test_fixture));
+    }
+    report
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::analyse_embedded_blobs::detect_blob_type;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::render_diff::{diff_chars, render_diff, DiffFormat};
+use crate::safe_write::WriteSession;
+use crate::translation_file::{read_translation_entries, serialize_translation_entries};
+
+const APOSTROPHES: [char; 2] = ['\'', '’'];
+const OPENING_QUOTES: [char; 3] = ['«', '“', '„'];
+const CLOSING_QUOTES: [char; 2] = ['»', '”'];
+
+/// Markup tags and `{{interpolations}}`, whose quotes are syntax rather than typography
+static SYNTAX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^<>]*>|\{\{[^{}]*\}\}").unwrap());
+
+/// Typographic characters expected in the values of a locale
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QuoteStyle {
+    pub apostrophe: char,
+    pub opening_quote: char,
+    pub closing_quote: char,
+}
+
+pub struct QuoteStyleReport<'a> {
+    pub translation: &'a Translation,
+    pub locale: String,
+    pub fixed_text: String,
+}
+
+pub fn default_quote_styles() -> BTreeMap<String, QuoteStyle> {
+    BTreeMap::from([
+        ("fr_FR".to_string(), QuoteStyle { apostrophe: '’', opening_quote: '«', closing_quote: '»' }),
+        ("en_GB".to_string(), QuoteStyle { apostrophe: '’', opening_quote: '“', closing_quote: '”' }),
+    ])
+}
+
+/// Rewrites the apostrophes between two letters and the quotes of a text with the expected style,
+/// straight double quotes being paired as opening then closing quotes
+/// ICU escaped apostrophes (`''`) and the quotes of markup tags and interpolations are kept
+pub fn apply_quote_style(text: &str, style: &QuoteStyle) -> String {
+    let syntax_ranges: Vec<(usize, usize)> = SYNTAX_REGEX.find_iter(text).map(|found| (found.start(), found.end())).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut double_quote_open = false;
+
+    chars
+        .iter()
+        .enumerate()
+        .map(|(index, &(offset, c))| {
+            if syntax_ranges.iter().any(|(start, end)| offset >= *start && offset < *end) {
+                return c;
+            }
+            let previous = index.checked_sub(1).map(|previous| chars[previous].1);
+            let next = chars.get(index + 1).map(|(_, next)| *next);
+
+            match c {
+                _ if APOSTROPHES.contains(&c) => {
+                    let between_letters = previous.is_some_and(char::is_alphabetic) && next.is_some_and(char::is_alphabetic);
+                    let icu_escape = c == '\'' && (previous == Some('\'') || next == Some('\''));
+                    if between_letters && !icu_escape { style.apostrophe } else { c }
+                }
+                '"' => {
+                    double_quote_open = !double_quote_open;
+                    if double_quote_open { style.opening_quote } else { style.closing_quote }
+                }
+                _ if OPENING_QUOTES.contains(&c) => style.opening_quote,
+                _ if CLOSING_QUOTES.contains(&c) => style.closing_quote,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+/// Reports the values whose apostrophes or quotes differ from the style expected for their locale,
+/// embedded JSON or HTML documents being left to the embedded blobs report
+pub fn analyse_quote_styles<'a>(
+    translations: &[&'a Translation],
+    quote_styles: &BTreeMap<String, QuoteStyle>,
+    locale_matcher: &LocaleMatcher,
+) -> Vec<QuoteStyleReport<'a>> {
+    let mut reports: Vec<QuoteStyleReport<'a>> = translations
+        .iter()
+        .filter_map(|translation| {
+            let locale = translation_locale(translation, locale_matcher)?;
+            let style = quote_styles.get(&locale)?;
+            let text = translation.text().filter(|text| detect_blob_type(text).is_none())?;
+            let fixed_text = apply_quote_style(text, style);
+            (fixed_text != text).then_some(QuoteStyleReport { translation, locale, fixed_text })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.translation.path.cmp(&b.translation.path).then_with(|| compare_text(&a.translation.key, &b.translation.key)));
+    reports
+}
+
+/// Writes the fixed values in their translation files
+pub fn fix_quote_styles(reports: &[QuoteStyleReport], session: &mut WriteSession) -> Result<(), LoadTranslationsFilesError> {
+    let mut fixes_by_path: BTreeMap<&PathBuf, BTreeMap<&str, &str>> = BTreeMap::new();
+    for report in reports {
+        fixes_by_path
+            .entry(&report.translation.path)
+            .or_default()
+            .insert(&report.translation.key, &report.fixed_text);
+    }
+
+    for (path, fixes) in fixes_by_path {
+        let mut entries = read_translation_entries(path)?;
+        for (key, value) in entries.0.iter_mut() {
+            if let Some(fixed_text) = fixes.get(key.as_str()) {
+                *value = Value::String(fixed_text.to_string());
+            }
+        }
+        session.write(path, serialize_translation_entries(&entries));
+    }
+
+    Ok(())
+}
+
+pub fn print_quote_styles_report(monorepo_path: &Path, reports: &[QuoteStyleReport], diff_format: DiffFormat) {
+    println!("Quote styles report :");
+    println!("Values with unexpected apostrophes or quotes : {}", reports.len());
+
+    for report in reports {
+        println!(
+            "{} - {} ({}) : {}",
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key,
+            report.locale,
+            render_diff(&diff_chars(report.translation.text().unwrap_or_default(), &report.fixed_text), diff_format)
+        );
+    }
+}
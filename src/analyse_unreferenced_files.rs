@@ -0,0 +1,108 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::scan_source_files::SourceStringLiteral;
+use crate::suggest_common_translations::{is_common_translation, CommonModulePath};
+
+/// Package folders usually holding the sources, not part of the paths given to loaders
+const SOURCE_ROOT_FOLDERS: [&str; 2] = ["src", "public"];
+
+/// Name of the folders whose sub folders are i18next namespaces, as `translations/dashboard`
+const TRANSLATIONS_FOLDER: &str = "translations";
+
+pub struct UnreferencedFolderReport {
+    pub folder: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+/// Reports translation folders that no string literal of their package points to,
+/// through a relative import (`./translations/Messages_${locale}.json`), a folder path or a namespace
+/// Common translations modules are skipped, being loaded by the other packages
+pub fn analyse_unreferenced_files(
+    monorepo_path: &Path,
+    translation_files: &[PathBuf],
+    literals: &[SourceStringLiteral],
+    common_modules_path: &[CommonModulePath],
+    package_path_pattern: &PackagePathPattern,
+) -> Vec<UnreferencedFolderReport> {
+    let mut literals_by_package: HashMap<String, Vec<&SourceStringLiteral>> = HashMap::new();
+    for literal in literals {
+        literals_by_package.entry(package_path_pattern.package_path(&literal.path.to_string_lossy())).or_default().push(literal);
+    }
+
+    let mut files_by_folder: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for file in translation_files {
+        if is_common_translation(file, common_modules_path) {
+            continue;
+        }
+        let folder = file.parent().map(Path::to_path_buf).unwrap_or_default();
+        files_by_folder.entry(folder).or_default().push(file.clone());
+    }
+
+    files_by_folder
+        .into_iter()
+        .filter(|(folder, _)| {
+            let package_path = package_path_pattern.package_path(&folder.to_string_lossy());
+            let package_literals = literals_by_package.get(&package_path).map(Vec::as_slice).unwrap_or_default();
+            !is_folder_referenced(&monorepo_path.join(&package_path), folder, package_literals)
+        })
+        .map(|(folder, mut files)| {
+            files.sort();
+            UnreferencedFolderReport { folder, files }
+        })
+        .collect()
+}
+
+fn is_folder_referenced(package_folder: &Path, folder: &Path, literals: &[&SourceStringLiteral]) -> bool {
+    let relative_folder = folder.strip_prefix(package_folder).unwrap_or(folder);
+    let loader_path = match relative_folder.components().next() {
+        Some(Component::Normal(root)) if SOURCE_ROOT_FOLDERS.iter().any(|source_root| root == *source_root) => {
+            relative_folder.components().skip(1).collect::<PathBuf>()
+        }
+        _ => relative_folder.to_path_buf(),
+    };
+    let loader_path = loader_path.to_string_lossy().to_string();
+    let namespace = loader_path
+        .rsplit_once(&format!("{}/", TRANSLATIONS_FOLDER))
+        .map(|(_, namespace)| namespace.to_string());
+
+    literals.iter().any(|literal| {
+        let value = literal.value.trim_end_matches('/');
+
+        if value.starts_with('.') {
+            let source_folder = literal.path.parent().unwrap_or(Path::new(""));
+            let resolved = normalize_path(&source_folder.join(value));
+            return resolved.starts_with(folder);
+        }
+
+        (!loader_path.is_empty() && value.ends_with(&loader_path)) || namespace.as_deref() == Some(value)
+    })
+}
+
+/// Resolves the `.` and `..` components of a path without touching the file system
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+pub fn print_unreferenced_files_report(monorepo_path: &Path, reports: &[UnreferencedFolderReport]) {
+    println!("Unreferenced translation files report :");
+    println!("Translation folders not referenced by their package : {}", reports.len());
+
+    for report in reports {
+        println!("\n");
+        println!(" ========= {} ==========", report.folder.strip_prefix(monorepo_path).unwrap_or(&report.folder).to_string_lossy());
+        for file in &report.files {
+            println!("{}", file.file_name().unwrap_or_default().to_string_lossy());
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use crate::collation::compare_text;
+use crate::entities::{Translation, TranslationValue};
+use crate::locales::{group_by_key_across_locales, LocaleMatcher};
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// A value byte-identical to the reference locale value of the same key
+pub struct UntranslatedValue {
+    pub folder: PathBuf,
+    pub key: String,
+    pub locale: String,
+    pub value: TranslationValue,
+}
+
+/// Translation progress of a locale within a package, against the keys of the reference locale
+#[derive(Default)]
+pub struct LocaleCompleteness {
+    pub reference_keys: usize,
+    pub translated: usize,
+    pub untranslated: usize,
+    pub missing: usize,
+}
+
+impl LocaleCompleteness {
+    pub fn percentage(&self) -> f64 {
+        if self.reference_keys == 0 {
+            return 100.0;
+        }
+        self.translated as f64 * 100.0 / self.reference_keys as f64
+    }
+}
+
+pub struct UntranslatedReport {
+    pub values: Vec<UntranslatedValue>,
+    /// Completeness indexed by package path then locale
+    pub completeness: BTreeMap<String, BTreeMap<String, LocaleCompleteness>>,
+}
+
+/// Compares every locale to the reference locale, a value identical to the reference one being likely untranslated
+/// The expected locales are measured in every package, even those without any file for them
+pub fn analyse_untranslated(translations: &[&Translation], locale_matcher: &LocaleMatcher, reference_locale: &str, expected_locales: &[String], package_path_pattern: &PackagePathPattern) -> UntranslatedReport {
+    let groups = group_by_key_across_locales(translations, locale_matcher);
+
+    let mut locales_by_package: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for ((folder, _), localized_values) in &groups {
+        locales_by_package
+            .entry(package_path_pattern.package_path(&folder.to_string_lossy()))
+            .or_default()
+            .extend(localized_values.keys().map(String::as_str).filter(|locale| *locale != reference_locale));
+    }
+    for locales in locales_by_package.values_mut() {
+        locales.extend(expected_locales.iter().map(String::as_str).filter(|locale| *locale != reference_locale));
+    }
+
+    let mut values: Vec<UntranslatedValue> = Vec::new();
+    let mut completeness: BTreeMap<String, BTreeMap<String, LocaleCompleteness>> = BTreeMap::new();
+
+    for ((folder, key), localized_values) in &groups {
+        let Some(reference) = localized_values.get(reference_locale) else {
+            continue;
+        };
+        if reference.text().is_some_and(|text| text.trim().is_empty()) {
+            continue;
+        }
+
+        let package_path = package_path_pattern.package_path(&folder.to_string_lossy());
+        let package_completeness = completeness.entry(package_path.clone()).or_default();
+
+        for locale in &locales_by_package[&package_path] {
+            let locale_completeness = package_completeness.entry(locale.to_string()).or_default();
+            locale_completeness.reference_keys += 1;
+
+            match localized_values.get(*locale) {
+                None => locale_completeness.missing += 1,
+                Some(translation) if translation.translations == reference.translations => {
+                    locale_completeness.untranslated += 1;
+                    values.push(UntranslatedValue {
+                        folder: folder.clone(),
+                        key: key.clone(),
+                        locale: locale.to_string(),
+                        value: translation.translations.clone(),
+                    });
+                }
+                Some(_) => locale_completeness.translated += 1,
+            }
+        }
+    }
+
+    values.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| compare_text(&a.key, &b.key)).then_with(|| a.locale.cmp(&b.locale)));
+    UntranslatedReport { values, completeness }
+}
+
+pub fn print_untranslated_report(monorepo_path: &Path, reference_locale: &str, report: &UntranslatedReport) {
+    println!("Untranslated values report :");
+    println!("Values identical to {} : {}", reference_locale, report.values.len());
+
+    for (package_path, completeness_by_locale) in &report.completeness {
+        println!("\n");
+        println!(" ========= {} ==========", package_path);
+        for (locale, completeness) in completeness_by_locale {
+            println!(
+                "{} : {:.1}% translated ({} / {}, {} identical, {} missing)",
+                locale,
+                completeness.percentage(),
+                completeness.translated,
+                completeness.reference_keys,
+                completeness.untranslated,
+                completeness.missing
+            );
+        }
+    }
+
+    println!("\n");
+    for value in &report.values {
+        println!(
+            "{} - {} : {} in {}",
+            value.folder.strip_prefix(monorepo_path).unwrap_or(&value.folder).to_string_lossy(),
+            value.key,
+            value.value,
+            value.locale
+        );
+    }
+}
+
+/// Prints the completeness of every package as a matrix, one column per locale
+pub fn print_coverage_matrix(reference_locale: &str, report: &UntranslatedReport) {
+    let locales: BTreeSet<&str> = report
+        .completeness
+        .values()
+        .flat_map(|completeness_by_locale| completeness_by_locale.keys().map(String::as_str))
+        .collect();
+    let package_width = report.completeness.keys().map(String::len).max().unwrap_or_default().max("package".len());
+    let column_width = locales.iter().map(|locale| locale.len()).max().unwrap_or_default().max("100.0%".len());
+
+    println!("Coverage report :");
+    println!("Translated keys compared to {}", reference_locale);
+    println!("\n");
+    println!(
+        "{:<package_width$} | {}",
+        "package",
+        locales.iter().map(|locale| format!("{:>column_width$}", locale)).collect::<Vec<String>>().join(" | ")
+    );
+
+    for (package_path, completeness_by_locale) in &report.completeness {
+        let cells: Vec<String> = locales
+            .iter()
+            .map(|locale| match completeness_by_locale.get(*locale) {
+                Some(completeness) => format!("{:>column_width$}", format!("{:.1}%", completeness.percentage())),
+                None => format!("{:>column_width$}", "-"),
+            })
+            .collect();
+        println!("{:<package_width$} | {}", package_path, cells.join(" | "));
+    }
+}
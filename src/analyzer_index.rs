@@ -0,0 +1,189 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use crate::allowed_duplications::{get_allowlist, AllowedDuplications, AllowlistError};
+use crate::analyse_project_duplication::{analyse_duplication, DuplicationReport};
+use crate::auto_triage::{apply_triage_rules_to, TriageRules};
+use crate::cluster_id::cluster_id;
+use crate::entities::{Translation, TranslationValue};
+use crate::get_translation_for_project::get_translations_for_project;
+use crate::map_translations_by_key::map_translation_positions_by_translation;
+use crate::package_dependencies::{get_package_dependencies, PackageDependencies, PackageManifestError};
+use crate::score_proposed_translation::{score_proposed_translation, ProposedTranslationScore};
+use crate::settings::Settings;
+use crate::test_fixtures::TestFixtures;
+use crate::locales::LocaleMatcher;
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::settings_overrides::{PackageAnalysis, SettingsOverrides};
+use crate::suggest_common_translations::CommonModulePath;
+
+#[derive(Error, Debug)]
+pub enum AnalyzerIndexError {
+    #[error("Invalid regex in the settings: {0}")]
+    InvalidRegex(#[from] regex::Error),
+
+    #[error(transparent)]
+    Allowlist(#[from] AllowlistError),
+
+    #[error(transparent)]
+    PackageManifest(#[from] PackageManifestError),
+}
+
+/// Translations of a monorepo loaded once with everything the duplication analysis needs, for embedders
+/// answering many queries, as a bundler plugin during a build
+/// Queries only read the index, so an `Arc<AnalyzerIndex>` is shared between threads without any lock
+pub struct AnalyzerIndex {
+    monorepo_path: PathBuf,
+    settings: Settings,
+    translations: Vec<Translation>,
+    /// Positions in `translations` by normalized value
+    positions_by_value: HashMap<String, Vec<usize>>,
+    /// Positions in `translations` by key
+    positions_by_key: HashMap<String, Vec<usize>>,
+    locale_matcher: LocaleMatcher,
+    package_path_pattern: PackagePathPattern,
+    allowed_duplications: AllowedDuplications,
+    dependencies: PackageDependencies,
+    /// Common translations modules of the settings, joined to the root path
+    common_modules_path: Vec<CommonModulePath>,
+    test_fixtures: TestFixtures,
+    triage_rules: TriageRules,
+    overrides: SettingsOverrides,
+}
+
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<AnalyzerIndex>();
+};
+
+impl AnalyzerIndex {
+    /// Indexes translations loaded from the monorepo, reading its allowlist and package manifests
+    pub fn new(monorepo_path: &Path, settings: Settings, translations: Vec<Translation>) -> Result<Self, AnalyzerIndexError> {
+        let locale_matcher = LocaleMatcher::compile(&settings)?;
+        let package_path_pattern = settings.package_path_pattern()?;
+        let allowlist = get_allowlist(&monorepo_path.join(&settings.allowlist_file_path))?;
+        let allowed_duplications = AllowedDuplications::compile(&settings.allowed_duplications, &settings.normalization)?
+            .with_allowlist(&allowlist, &settings.normalization);
+        let dependencies = get_package_dependencies(monorepo_path, &translations, &package_path_pattern)?;
+        let common_modules_path = settings.common_modules_paths(monorepo_path);
+        let test_fixtures = TestFixtures::compile(monorepo_path, &settings.test_fixtures)?;
+        let triage_rules = TriageRules::compile(&settings.triage_rules)?;
+        let overrides = SettingsOverrides::compile(&settings.overrides, &package_path_pattern)?;
+
+        let positions_by_value = map_translation_positions_by_translation(&translations, &settings.normalization, &settings.duplication_filters);
+        let mut positions_by_key: HashMap<String, Vec<usize>> = HashMap::new();
+        for (position, translation) in translations.iter().enumerate() {
+            positions_by_key.entry(translation.key.clone()).or_default().push(position);
+        }
+
+        Ok(Self {
+            monorepo_path: monorepo_path.to_path_buf(),
+            settings,
+            translations,
+            positions_by_value,
+            positions_by_key,
+            locale_matcher,
+            package_path_pattern,
+            allowed_duplications,
+            dependencies,
+            common_modules_path,
+            test_fixtures,
+            triage_rules,
+            overrides,
+        })
+    }
+
+    pub fn translations(&self) -> &[Translation] {
+        &self.translations
+    }
+
+    /// Translations whose value normalizes as the text, none for the values left out by the duplication filters
+    pub fn occurrences(&self, text: &str) -> Vec<&Translation> {
+        self.value_occurrences(&TranslationValue::String(text.to_string()))
+    }
+
+    /// Translations defining the key, in every file and locale
+    pub fn key_occurrences(&self, key: &str) -> Vec<&Translation> {
+        self.positions_by_key
+            .get(key)
+            .map(|positions| positions.iter().map(|position| &self.translations[*position]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Cluster the text belongs to in a locale, as `fr_FR`
+    pub fn cluster_id(&self, text: &str, locale: &str) -> String {
+        cluster_id(&TranslationValue::String(text.to_string()).normalized(&self.settings.normalization), locale)
+    }
+
+    /// Classifies a translation before it is added to `file`, relative to the monorepo root
+    pub fn score(&self, file: &Path, key: &str, text: &str) -> ProposedTranslationScore {
+        let proposed = Translation {
+            path: self.monorepo_path.join(file),
+            translations: TranslationValue::String(text.to_string()),
+            key: key.to_string(),
+            locale: None,
+        };
+        let normalized_value = proposed.translations.normalized(&self.settings.normalization);
+        let occurrences = self.value_occurrences(&proposed.translations);
+        let index = HashMap::from([(normalized_value, occurrences)]);
+
+        score_proposed_translation(
+            &proposed,
+            &index,
+            &self.settings.normalization,
+            &self.allowed_duplications,
+            &self.dependencies,
+            &self.common_modules_path,
+            &self.test_fixtures,
+            &self.settings.i18next_keys,
+            &self.locale_matcher,
+            &self.package_path_pattern,
+        )
+    }
+
+    /// Duplications of a package as reported by the CLI, triage rules applied, none when a settings override
+    /// disables its duplication analysis
+    pub fn package_duplications(&self, package_path: &str) -> Vec<DuplicationReport<'_>> {
+        if self.overrides.is_disabled(package_path, PackageAnalysis::Duplication) {
+            return Vec::new();
+        }
+        let project_translations = get_translations_for_project(package_path, &self.translations, &self.package_path_pattern);
+
+        // Only the values of the package are looked up by the analysis
+        let mut index: HashMap<String, Vec<&Translation>> = HashMap::new();
+        for translation in &project_translations {
+            let normalized_value = translation.translations.normalized(&self.settings.normalization);
+            if let Entry::Vacant(entry) = index.entry(normalized_value) {
+                let occurrences = self.value_occurrences(&translation.translations);
+                if !occurrences.is_empty() {
+                    entry.insert(occurrences);
+                }
+            }
+        }
+
+        let mut duplications = analyse_duplication(
+            package_path,
+            &project_translations,
+            &index,
+            &self.settings.normalization,
+            &self.allowed_duplications,
+            &self.dependencies,
+            &self.common_modules_path,
+            &self.test_fixtures,
+            &self.settings.i18next_keys,
+            &self.locale_matcher,
+            &self.package_path_pattern,
+        );
+        apply_triage_rules_to(&mut duplications, &index, &self.settings.normalization, &self.triage_rules, &self.package_path_pattern);
+
+        duplications
+    }
+
+    fn value_occurrences(&self, value: &TranslationValue) -> Vec<&Translation> {
+        self.positions_by_value
+            .get(&value.normalized(&self.settings.normalization))
+            .map(|positions| positions.iter().map(|position| &self.translations[*position]).collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,139 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use crate::translation_file::TranslationEntries;
+
+static COMMENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+static RESOURCE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(string-array|plurals|string)\b([^>]*?)(/?)>").unwrap());
+static ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<item\b([^>]*?)(?:/>|>(.*?)</item>)").unwrap());
+static ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([\w:-]+)\s*=\s*"([^"]*)""#).unwrap());
+static CDATA_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!\[CDATA\[(.*?)\]\]>").unwrap());
+static ENTITY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"&(#x[0-9a-fA-F]+|#[0-9]+|[a-z]+);").unwrap());
+
+/// Parses the resources of an Android `strings.xml` file
+/// Plurals are keyed `name[quantity]` and string arrays `name[index]`, strings marked
+/// `translatable="false"` being left out. Markup inside a string is kept as written
+pub fn parse_android_xml_entries(content: &str) -> Result<TranslationEntries, String> {
+    let content = COMMENT_REGEX.replace_all(content, "");
+    let mut entries = TranslationEntries::default();
+    let mut position = 0;
+
+    while let Some(captures) = RESOURCE_REGEX.captures_at(&content, position) {
+        let (Some(tag), Some(opening)) = (captures.get(1), captures.get(0)) else {
+            break;
+        };
+        let attributes = captures.get(2).map_or("", |attributes| attributes.as_str());
+        let name = attribute(attributes, "name").ok_or_else(|| format!("<{}> without name", tag.as_str()))?;
+
+        let inner = if captures.get(3).is_some_and(|self_closing| !self_closing.as_str().is_empty()) {
+            position = opening.end();
+            ""
+        } else {
+            let closing = format!("</{}>", tag.as_str());
+            let end = content[opening.end()..]
+                .find(&closing)
+                .map(|offset| opening.end() + offset)
+                .ok_or_else(|| format!("<{} name=\"{}\"> is not closed", tag.as_str(), name))?;
+            position = end + closing.len();
+            &content[opening.end()..end]
+        };
+
+        if attribute(attributes, "translatable").is_some_and(|translatable| translatable == "false") {
+            continue;
+        }
+
+        match tag.as_str() {
+            "string" => entries.0.push((name.to_string(), Value::String(decode_text(inner)))),
+            tag => {
+                for (index, item) in ITEM_REGEX.captures_iter(inner).enumerate() {
+                    let item_attributes = item.get(1).map_or("", |item_attributes| item_attributes.as_str());
+                    let suffix = match (tag, attribute(item_attributes, "quantity")) {
+                        ("plurals", Some(quantity)) => quantity.to_string(),
+                        ("plurals", None) => return Err(format!("<plurals name=\"{}\"> item without quantity", name)),
+                        _ => index.to_string(),
+                    };
+                    let text = item.get(2).map_or("", |text| text.as_str());
+                    entries.0.push((format!("{}[{}]", name, suffix), Value::String(decode_text(text))));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    ATTRIBUTE_REGEX
+        .captures_iter(attributes)
+        .find(|captures| captures.get(1).is_some_and(|attribute_name| attribute_name.as_str() == name))
+        .and_then(|captures| captures.get(2))
+        .map(|value| value.as_str())
+}
+
+/// Text of a resource as Android displays it: XML entities and CDATA sections decoded, then whitespace
+/// collapsed outside of double quotes and backslash escapes applied
+fn decode_text(raw: &str) -> String {
+    let mut xml_decoded = String::new();
+    let mut last = 0;
+    for cdata in CDATA_REGEX.captures_iter(raw) {
+        let (Some(section), Some(text)) = (cdata.get(0), cdata.get(1)) else {
+            continue;
+        };
+        xml_decoded.push_str(&decode_entities(&raw[last..section.start()]));
+        xml_decoded.push_str(text.as_str());
+        last = section.end();
+    }
+    xml_decoded.push_str(&decode_entities(&raw[last..]));
+
+    let mut text = String::new();
+    let mut quoted = false;
+    let mut collapsing = false;
+    let mut chars = xml_decoded.trim().chars();
+    while let Some(c) = chars.next() {
+        let whitespace = c.is_whitespace() && !quoted;
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('t') => text.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    text.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+                }
+                Some(escaped) => text.push(escaped),
+                None => text.push('\\'),
+            },
+            _ if whitespace => {
+                if !collapsing {
+                    text.push(' ');
+                }
+            }
+            c => text.push(c),
+        }
+        collapsing = whitespace;
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    ENTITY_REGEX
+        .replace_all(text, |captures: &regex::Captures| {
+            let entity = &captures[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|decimal| decimal.parse::<u32>()))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| captures[0].to_string(), |c| c.to_string())
+        })
+        .to_string()
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::cluster_id::legacy_cluster_id;
+use crate::extendable_file::{read_extendable_entries, ExtendableFileError};
+
+#[derive(Error, Debug)]
+pub enum AnnotationsFileError {
+    #[error("Unable to read annotations file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid annotations file: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("Annotations file {0} extends itself through its `extends` chain")]
+    ExtendsCycle(String),
+}
+
+impl From<ExtendableFileError> for AnnotationsFileError {
+    fn from(error: ExtendableFileError) -> Self {
+        match error {
+            ExtendableFileError::UnableToReadPath(path, e) => AnnotationsFileError::UnableToReadPath(path, e),
+            ExtendableFileError::InvalidFormat(path, e) => AnnotationsFileError::InvalidFormat(path, e),
+            ExtendableFileError::ExtendsCycle(path) => AnnotationsFileError::ExtendsCycle(path),
+        }
+    }
+}
+
+/// Triage decision of a reviewer on a duplicate cluster
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnnotationStatus {
+    Intentional,
+    ToFix,
+    NeedsDiscussion,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Annotation {
+    /// Cluster identifier, as displayed by the reports
+    pub cluster_id: String,
+    pub status: AnnotationStatus,
+    pub comment: Option<String>,
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            AnnotationStatus::Intentional => "intentional",
+            AnnotationStatus::ToFix => "to fix",
+            AnnotationStatus::NeedsDiscussion => "needs discussion",
+        };
+        match &self.comment {
+            Some(comment) => write!(f, "{} - {}", status, comment),
+            None => write!(f, "{}", status),
+        }
+    }
+}
+
+/// Annotations indexed by cluster identifier
+#[derive(Default)]
+pub struct Annotations(HashMap<String, Annotation>);
+
+impl Annotations {
+    pub fn get(&self, cluster_id: &str) -> Option<&Annotation> {
+        self.0.get(cluster_id).or_else(|| self.0.get(legacy_cluster_id(cluster_id)))
+    }
+}
+
+/// Reads the annotations file, a missing file meaning no annotation
+/// Cluster identifiers may be written with their leading `#`, the last annotation of a cluster wins
+/// The annotations of the files it `extends` come first, so local decisions override the inherited ones
+pub fn get_annotations(annotations_file_path: &Path) -> Result<Annotations, AnnotationsFileError> {
+    let annotations: Vec<Annotation> = read_extendable_entries(annotations_file_path)?;
+
+    Ok(Annotations(
+        annotations
+            .into_iter()
+            .map(|mut annotation| {
+                annotation.cluster_id = annotation.cluster_id.trim_start_matches('#').to_string();
+                (annotation.cluster_id.clone(), annotation)
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::analyse_project_duplication::{DuplicationReport, DuplicationType};
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::settings::ValueNormalization;
+
+/// Tag given to the findings matched by a triage rule
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageAction {
+    /// Left out of the reports, exports and check thresholds
+    AutoIgnore,
+    AutoPriorityHigh,
+    AutoPriorityLow,
+}
+
+impl fmt::Display for TriageAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TriageAction::AutoIgnore => "auto-ignore",
+            TriageAction::AutoPriorityHigh => "auto-priority-high",
+            TriageAction::AutoPriorityLow => "auto-priority-low",
+        })
+    }
+}
+
+/// Tags the duplications matching every condition set, as values shorter than 4 characters or clusters
+/// spanning more than 10 packages. The first matching rule wins
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TriageRule {
+    /// Shown next to the tag in the reports
+    pub name: String,
+    pub action: TriageAction,
+    /// Length of the value in characters
+    pub max_value_length: Option<usize>,
+    pub min_value_length: Option<usize>,
+    /// Number of packages using the value
+    pub min_packages: Option<usize>,
+    pub max_packages: Option<usize>,
+    pub duplication_type: Option<DuplicationType>,
+    pub key_pattern: Option<String>,
+    pub value_pattern: Option<String>,
+}
+
+/// Tag of a duplication, with the rule which gave it
+#[derive(Serialize, Clone, Debug)]
+pub struct TriageTag {
+    pub rule: String,
+    pub action: TriageAction,
+}
+
+struct CompiledTriageRule {
+    rule: TriageRule,
+    key_regex: Option<Regex>,
+    value_regex: Option<Regex>,
+}
+
+/// Triage rules of the settings, ready to be evaluated on the duplications
+pub struct TriageRules(Vec<CompiledTriageRule>);
+
+impl TriageRules {
+    pub fn compile(rules: &[TriageRule]) -> Result<Self, regex::Error> {
+        let compile_pattern = |pattern: &Option<String>| pattern.as_deref().map(Regex::new).transpose();
+
+        rules
+            .iter()
+            .map(|rule| {
+                Ok(CompiledTriageRule {
+                    rule: rule.clone(),
+                    key_regex: compile_pattern(&rule.key_pattern)?,
+                    value_regex: compile_pattern(&rule.value_pattern)?,
+                })
+            })
+            .collect::<Result<Vec<_>, regex::Error>>()
+            .map(Self)
+    }
+
+    /// First rule matching the duplication, `packages` being the number of packages using its value
+    pub fn triage(&self, duplication: &DuplicationReport, packages: usize) -> Option<TriageTag> {
+        let text = duplication.translation.text().map_or_else(|| duplication.translation.translations.to_string(), str::to_string);
+        let length = text.chars().count();
+
+        self.0
+            .iter()
+            .find(|compiled| {
+                let rule = &compiled.rule;
+                rule.max_value_length.is_none_or(|max| length <= max)
+                    && rule.min_value_length.is_none_or(|min| length >= min)
+                    && rule.max_packages.is_none_or(|max| packages <= max)
+                    && rule.min_packages.is_none_or(|min| packages >= min)
+                    && rule.duplication_type.as_ref().is_none_or(|duplication_type| *duplication_type == duplication.duplication_type)
+                    && compiled.key_regex.as_ref().is_none_or(|key_regex| key_regex.is_match(&duplication.translation.key))
+                    && compiled.value_regex.as_ref().is_none_or(|value_regex| value_regex.is_match(&text))
+            })
+            .map(|compiled| TriageTag { rule: compiled.rule.name.clone(), action: compiled.rule.action })
+    }
+}
+
+/// Tags the duplications of every package, removing the auto-ignored ones before anything is rendered or checked
+/// Returns the number of auto-ignored duplications
+pub fn apply_triage_rules(
+    duplications_by_package: &mut BTreeMap<String, Vec<DuplicationReport>>,
+    all_translations: &HashMap<String, Vec<&Translation>>,
+    normalization: &ValueNormalization,
+    rules: &TriageRules,
+    package_path_pattern: &PackagePathPattern,
+) -> usize {
+    if rules.0.is_empty() {
+        return 0;
+    }
+
+    let mut ignored = 0;
+    for duplications in duplications_by_package.values_mut() {
+        ignored += apply_triage_rules_to(duplications, all_translations, normalization, rules, package_path_pattern);
+    }
+    ignored
+}
+
+/// Tags the duplications of a single package, as `apply_triage_rules`
+pub fn apply_triage_rules_to(
+    duplications: &mut Vec<DuplicationReport>,
+    all_translations: &HashMap<String, Vec<&Translation>>,
+    normalization: &ValueNormalization,
+    rules: &TriageRules,
+    package_path_pattern: &PackagePathPattern,
+) -> usize {
+    let before = duplications.len();
+
+    for duplication in duplications.iter_mut() {
+        let normalized_value = duplication.translation.translations.normalized(normalization);
+        let packages: BTreeSet<String> = all_translations
+            .get(&normalized_value)
+            .map(|occurrences| occurrences.iter().map(|occurrence| package_path_pattern.package_path(&occurrence.path.to_string_lossy())).collect())
+            .unwrap_or_default();
+        duplication.triage = rules.triage(duplication, packages.len());
+    }
+    duplications.retain(|duplication| duplication.triage.as_ref().is_none_or(|tag| tag.action != TriageAction::AutoIgnore));
+
+    before - duplications.len()
+}
+
+/// Rank of a duplication in the reports, high priority ones first and low priority ones last
+pub fn triage_order(triage: &Option<TriageTag>) -> u8 {
+    match triage.as_ref().map(|tag| tag.action) {
+        Some(TriageAction::AutoPriorityHigh) => 0,
+        Some(TriageAction::AutoPriorityLow) => 2,
+        _ => 1,
+    }
+}
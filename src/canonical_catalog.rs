@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::collation::compare_text;
+use crate::entities::{Translation, TranslationValue};
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::settings::ValueNormalization;
+
+#[derive(Error, Debug)]
+pub enum CanonicalCatalogError {
+    #[error("No canonical catalog configured, set `canonical_catalog_file_path` in the settings")]
+    NotConfigured,
+
+    #[error("Unable to read canonical catalog: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid canonical catalog: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+}
+
+/// Curated strings maintained by UX writing, as canonical key then locale then value
+#[derive(Serialize, Deserialize, Default)]
+pub struct CanonicalCatalog(pub BTreeMap<String, BTreeMap<String, String>>);
+
+/// A package value matching a catalog value under another key
+pub struct NonCanonicalKeyReport<'a> {
+    pub translation: &'a Translation,
+    pub locale: String,
+    pub canonical_key: &'a str,
+}
+
+pub struct CanonicalCatalogReport<'a> {
+    pub non_canonical_keys: Vec<NonCanonicalKeyReport<'a>>,
+    /// Catalog keys defined by no package
+    pub unused_keys: Vec<&'a str>,
+}
+
+pub fn get_canonical_catalog(catalog_file_path: &Path) -> Result<CanonicalCatalog, CanonicalCatalogError> {
+    let content = fs::read_to_string(catalog_file_path)
+        .map_err(|e| CanonicalCatalogError::UnableToReadPath(catalog_file_path.to_string_lossy().to_string(), e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| CanonicalCatalogError::InvalidFormat(catalog_file_path.to_string_lossy().to_string(), e))
+}
+
+/// Compares the translations to the catalog, values being matched once normalized within a same locale
+pub fn analyse_canonical_catalog<'a>(
+    translations: &[&'a Translation],
+    catalog: &'a CanonicalCatalog,
+    normalization: &ValueNormalization,
+    locale_matcher: &LocaleMatcher,
+) -> CanonicalCatalogReport<'a> {
+    let mut canonical_keys: HashMap<(&str, String), &'a str> = HashMap::new();
+    for (key, values) in &catalog.0 {
+        for (locale, value) in values {
+            let normalized_value = TranslationValue::String(value.clone()).normalized(normalization);
+            canonical_keys.entry((locale.as_str(), normalized_value)).or_insert(key);
+        }
+    }
+
+    let mut non_canonical_keys: Vec<NonCanonicalKeyReport<'a>> = translations
+        .iter()
+        .filter_map(|translation| {
+            let locale = translation_locale(translation, locale_matcher)?;
+            let canonical_key = *canonical_keys.get(&(locale.as_str(), translation.translations.normalized(normalization)))?;
+            (canonical_key != translation.key).then_some(NonCanonicalKeyReport { translation, locale, canonical_key })
+        })
+        .collect();
+    non_canonical_keys.sort_by(|a, b| a.translation.path.cmp(&b.translation.path).then_with(|| compare_text(&a.translation.key, &b.translation.key)));
+
+    let used_keys: HashSet<&str> = translations.iter().map(|translation| translation.key.as_str()).collect();
+    let unused_keys: Vec<&'a str> = catalog.0.keys().map(String::as_str).filter(|key| !used_keys.contains(key)).collect();
+
+    CanonicalCatalogReport { non_canonical_keys, unused_keys }
+}
+
+pub fn print_canonical_catalog_report(monorepo_path: &Path, report: &CanonicalCatalogReport) {
+    println!("Canonical catalog report :");
+    println!("Catalog values under non-canonical keys : {}", report.non_canonical_keys.len());
+    println!("Catalog entries without users : {}", report.unused_keys.len());
+
+    for non_canonical_key in &report.non_canonical_keys {
+        println!(
+            "{} - {} ({}) : use {}",
+            non_canonical_key.translation.path.strip_prefix(monorepo_path).unwrap_or(&non_canonical_key.translation.path).to_string_lossy(),
+            non_canonical_key.translation.key,
+            non_canonical_key.locale,
+            non_canonical_key.canonical_key
+        );
+    }
+
+    if !report.unused_keys.is_empty() {
+        println!("\n");
+        println!("Unused catalog entries : {}", report.unused_keys.join(", "));
+    }
+}
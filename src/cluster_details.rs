@@ -0,0 +1,68 @@
+use std::path::Path;
+use crate::annotations::Annotation;
+use crate::analyse_project_duplication::{DuplicationReport, DuplicationType};
+use crate::entities::{Translation, TranslationValue};
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::suggest_common_translations::CommonTranslationSuggestion;
+
+/// Every occurrence of a duplicate cluster, with how each one is classified
+pub struct ClusterDetails<'a> {
+    pub cluster_id: String,
+    pub locale: String,
+    pub value: &'a TranslationValue,
+    /// Occurrences of the value, without classification when an allowed duplication suppresses them
+    pub occurrences: Vec<(&'a Translation, Option<&'a DuplicationType>)>,
+    pub suggestion: Option<CommonTranslationSuggestion<'a>>,
+    pub annotation: Option<&'a Annotation>,
+}
+
+/// Pairs the occurrences of a cluster with their duplication reports
+pub fn collect_cluster_occurrences<'a>(
+    occurrences: &[&'a Translation],
+    duplications: &[&'a DuplicationReport<'a>],
+) -> Vec<(&'a Translation, Option<&'a DuplicationType>)> {
+    let mut occurrences: Vec<(&'a Translation, Option<&'a DuplicationType>)> = occurrences
+        .iter()
+        .map(|occurrence| {
+            let duplication = duplications
+                .iter()
+                .find(|duplication| std::ptr::eq(duplication.translation, *occurrence));
+            (*occurrence, duplication.map(|duplication| &duplication.duplication_type))
+        })
+        .collect();
+
+    occurrences.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path).then_with(|| a.key.cmp(&b.key)));
+    occurrences
+}
+
+pub fn print_cluster_details(monorepo_path: &Path, details: &ClusterDetails, package_path_pattern: &PackagePathPattern) {
+    println!(" ========= Cluster #{} - {} ==========", details.cluster_id, details.locale);
+    println!(" ========= {} ==========", details.value);
+    println!("Occurrences : {}", details.occurrences.len());
+    println!("Suppressed by allowed duplications : {}", details.occurrences.iter().filter(|(_, duplication_type)| duplication_type.is_none()).count());
+    if let Some(annotation) = details.annotation {
+        println!("Annotation : {}", annotation);
+    }
+
+    for (occurrence, duplication_type) in &details.occurrences {
+        println!(
+            "{} - {} ({})",
+            occurrence.path.strip_prefix(monorepo_path).unwrap_or(&occurrence.path).to_string_lossy(),
+            occurrence.key,
+            duplication_type.map_or("allowed".to_string(), |duplication_type| format!("{:?}", duplication_type))
+        );
+    }
+
+    println!("\n");
+    match &details.suggestion {
+        Some(suggestion) => match suggestion.existing_common_translation {
+            Some(common_translation) => println!(
+                "Suggested action : use existing common key {} ({})",
+                suggestion.proposed_key,
+                package_path_pattern.package_path(&common_translation.path.to_string_lossy())
+            ),
+            None => println!("Suggested action : create common key {}", suggestion.proposed_key),
+        },
+        None => println!("Suggested action : none, the cluster has no reported duplication"),
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use clap::ValueEnum;
+use serde::Serialize;
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::scan_source_files::SourceStringLiteral;
+use crate::safe_write::write_output_file;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    /// Graphviz DOT, as `dot -Tsvg cluster.dot -o cluster.svg`
+    Dot,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphNodeKind {
+    Package,
+    File,
+    Key,
+    CallSite,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GraphNode {
+    pub id: String,
+    pub kind: GraphNodeKind,
+    pub label: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// What a consolidation of a cluster would touch: packages contain files, files define keys, call sites use keys
+#[derive(Serialize, Debug)]
+pub struct ClusterGraph {
+    pub cluster_id: String,
+    pub value: String,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Builds the graph of the occurrences of a cluster, their call sites being the string literals equal to their key
+/// in the source files of their package
+pub fn build_cluster_graph(
+    monorepo_path: &Path,
+    cluster_id: &str,
+    occurrences: &[&Translation],
+    string_literals: &[SourceStringLiteral],
+    package_path_pattern: &PackagePathPattern,
+) -> ClusterGraph {
+    let relative = |path: &Path| path.strip_prefix(monorepo_path).unwrap_or(path).to_string_lossy().to_string();
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    let mut edges: BTreeSet<(String, String)> = BTreeSet::new();
+    let mut add_node = |kind: GraphNodeKind, id: String, label: String| {
+        nodes.entry(id.clone()).or_insert(GraphNode { id, kind, label });
+    };
+
+    for occurrence in occurrences {
+        let file_path = relative(&occurrence.path);
+        let package_path = package_path_pattern.package_path(&file_path);
+        let package_id = format!("package:{}", package_path);
+        let file_id = format!("file:{}", file_path);
+        let key_id = format!("key:{}#{}", file_path, occurrence.key);
+
+        add_node(GraphNodeKind::Package, package_id.clone(), package_path.clone());
+        add_node(GraphNodeKind::File, file_id.clone(), file_path.clone());
+        add_node(GraphNodeKind::Key, key_id.clone(), occurrence.key.clone());
+        edges.insert((package_id, file_id.clone()));
+        edges.insert((file_id, key_id.clone()));
+
+        for literal in string_literals.iter().filter(|literal| literal.value == occurrence.key) {
+            let source_path = relative(&literal.path);
+            if package_path_pattern.package_path(&source_path) != package_path {
+                continue;
+            }
+            let call_site = format!("{}:{}", source_path, literal.line);
+            let call_site_id = format!("call_site:{}", call_site);
+            add_node(GraphNodeKind::CallSite, call_site_id.clone(), call_site);
+            edges.insert((key_id.clone(), call_site_id));
+        }
+    }
+
+    let mut nodes: Vec<GraphNode> = nodes.into_values().collect();
+    nodes.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.id.cmp(&b.id)));
+
+    ClusterGraph {
+        cluster_id: cluster_id.to_string(),
+        value: occurrences.first().map(|occurrence| occurrence.translations.to_string()).unwrap_or_default(),
+        nodes,
+        edges: edges.into_iter().map(|(from, to)| GraphEdge { from, to }).collect(),
+    }
+}
+
+pub fn render_cluster_graph(graph: &ClusterGraph, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Json => serde_json::to_string_pretty(graph).expect("Cluster graphs are always serializable") + "\n",
+        GraphFormat::Dot => render_dot(graph),
+    }
+}
+
+pub fn write_cluster_graph(output_path: &Path, rendered: &str) -> Result<(), std::io::Error> {
+    write_output_file(output_path, rendered)
+}
+
+fn render_dot(graph: &ClusterGraph) -> String {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut dot = format!("digraph {} {{\n", quote(&format!("cluster_{}", graph.cluster_id)));
+    dot.push_str(&format!("  label={};\n  rankdir=LR;\n", quote(&format!("#{} {}", graph.cluster_id, graph.value))));
+
+    for node in &graph.nodes {
+        let shape = match node.kind {
+            GraphNodeKind::Package => "folder",
+            GraphNodeKind::File => "note",
+            GraphNodeKind::Key => "box",
+            GraphNodeKind::CallSite => "ellipse",
+        };
+        dot.push_str(&format!("  {} [label={}, shape={}];\n", quote(&node.id), quote(&node.label), shape));
+    }
+    for edge in &graph.edges {
+        dot.push_str(&format!("  {} -> {};\n", quote(&edge.from), quote(&edge.to)));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
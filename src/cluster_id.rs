@@ -0,0 +1,42 @@
+use crate::entities::Translation;
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::settings::ValueNormalization;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+/// Length of the cluster ids written before they held the whole hash, still matched in baselines and annotations
+const LEGACY_CLUSTER_ID_LENGTH: usize = 8;
+
+/// Stable identifier of a duplicate cluster, derived from its normalized value and locale
+/// FNV-1a is used instead of the std hasher, whose output may change between Rust releases
+pub fn cluster_id(normalized_value: &str, locale: &str) -> String {
+    let hash = normalized_value
+        .bytes()
+        .chain(std::iter::once(0))
+        .chain(locale.bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME));
+
+    format!("{:016x}", hash)
+}
+
+/// Cluster id as written by the former releases, truncated to 32 bits of the hash
+pub fn legacy_cluster_id(cluster_id: &str) -> &str {
+    cluster_id.get(..LEGACY_CLUSTER_ID_LENGTH).unwrap_or(cluster_id)
+}
+
+/// Whether a cluster id given by the user, whole or legacy, designates the cluster
+pub fn matches_cluster_id(cluster_id: &str, given: &str) -> bool {
+    let given = given.trim_start_matches('#');
+    cluster_id == given || legacy_cluster_id(cluster_id) == given
+}
+
+pub fn translation_cluster_id(
+    translation: &Translation,
+    normalization: &ValueNormalization,
+    locale_matcher: &LocaleMatcher,
+) -> String {
+    cluster_id(
+        &translation.translations.normalized(normalization),
+        &translation_locale(translation, locale_matcher).unwrap_or_default(),
+    )
+}
@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap};
+use rayon::prelude::*;
+use serde_json::Value;
+use crate::analyse_near_duplication::compute_similarity;
+use crate::collation::compare_text;
+use crate::entities::Translation;
+use crate::normalize_value::normalize_text;
+use crate::settings::{SimilarityAlgorithm, ValueNormalization};
+
+/// Normalization under which values are considered the same wording, whatever the configured one
+const LOOSE_NORMALIZATION: ValueNormalization = ValueNormalization {
+    trim: true,
+    collapse_whitespace: true,
+    case_fold: true,
+    strip_trailing_punctuation: true,
+};
+
+pub struct SimilarValuesCluster<'a> {
+    /// Most used value of the cluster, suggested as the one to keep
+    pub canonical_value: &'a str,
+    /// Distinct string contents of the cluster with their translations, most used first
+    pub values: Vec<(&'a str, Vec<&'a Translation>)>,
+}
+
+impl SimilarValuesCluster<'_> {
+    pub fn usages(&self) -> usize {
+        self.values.iter().map(|(_, translations)| translations.len()).sum()
+    }
+}
+
+/// Groups values that are the same wording or similar enough into clusters, transitively,
+/// and suggests the most used value of each cluster as canonical value
+pub fn cluster_similar_values<'a>(
+    translations: &[&'a Translation],
+    algorithm: SimilarityAlgorithm,
+    threshold: f64,
+) -> Vec<SimilarValuesCluster<'a>> {
+    let mut translations_by_value: HashMap<&'a str, Vec<&'a Translation>> = HashMap::new();
+    for translation in translations {
+        if let Some(text) = translation.text().filter(|text| !text.trim().is_empty()) {
+            translations_by_value.entry(text).or_default().push(translation);
+        }
+    }
+
+    // Distinct loose forms, sorted by length so only values of comparable length are compared when the length ratio
+    // bounds the similarity, as the normalized Levenshtein one, Jaro-Winkler comparing every pair
+    let mut loose_values: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+    for value in translations_by_value.keys() {
+        loose_values.entry(normalize_text(value, &LOOSE_NORMALIZATION)).or_default().push(value);
+    }
+    let mut loose_texts: Vec<(String, Vec<&'a str>)> = loose_values.into_iter().collect();
+    loose_texts.sort_by_key(|(text, _)| text.chars().count());
+
+    let lengths: Vec<usize> = loose_texts.iter().map(|(text, _)| text.chars().count()).collect();
+    let similar_pairs: Vec<(usize, usize)> = (0..loose_texts.len())
+        .into_par_iter()
+        .flat_map_iter(|i| {
+            let loose_texts = &loose_texts;
+            let lengths = &lengths;
+            ((i + 1)..loose_texts.len())
+                .take_while(move |j| algorithm != SimilarityAlgorithm::Levenshtein || lengths[i] as f64 >= lengths[*j] as f64 * threshold)
+                .filter(move |j| compute_similarity(algorithm, &loose_texts[i].0, &loose_texts[*j].0) >= threshold)
+                .map(move |j| (i, j))
+        })
+        .collect();
+
+    let mut parents: Vec<usize> = (0..loose_texts.len()).collect();
+    for (i, j) in similar_pairs {
+        let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+        parents[root_i.max(root_j)] = root_i.min(root_j);
+    }
+
+    let mut values_by_root: BTreeMap<usize, Vec<&'a str>> = BTreeMap::new();
+    for (index, (_, values)) in loose_texts.iter().enumerate() {
+        let root = find_root(&mut parents, index);
+        values_by_root.entry(root).or_default().extend(values.iter().copied());
+    }
+
+    let mut clusters: Vec<SimilarValuesCluster<'a>> = values_by_root
+        .into_values()
+        .filter(|values| values.len() > 1)
+        .map(|values| {
+            let mut values: Vec<(&'a str, Vec<&'a Translation>)> = values
+                .into_iter()
+                .map(|value| (value, translations_by_value.remove(value).unwrap_or_default()))
+                .collect();
+            values.sort_by(|(value_a, translations_a), (value_b, translations_b)| {
+                translations_b.len().cmp(&translations_a.len()).then_with(|| compare_text(value_a, value_b))
+            });
+            SimilarValuesCluster { canonical_value: values[0].0, values }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| b.usages().cmp(&a.usages()).then_with(|| compare_text(a.canonical_value, b.canonical_value)));
+    clusters
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+    root
+}
+
+pub fn print_similar_values_clusters(clusters: &[SimilarValuesCluster]) {
+    println!("Similar values clusters :");
+    println!("Clusters : {}", clusters.len());
+
+    for cluster in clusters {
+        println!("\n");
+        println!(" ========= {} values, {} usages ==========", cluster.values.len(), cluster.usages());
+        println!("Suggested canonical value : {}", Value::from(cluster.canonical_value));
+        for (value, translations) in &cluster.values {
+            println!("{} - {} usages", Value::from(*value), translations.len());
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::cmp::Ordering;
+
+/// Compares two texts for display ordering
+/// With the `collation` feature, Latin letters are compared without accents and case first (so "école" sorts next
+/// to "ecole" and "œuvre" next to "oeuvre"), accents then case only breaking ties. This is not a locale collation:
+/// other scripts keep the order of their code points, and no locale specific rule is applied
+#[cfg(feature = "collation")]
+pub fn compare_text(a: &str, b: &str) -> Ordering {
+    let primary = |text: &str| -> String {
+        text.chars()
+            .map(|c| match expand_ligature(c) {
+                Some(letters) => letters.to_string(),
+                None => fold_diacritic(c).to_lowercase().collect(),
+            })
+            .collect()
+    };
+    let secondary = |text: &str| -> Vec<char> { text.chars().flat_map(char::to_lowercase).collect() };
+
+    primary(a)
+        .cmp(&primary(b))
+        .then_with(|| secondary(a).cmp(&secondary(b)))
+        // Lowercase first, as in most locales
+        .then_with(|| b.cmp(a))
+}
+
+#[cfg(not(feature = "collation"))]
+pub fn compare_text(a: &str, b: &str) -> Ordering {
+    a.cmp(b)
+}
+
+/// Letters of the Latin ligatures, compared as the letters they stand for
+#[cfg(feature = "collation")]
+fn expand_ligature(c: char) -> Option<&'static str> {
+    match c {
+        'œ' | 'Œ' => Some("oe"),
+        'æ' | 'Æ' => Some("ae"),
+        'ß' | 'ẞ' => Some("ss"),
+        _ => None,
+    }
+}
+
+/// Base letter of the accented Latin characters used by European locales
+#[cfg(feature = "collation")]
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => 'A',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => 'C',
+        'ď' | 'đ' => 'd',
+        'Ď' | 'Đ' => 'D',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => 'E',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => 'G',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => 'I',
+        'ł' | 'ĺ' | 'ļ' | 'ľ' => 'l',
+        'Ł' | 'Ĺ' | 'Ļ' | 'Ľ' => 'L',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => 'N',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => 'O',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'Ŕ' | 'Ŗ' | 'Ř' => 'R',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => 'S',
+        'ţ' | 'ť' => 't',
+        'Ţ' | 'Ť' => 'T',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => 'U',
+        'ý' | 'ÿ' => 'y',
+        'Ý' | 'Ÿ' => 'Y',
+        'ź' | 'ż' | 'ž' => 'z',
+        'Ź' | 'Ż' | 'Ž' => 'Z',
+        _ => c,
+    }
+}
@@ -0,0 +1,95 @@
+use serde_json::Value;
+use crate::translation_file::TranslationEntries;
+
+/// Parses a spreadsheet export whose header is a key column followed by a column per locale,
+/// as `key,fr_FR,en_GB`, into the entries of each locale in column order
+/// Fields follow RFC 4180: quoted with `"`, quotes doubled inside, possibly spanning lines.
+/// Empty cells are untranslated values and left out
+pub fn parse_csv_entries(content: &str) -> Result<Vec<(String, TranslationEntries)>, String> {
+    let mut rows = parse_csv_rows(content.strip_prefix('\u{feff}').unwrap_or(content))?.into_iter();
+
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    if header.len() < 2 {
+        return Err("the header needs a key column followed by a column per locale".to_string());
+    }
+    let mut locales: Vec<(String, TranslationEntries)> = header[1..]
+        .iter()
+        .map(|locale| (locale.trim().to_string(), TranslationEntries::default()))
+        .collect();
+    if locales.iter().any(|(locale, _)| locale.is_empty()) {
+        return Err("a locale column has an empty header".to_string());
+    }
+
+    for (line, row) in rows {
+        let Some((key, cells)) = row.split_first() else {
+            continue;
+        };
+        if key.is_empty() {
+            if cells.iter().all(String::is_empty) {
+                continue;
+            }
+            return Err(format!("line {} : values without key", line));
+        }
+        if cells.len() > locales.len() {
+            return Err(format!("line {} : more cells than locale columns", line));
+        }
+
+        for ((_, entries), cell) in locales.iter_mut().zip(cells) {
+            if !cell.is_empty() {
+                entries.0.push((key.clone(), Value::String(cell.clone())));
+            }
+        }
+    }
+
+    Ok(locales)
+}
+
+/// Rows with the physical line they start at, quoted fields spanning lines
+fn parse_csv_rows(content: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            '"' => return Err(format!("line {} : quote inside an unquoted field", line)),
+            _ if quoted => field.push(c),
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                row_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(format!("line {} : unterminated quoted field", line));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+
+    Ok(rows)
+}
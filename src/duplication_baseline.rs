@@ -0,0 +1,82 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::analyse_project_duplication::DuplicationReport;
+use crate::cluster_id::legacy_cluster_id;
+use crate::safe_write::write_output_file;
+
+#[derive(Error, Debug)]
+pub enum DuplicationBaselineError {
+    #[error("Unable to read duplication baseline: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid duplication baseline format: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("Unable to write duplication baseline: {0}")]
+    UnableToWritePath(String, #[source] std::io::Error),
+}
+
+/// Duplications known when the baseline was created, so only the new ones are reported
+#[derive(Serialize, Deserialize, Default)]
+pub struct DuplicationBaseline {
+    pub fingerprints: BTreeSet<String>,
+}
+
+/// Identifies a duplication across runs: the file relative to the root path, the key and the cluster of the value
+/// Moving the file, renaming the key or changing the value makes the duplication a new one
+pub fn duplication_fingerprint(monorepo_path: &Path, duplication: &DuplicationReport) -> String {
+    fingerprint(monorepo_path, duplication, &duplication.cluster_id)
+}
+
+/// Fingerprint of a duplication with the given id of its cluster
+fn fingerprint(monorepo_path: &Path, duplication: &DuplicationReport, cluster_id: &str) -> String {
+    let path = duplication.translation.path.strip_prefix(monorepo_path).unwrap_or(&duplication.translation.path);
+    let path: Vec<_> = path.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+
+    format!("{}:{}:{}", path.join("/"), duplication.translation.key, cluster_id)
+}
+
+/// Reads the duplication baseline, a missing file meaning no baseline
+pub fn get_duplication_baseline(baseline_file_path: &Path) -> Result<Option<DuplicationBaseline>, DuplicationBaselineError> {
+    match fs::read_to_string(baseline_file_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| DuplicationBaselineError::InvalidFormat(baseline_file_path.to_string_lossy().to_string(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(DuplicationBaselineError::UnableToReadPath(baseline_file_path.to_string_lossy().to_string(), e)),
+    }
+}
+
+pub fn write_duplication_baseline(baseline_file_path: &Path, baseline: &DuplicationBaseline) -> Result<(), DuplicationBaselineError> {
+    let content = serde_json::to_string_pretty(baseline).unwrap_or_default();
+
+    write_output_file(baseline_file_path, content + "\n")
+        .map_err(|e| DuplicationBaselineError::UnableToWritePath(baseline_file_path.to_string_lossy().to_string(), e))
+}
+
+/// Baseline of every duplication of the packages
+pub fn create_duplication_baseline(monorepo_path: &Path, duplications_by_package: &BTreeMap<String, Vec<DuplicationReport>>) -> DuplicationBaseline {
+    let fingerprints = duplications_by_package
+        .values()
+        .flatten()
+        .map(|duplication| duplication_fingerprint(monorepo_path, duplication))
+        .collect();
+
+    DuplicationBaseline { fingerprints }
+}
+
+/// Removes the duplications of the baseline, returning how many were removed
+/// Fingerprints written with the legacy 32-bit cluster ids still match
+pub fn apply_duplication_baseline(monorepo_path: &Path, duplications: &mut Vec<DuplicationReport>, baseline: &DuplicationBaseline) -> usize {
+    let before = duplications.len();
+    duplications.retain(|duplication| {
+        !baseline.fingerprints.contains(&duplication_fingerprint(monorepo_path, duplication))
+            && !baseline.fingerprints.contains(&fingerprint(monorepo_path, duplication, legacy_cluster_id(&duplication.cluster_id)))
+    });
+
+    before - duplications.len()
+}
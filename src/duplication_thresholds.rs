@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Caps on the duplications enforced by the `check` command, so it can gate merges
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct DuplicationThresholds {
+    /// Maximum number of duplications of the monorepo, the ones of the baseline included
+    pub max_duplications: Option<usize>,
+    /// Maximum number of duplications of a package, the ones of the baseline included
+    pub max_duplications_by_package: Option<usize>,
+    /// Maximum number of duplications missing from the baseline
+    pub max_new_duplications: Option<usize>,
+}
+
+impl DuplicationThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.max_duplications.is_none() && self.max_duplications_by_package.is_none() && self.max_new_duplications.is_none()
+    }
+}
+
+/// Duplication counts the thresholds are checked against
+pub struct DuplicationCounts {
+    /// Duplications of each package, the ones of the baseline included
+    pub by_package: BTreeMap<String, usize>,
+    /// Duplications missing from the baseline, every duplication when there is no baseline
+    pub new: usize,
+}
+
+pub enum DuplicationThresholdAlert {
+    TotalExceeded { count: usize, max: usize },
+    PackageExceeded { package_path: String, count: usize, max: usize },
+    NewExceeded { count: usize, max: usize },
+}
+
+impl fmt::Display for DuplicationThresholdAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicationThresholdAlert::TotalExceeded { count, max } => write!(f, "{} duplications, the maximum being {}", count, max),
+            DuplicationThresholdAlert::PackageExceeded { package_path, count, max } => {
+                write!(f, "{} : {} duplications, the maximum by package being {}", package_path, count, max)
+            }
+            DuplicationThresholdAlert::NewExceeded { count, max } => write!(f, "{} new duplications since the baseline, the maximum being {}", count, max),
+        }
+    }
+}
+
+pub fn check_duplication_thresholds(counts: &DuplicationCounts, thresholds: &DuplicationThresholds) -> Vec<DuplicationThresholdAlert> {
+    let mut alerts = Vec::new();
+
+    let total: usize = counts.by_package.values().sum();
+    if let Some(max) = thresholds.max_duplications
+        && total > max
+    {
+        alerts.push(DuplicationThresholdAlert::TotalExceeded { count: total, max });
+    }
+
+    if let Some(max) = thresholds.max_duplications_by_package {
+        for (package_path, &count) in &counts.by_package {
+            if count > max {
+                alerts.push(DuplicationThresholdAlert::PackageExceeded { package_path: package_path.clone(), count, max });
+            }
+        }
+    }
+
+    if let Some(max) = thresholds.max_new_duplications
+        && counts.new > max
+    {
+        alerts.push(DuplicationThresholdAlert::NewExceeded { count: counts.new, max });
+    }
+
+    alerts
+}
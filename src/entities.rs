@@ -1,21 +1,142 @@
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::normalize_value::normalize_text;
+use crate::settings::ValueNormalization;
 
 
-fn serialize_path_lossy<S>(path: &PathBuf, serializer: S) -> Result<S::Ok, S::Error>
+fn serialize_path_lossy<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
 {
     serializer.serialize_str(&path.to_string_lossy())
 }
 
+/// Format of the file a translation was read from
+/// Every parser stores values JSON encoded, so values are indexed and compared whatever their format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationFormat {
+    Json,
+    /// Gettext catalogs, `.po` files and `.pot` templates
+    Po,
+    /// Apple `.strings` files
+    Strings,
+    /// Android `strings.xml` resources
+    #[serde(rename = "android_xml")]
+    AndroidXml,
+    /// JavaScript and TypeScript modules exporting an object literal by default
+    #[serde(rename = "js_module")]
+    JsModule,
+    /// Spreadsheet exports, a key column followed by a column per locale
+    Csv,
+    Toml,
+}
+
+impl TranslationFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" | "jsonc" => Some(TranslationFormat::Json),
+            "po" | "pot" => Some(TranslationFormat::Po),
+            "strings" => Some(TranslationFormat::Strings),
+            "xml" => Some(TranslationFormat::AndroidXml),
+            "js" | "mjs" | "cjs" | "ts" | "mts" | "cts" => Some(TranslationFormat::JsModule),
+            "csv" => Some(TranslationFormat::Csv),
+            "toml" => Some(TranslationFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a translation as read from its file, strings being compared on their content
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationValue {
+    String(String),
+    /// Lists, as the values of a select or the lines of a paragraph
+    Array(Vec<Value>),
+    /// Objects left after flattening, as the objects of an array, or given by a downstream loader
+    Nested(Map<String, Value>),
+    /// Numbers, booleans and nulls
+    Other(Value),
+}
+
+impl TranslationValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TranslationValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            TranslationValue::String(text) => Value::String(text.clone()),
+            TranslationValue::Array(values) => Value::Array(values.clone()),
+            TranslationValue::Nested(object) => Value::Object(object.clone()),
+            TranslationValue::Other(value) => value.clone(),
+        }
+    }
+
+    /// Key of the value in the duplication index, string contents being normalized
+    /// Keys are JSON encoded, so the string `"1"` and the number `1` are different values
+    pub fn normalized(&self, normalization: &ValueNormalization) -> String {
+        match self {
+            TranslationValue::String(text) => Value::String(normalize_text(text, normalization)).to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl From<Value> for TranslationValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::String(text) => TranslationValue::String(text),
+            Value::Array(values) => TranslationValue::Array(values),
+            Value::Object(object) => TranslationValue::Nested(object),
+            value => TranslationValue::Other(value),
+        }
+    }
+}
+
+/// JSON encoding of the value, as written in translation files
+impl fmt::Display for TranslationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+impl Serialize for TranslationValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_json().serialize(serializer)
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Translation {
     #[serde(serialize_with = "serialize_path_lossy")]
     pub path: PathBuf,
-    pub translations: String,
+    pub translations: TranslationValue,
     pub key: String,
+    /// Locale of the value, captured from the file name by the loading regexes or given by the file
+    /// itself when it holds several locales, as the columns of a CSV export
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl Translation {
+    /// Returns the string content when the value is a string
+    pub fn text(&self) -> Option<&str> {
+        self.translations.as_str()
+    }
+
+    pub fn format(&self) -> Option<TranslationFormat> {
+        TranslationFormat::from_path(&self.path)
+    }
 }
 
 
@@ -37,3 +158,12 @@ impl FromStr for PackageType {
         }
     }
 }
+
+impl fmt::Display for PackageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageType::Apps => write!(f, "apps"),
+            PackageType::Modules => write!(f, "modules"),
+        }
+    }
+}
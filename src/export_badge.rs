@@ -0,0 +1,82 @@
+use std::path::Path;
+use clap::ValueEnum;
+use crate::run_stamp::RunStamp;
+use crate::safe_write::write_output_file;
+
+const CHARACTER_WIDTH: usize = 7;
+const TEXT_PADDING: usize = 10;
+
+/// Figure shown by the badge
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum BadgeMetric {
+    /// Total duplication count
+    #[default]
+    Duplications,
+    /// Health score of the monorepo, the mean of the package scores weighted by their translations
+    Score,
+}
+
+/// Badge colors following the shields.io palette
+pub fn badge_color_for_duplications(total_duplications: usize) -> &'static str {
+    match total_duplications {
+        0 => "#4c1",
+        1..=99 => "#dfb317",
+        100..=999 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+pub fn badge_color_for_score(score: u8) -> &'static str {
+    match score {
+        90.. => "#4c1",
+        75..=89 => "#dfb317",
+        50..=74 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// Renders a flat shields.io style SVG badge
+pub fn render_badge(label: &str, message: &str, color: &str) -> String {
+    let label_width = label.chars().count() * CHARACTER_WIDTH + TEXT_PADDING;
+    let message_width = message.chars().count() * CHARACTER_WIDTH + TEXT_PADDING;
+    let total_width = label_width + message_width;
+    let label = escape_xml(label);
+    let message = escape_xml(message);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{total_width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{total_width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+/// Adds the run stamp to a badge as a leading XML comment, which renderers ignore
+pub fn stamp_badge(badge: &str, stamp: &RunStamp) -> String {
+    let generated_at = stamp.generated_at.as_ref().map_or(String::new(), |generated_at| format!(", generated at {}", generated_at));
+    format!("<!-- run {}{} -->\n{}", escape_xml(&stamp.run_id).replace("--", "- -"), generated_at, badge)
+}
+
+pub fn write_badge(output_path: &Path, badge: &str) -> Result<(), std::io::Error> {
+    write_output_file(output_path, badge)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use crate::entities::TranslationFormat;
+use crate::annotations::{Annotation, Annotations};
+use crate::analyse_project_duplication::{DuplicationReport, DuplicationSummary, DuplicationType};
+use crate::auto_triage::TriageTag;
+use crate::run_stamp::RunStamp;
+use crate::safe_write::write_output_file;
+
+#[derive(Error, Debug)]
+pub enum ExportJsonError {
+    #[error("Unable to serialize the report: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    #[error("Unable to write the report: {0}")]
+    UnableToWritePath(String, #[source] std::io::Error),
+
+    #[error("Unable to read the report: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid report {0}: {1}")]
+    InvalidReport(String, String),
+
+    #[error("Report schema version {0} is newer than the supported version {1}")]
+    UnsupportedSchemaVersion(u64, u64),
+}
+
+/// Version of the JSON report model, increased whenever a change requires converting older reports
+/// Reports written before versioning have no `schema_version` and are version 1
+pub const JSON_REPORT_SCHEMA_VERSION: u64 = 7;
+
+/// Machine readable duplication report, for CI tooling and dashboards
+#[derive(Serialize)]
+pub struct JsonReport<'a> {
+    pub schema_version: u64,
+    pub run_id: &'a str,
+    pub generated_at: Option<&'a str>,
+    pub summary: DuplicationSummary,
+    pub packages: Vec<JsonPackageReport<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct JsonPackageReport<'a> {
+    pub package_path: &'a str,
+    pub summary: DuplicationSummary,
+    pub duplications: Vec<JsonDuplication<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct JsonDuplication<'a> {
+    pub cluster_id: &'a str,
+    pub duplication_type: &'a DuplicationType,
+    pub path: String,
+    pub format: Option<TranslationFormat>,
+    pub key: &'a str,
+    pub canonical_key: &'a str,
+    /// Package path of the common translations module having the value, for common translation duplications
+    pub common_module: Option<&'a str>,
+    pub value: Value,
+    pub annotation: Option<&'a Annotation>,
+    pub triage: Option<&'a TriageTag>,
+}
+
+pub fn build_json_report<'a>(monorepo_path: &Path, duplications_by_package: &'a BTreeMap<String, Vec<DuplicationReport>>, annotations: &'a Annotations, stamp: &'a RunStamp) -> JsonReport<'a> {
+    let mut summary = DuplicationSummary::default();
+
+    let packages = duplications_by_package
+        .iter()
+        .map(|(package_path, duplications)| {
+            let package_summary = DuplicationSummary::from_reports(duplications);
+            summary.add(&package_summary);
+
+            JsonPackageReport {
+                package_path,
+                summary: package_summary,
+                duplications: duplications
+                    .iter()
+                    .map(|duplication| JsonDuplication {
+                        cluster_id: &duplication.cluster_id,
+                        duplication_type: &duplication.duplication_type,
+                        path: duplication.translation.path.strip_prefix(monorepo_path).unwrap_or(&duplication.translation.path).to_string_lossy().to_string(),
+                        format: duplication.translation.format(),
+                        key: &duplication.translation.key,
+                        canonical_key: &duplication.canonical_key,
+                        common_module: duplication.common_module.as_deref(),
+                        value: duplication.translation.translations.to_json(),
+                        annotation: annotations.get(&duplication.cluster_id),
+                        triage: duplication.triage.as_ref(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    JsonReport { schema_version: JSON_REPORT_SCHEMA_VERSION, run_id: &stamp.run_id, generated_at: stamp.generated_at.as_deref(), summary, packages }
+}
+
+pub fn write_json_report(output_path: &Path, report: &impl Serialize) -> Result<(), ExportJsonError> {
+    let content = serde_json::to_string_pretty(report).map_err(ExportJsonError::Serialize)?;
+
+    write_output_file(output_path, content + "\n")
+        .map_err(|e| ExportJsonError::UnableToWritePath(output_path.to_string_lossy().to_string(), e))
+}
+
+/// Reads a JSON report written by any release, converted to the current schema version
+pub fn read_json_report(input_path: &Path) -> Result<Value, ExportJsonError> {
+    let display_path = input_path.to_string_lossy().to_string();
+    let content = fs::read_to_string(input_path).map_err(|e| ExportJsonError::UnableToReadPath(display_path.clone(), e))?;
+    let report: Value = serde_json::from_str(&content).map_err(|e| ExportJsonError::InvalidReport(display_path.clone(), e.to_string()))?;
+
+    upgrade_json_report(report).map_err(|error| match error {
+        ExportJsonError::InvalidReport(_, reason) => ExportJsonError::InvalidReport(display_path, reason),
+        error => error,
+    })
+}
+
+/// Converts a JSON report to the current schema version, one version at a time
+pub fn upgrade_json_report(mut report: Value) -> Result<Value, ExportJsonError> {
+    let invalid = |reason: &str| ExportJsonError::InvalidReport(String::new(), reason.to_string());
+
+    let mut version = match report.get("schema_version") {
+        Some(version) => version.as_u64().ok_or_else(|| invalid("`schema_version` is not a number"))?,
+        None => 1,
+    };
+    if version > JSON_REPORT_SCHEMA_VERSION {
+        return Err(ExportJsonError::UnsupportedSchemaVersion(version, JSON_REPORT_SCHEMA_VERSION));
+    }
+
+    while version < JSON_REPORT_SCHEMA_VERSION {
+        match version {
+            1 => upgrade_from_v1(&mut report).ok_or_else(|| invalid("expected a version 1 report"))?,
+            2 => upgrade_from_v2(&mut report).ok_or_else(|| invalid("expected a version 2 report"))?,
+            3 => upgrade_from_v3(&mut report).ok_or_else(|| invalid("expected a version 3 report"))?,
+            4 => upgrade_from_v4(&mut report).ok_or_else(|| invalid("expected a version 4 report"))?,
+            5 => upgrade_from_v5(&mut report).ok_or_else(|| invalid("expected a version 5 report"))?,
+            6 => upgrade_from_v6(&mut report).ok_or_else(|| invalid("expected a version 6 report"))?,
+            _ => unreachable!("every version below the current one has a converter"),
+        }
+        version += 1;
+        report["schema_version"] = Value::from(version);
+    }
+
+    Ok(report)
+}
+
+/// Version 2 adds the canonical key of each duplication, the key itself being the only one known to older reports
+fn upgrade_from_v1(report: &mut Value) -> Option<()> {
+    for package in report.get_mut("packages")?.as_array_mut()? {
+        for duplication in package.get_mut("duplications")?.as_array_mut()? {
+            let key = duplication.get("key")?.clone();
+            duplication.as_object_mut()?.entry("canonical_key").or_insert(key);
+        }
+    }
+    Some(())
+}
+
+/// Version 3 adds the annotation of each duplication, older reports having none
+fn upgrade_from_v2(report: &mut Value) -> Option<()> {
+    for package in report.get_mut("packages")?.as_array_mut()? {
+        for duplication in package.get_mut("duplications")?.as_array_mut()? {
+            duplication.as_object_mut()?.entry("annotation").or_insert(Value::Null);
+        }
+    }
+    Some(())
+}
+
+/// Version 4 adds the file format of each duplication, deduced from the extension of its path
+fn upgrade_from_v3(report: &mut Value) -> Option<()> {
+    for package in report.get_mut("packages")?.as_array_mut()? {
+        for duplication in package.get_mut("duplications")?.as_array_mut()? {
+            let format = TranslationFormat::from_path(Path::new(duplication.get("path")?.as_str()?));
+            duplication.as_object_mut()?.entry("format").or_insert(serde_json::to_value(format).ok()?);
+        }
+    }
+    Some(())
+}
+
+/// Version 5 adds the test fixture duplication counts, older reports counting them as other duplications
+fn upgrade_from_v4(report: &mut Value) -> Option<()> {
+    report.get_mut("summary")?.as_object_mut()?.entry("test_fixture").or_insert(Value::from(0));
+    for package in report.get_mut("packages")?.as_array_mut()? {
+        package.get_mut("summary")?.as_object_mut()?.entry("test_fixture").or_insert(Value::from(0));
+    }
+    Some(())
+}
+
+/// Version 6 adds the run identifier and date, unknown for older reports
+fn upgrade_from_v5(report: &mut Value) -> Option<()> {
+    let report = report.as_object_mut()?;
+    report.entry("run_id").or_insert(Value::Null);
+    report.entry("generated_at").or_insert(Value::Null);
+    Some(())
+}
+
+/// Version 7 adds the triage rule tag of each duplication, older reports having none
+fn upgrade_from_v6(report: &mut Value) -> Option<()> {
+    for package in report.get_mut("packages")?.as_array_mut()? {
+        for duplication in package.get_mut("duplications")?.as_array_mut()? {
+            duplication.as_object_mut()?.entry("triage").or_insert(Value::Null);
+        }
+    }
+    Some(())
+}
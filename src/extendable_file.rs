@@ -0,0 +1,69 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExtendableFileError {
+    #[error("Unable to read {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid format of {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("{0} extends itself through its `extends` chain")]
+    ExtendsCycle(String),
+}
+
+/// Side file extending other ones, the plain form being a list of entries
+/// `{"extends": ["../upstream/translations-allow.json"], "entries": [...]}` lets a forked monorepo
+/// inherit the decisions of the upstream one and only keep its own delta
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExtendingFile<T> {
+    #[serde(default)]
+    extends: Vec<String>,
+    #[serde(default = "Vec::new")]
+    entries: Vec<T>,
+}
+
+/// Reads the entries of a side file after the ones of the files it extends, recursively
+/// Extended paths are relative to the extending file. A missing root file means no entry, a missing
+/// extended file is an error
+pub fn read_extendable_entries<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, ExtendableFileError> {
+    let mut entries = Vec::new();
+    read_into(path, true, &mut Vec::new(), &mut entries)?;
+    Ok(entries)
+}
+
+fn read_into<T: DeserializeOwned>(path: &Path, optional: bool, chain: &mut Vec<PathBuf>, entries: &mut Vec<T>) -> Result<(), ExtendableFileError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if optional && e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(ExtendableFileError::UnableToReadPath(path.to_string_lossy().to_string(), e)),
+    };
+
+    let identity = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&identity) {
+        return Err(ExtendableFileError::ExtendsCycle(path.to_string_lossy().to_string()));
+    }
+
+    let invalid = |e| ExtendableFileError::InvalidFormat(path.to_string_lossy().to_string(), e);
+    if content.trim_start().starts_with('[') {
+        entries.extend(serde_json::from_str::<Vec<T>>(&content).map_err(invalid)?);
+        return Ok(());
+    }
+
+    let file: ExtendingFile<T> = serde_json::from_str(&content).map_err(invalid)?;
+    chain.push(identity);
+    let directory = path.parent().unwrap_or(Path::new(""));
+    for extended_path in file.extends {
+        read_into(&directory.join(extended_path), false, chain, entries)?;
+    }
+    chain.pop();
+    entries.extend(file.entries);
+
+    Ok(())
+}
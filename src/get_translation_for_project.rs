@@ -1,14 +1,15 @@
 use crate::entities::Translation;
-use crate::map_translations_by_project::get_package_path;
+use crate::map_translations_by_project::PackagePathPattern;
 
 pub fn get_translations_for_project<'a>(
     project_path: &str,
     translation: &'a [Translation],
+    package_path_pattern: &PackagePathPattern,
 ) -> Vec<&'a Translation> {
     let mut translations: Vec<&'a Translation> = Vec::new();
 
     translation.iter().for_each(|translation| {
-        if get_package_path(translation.path.to_str().unwrap()) == project_path {
+        if package_path_pattern.package_path(&translation.path.to_string_lossy()) == project_path {
             translations.push(translation);
         }
     });
@@ -0,0 +1,76 @@
+use regex::Regex;
+
+/// Placeholder of a glob matching the locale of its files, as `**/Messages_{locale}.json`
+pub const LOCALE_PLACEHOLDER: &str = "{locale}";
+
+/// Compiles a glob matched against a whole `/` separated path: `*` and `?` stay within a path segment,
+/// `**` spans any number of segments, `[abc]` or `[!abc]` match one character of a set and `{locale}` matches
+/// a segment part captured as the `locale` group
+pub fn compile_glob(glob: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^{}$", glob_to_regex(glob)))
+}
+
+/// Compiles a glob matched against the end of a path, to capture the `{locale}` of files selected by it whatever
+/// the directory they were searched from
+pub fn compile_locale_glob(glob: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("(?:^|/){}$", glob_to_regex(glob)))
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::new();
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else if chars.peek().is_none() && pattern.ends_with('/') {
+                    // `dir/**` matches the directory itself as well as everything below it
+                    pattern.pop();
+                    pattern.push_str("(?:/.*)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '{' if chars.clone().take(LOCALE_PLACEHOLDER.len() - 1).eq(LOCALE_PLACEHOLDER.chars().skip(1)) => {
+                chars.nth(LOCALE_PLACEHOLDER.len() - 2);
+                pattern.push_str("(?P<locale>[^/]+)");
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                let mut closed = false;
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        closed = true;
+                        break;
+                    }
+                    class.push(class_char);
+                }
+                match (closed, class.strip_prefix('!')) {
+                    (true, Some(negated)) => pattern.push_str(&format!("[^{}]", escape_class(negated))),
+                    (true, None) => pattern.push_str(&format!("[{}]", escape_class(&class))),
+                    (false, _) => pattern.push_str(&regex::escape(&format!("[{}", class))),
+                }
+            }
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    pattern
+}
+
+/// Escapes the characters with a meaning in regex classes, ranges as `a-z` being kept
+fn escape_class(class: &str) -> String {
+    class.chars().fold(String::new(), |mut escaped, c| {
+        if matches!(c, '\\' | '[' | ']' | '^' | '&' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
@@ -0,0 +1,112 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use regex::Regex;
+use crate::analyse_project_duplication::{DuplicationReport, DuplicationType};
+use crate::entities::Translation;
+use crate::lint_keys::{lint_keys, KeyRules};
+use crate::locales::{translation_locale, LocaleMatcher};
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// Points each signal can remove from the score of 100
+const DUPLICATION_WEIGHT: f64 = 40.0;
+const MISSING_LOCALES_WEIGHT: f64 = 25.0;
+const NAMING_VIOLATIONS_WEIGHT: f64 = 20.0;
+const EMPTY_VALUES_WEIGHT: f64 = 15.0;
+/// Duplications of a value a dependency already provides count this many times, the fix being at hand
+const DEPENDENCY_DUPLICATION_FACTOR: usize = 2;
+
+/// Signals combined into the health score of a package
+#[derive(Debug, Default)]
+pub struct PackageHealth {
+    pub translations: usize,
+    pub duplications: usize,
+    /// Duplications whose value is already provided by a package this one depends on
+    pub dependency_duplications: usize,
+    pub empty_values: usize,
+    pub naming_violations: usize,
+    pub expected_locales: usize,
+    pub missing_locales: Vec<String>,
+}
+
+impl PackageHealth {
+    /// Score from 0 to 100, each signal removing its weight in proportion to the translations or locales affected
+    pub fn score(&self) -> u8 {
+        let weighted_duplications = self.duplications + self.dependency_duplications * (DEPENDENCY_DUPLICATION_FACTOR - 1);
+        let penalty = DUPLICATION_WEIGHT * ratio(weighted_duplications, self.translations)
+            + MISSING_LOCALES_WEIGHT * ratio(self.missing_locales.len(), self.expected_locales)
+            + NAMING_VIOLATIONS_WEIGHT * ratio(self.naming_violations, self.translations)
+            + EMPTY_VALUES_WEIGHT * ratio(self.empty_values, self.translations);
+
+        (100.0 - penalty).round().clamp(0.0, 100.0) as u8
+    }
+}
+
+/// Score of several packages, the mean of their scores weighted by their translations, 100 without translations
+pub fn overall_score(healths: &[PackageHealth]) -> u8 {
+    let translations: usize = healths.iter().map(|health| health.translations).sum();
+    if translations == 0 {
+        return 100;
+    }
+    let weighted: f64 = healths.iter().map(|health| health.score() as f64 * health.translations as f64).sum();
+    (weighted / translations as f64).round() as u8
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (count as f64 / total as f64).min(1.0)
+}
+
+/// Locales provided by each package, from the paths of the locale files, the locale matcher then the first override
+/// regex capturing a locale being used, and from the locales of their translations, as the columns of the files holding
+/// several locales
+/// Files without translation still provide the locale of their path
+pub fn locales_by_package(locale_files: &[PathBuf], translations: &[Translation], locale_matcher: &LocaleMatcher, override_regexes: &[Regex], package_path_pattern: &PackagePathPattern) -> BTreeMap<String, BTreeSet<String>> {
+    let mut locales_by_package: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for path in locale_files {
+        let locale = locale_matcher.locale(path).or_else(|| override_regexes.iter().find_map(|locale_regex| locale_matcher.capture(path, locale_regex)));
+        if let Some(locale) = locale {
+            locales_by_package.entry(package_path_pattern.package_path(&path.to_string_lossy())).or_default().insert(locale);
+        }
+    }
+    for translation in translations {
+        if let Some(locale) = translation_locale(translation, locale_matcher) {
+            locales_by_package.entry(package_path_pattern.package_path(&translation.path.to_string_lossy())).or_default().insert(locale);
+        }
+    }
+
+    locales_by_package
+}
+
+pub fn analyse_package_health(
+    translations: &[&Translation],
+    duplications: &[DuplicationReport],
+    key_rules: &KeyRules,
+    package_locales: &BTreeSet<String>,
+    expected_locales: &BTreeSet<String>,
+) -> Result<PackageHealth, regex::Error> {
+    Ok(PackageHealth {
+        translations: translations.len(),
+        duplications: duplications.iter().filter(|duplication| duplication.duplication_type != DuplicationType::TestFixture).count(),
+        dependency_duplications: duplications.iter().filter(|duplication| duplication.dependency_package.is_some()).count(),
+        empty_values: translations
+            .iter()
+            .filter(|translation| translation.text().is_some_and(|text| text.trim().is_empty()))
+            .count(),
+        naming_violations: lint_keys(translations, key_rules)?.len(),
+        expected_locales: expected_locales.len(),
+        missing_locales: expected_locales.difference(package_locales).cloned().collect(),
+    })
+}
+
+pub fn print_package_health(health: &PackageHealth) {
+    println!("Health score : {}/100", health.score());
+    println!("Reusable from dependencies : {}", health.dependency_duplications);
+    println!("Empty values : {}", health.empty_values);
+    println!("Naming violations : {}", health.naming_violations);
+    if !health.missing_locales.is_empty() {
+        println!("Missing locales : {}", health.missing_locales.join(", "));
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::entities::Translation;
+use crate::icu_message_format::PLURAL_KEYWORDS;
+
+const ORDINAL_MARKER: &str = "ordinal";
+
+/// How i18next suffixed keys are recognized, as `item_one` / `item_other` or `friend_male` / `friend_female`
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct I18nextKeys {
+    /// Groups the suffixed variants of a key, `false` for projects where `_one` or `_other` are plain words of keys
+    pub enabled: bool,
+    /// Context suffixes used with `t(key, { context })`, plural suffixes being always recognized
+    pub contexts: Vec<String>,
+}
+
+impl Default for I18nextKeys {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            contexts: vec!["male".to_string(), "female".to_string()],
+        }
+    }
+}
+
+/// A key split into its i18next parts, `friend_male_one` giving `friend`, `male` and `one`
+#[derive(Debug, PartialEq)]
+pub struct I18nextKey<'a> {
+    /// Logical key, as passed to `t()`
+    pub base_key: &'a str,
+    pub context: Option<&'a str>,
+    /// Plural category, `ordinal_` prefixed for ordinal plurals
+    pub plural_form: Option<&'a str>,
+}
+
+impl I18nextKeys {
+    pub fn parse<'a>(&self, key: &'a str) -> I18nextKey<'a> {
+        let mut parsed = I18nextKey { base_key: key, context: None, plural_form: None };
+        if !self.enabled {
+            return parsed;
+        }
+
+        if let Some((rest, form)) = parsed.base_key.rsplit_once('_')
+            && PLURAL_KEYWORDS.contains(&form)
+        {
+            let ordinal_start = rest.strip_suffix(ORDINAL_MARKER).and_then(|rest| rest.strip_suffix('_'));
+            let (rest, plural_form) = match ordinal_start {
+                Some(before_ordinal) => (before_ordinal, &key[before_ordinal.len() + 1..]),
+                None => (rest, form),
+            };
+            parsed.base_key = rest;
+            parsed.plural_form = Some(plural_form);
+        }
+
+        if let Some((rest, context)) = parsed.base_key.rsplit_once('_')
+            && self.contexts.iter().any(|known_context| known_context == context)
+        {
+            parsed.base_key = rest;
+            parsed.context = Some(context);
+        }
+
+        parsed
+    }
+
+    /// Key as passed to `t()`, without its context and plural suffixes
+    pub fn logical_key<'a>(&self, key: &'a str) -> &'a str {
+        self.parse(key).base_key
+    }
+
+    /// Whether two keys of a same file are variants of one logical key, as `item_one` and `item_other`
+    pub fn are_variants(&self, translation: &Translation, other: &Translation) -> bool {
+        translation.path == other.path && self.logical_key(&translation.key) == self.logical_key(&other.key)
+    }
+
+    /// Lists the occurrences of a value by file then logical key, the variants of a key being written
+    /// together as `item_{one,other}`
+    pub fn group_variants<'a>(&self, occurrences: &[&'a Translation]) -> Vec<(&'a Path, String)> {
+        let mut groups: BTreeMap<(&Path, &str), Vec<&str>> = BTreeMap::new();
+        for occurrence in occurrences {
+            let base_key = self.logical_key(&occurrence.key);
+            groups.entry((occurrence.path.as_path(), base_key)).or_default().push(&occurrence.key[base_key.len()..]);
+        }
+
+        groups
+            .into_iter()
+            .map(|((path, base_key), suffixes)| match suffixes.as_slice() {
+                [suffix] => (path, format!("{}{}", base_key, suffix)),
+                _ => {
+                    let suffixes: Vec<&str> = suffixes.iter().map(|suffix| suffix.trim_start_matches('_')).collect();
+                    (path, format!("{}_{{{}}}", base_key, suffixes.join(",")))
+                }
+            })
+            .collect()
+    }
+}
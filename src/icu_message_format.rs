@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+use std::iter::Peekable;
+use std::str::CharIndices;
+use thiserror::Error;
+
+pub const PLURAL_KEYWORDS: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+const SIMPLE_ARGUMENT_TYPES: [&str; 6] = ["number", "date", "time", "spellout", "ordinal", "duration"];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IcuSyntaxError {
+    #[error("unexpected `}}` at offset {0}")]
+    UnexpectedClosingBrace(usize),
+
+    #[error("unclosed `{{` opened at offset {0}")]
+    UnclosedBrace(usize),
+
+    #[error("missing argument name at offset {0}")]
+    MissingArgumentName(usize),
+
+    #[error("unknown argument type `{1}` at offset {0}")]
+    UnknownArgumentType(usize, String),
+
+    #[error("invalid selector `{1}` in {2} block at offset {0}")]
+    InvalidSelector(usize, String, String),
+
+    #[error("missing `other` case in {1} block at offset {0}")]
+    MissingOtherCase(usize, String),
+
+    #[error("expected `{{` after selector `{1}` at offset {0}")]
+    MissingCaseMessage(usize, String),
+}
+
+/// Validates a value against the ICU MessageFormat syntax
+/// `{{name}}` interpolations are considered as plain text since they are handled by the i18n library
+pub fn validate_icu_message(text: &str) -> Result<(), IcuSyntaxError> {
+    parse_icu_message(text).map(|_| ())
+}
+
+/// Keyword cases (`one`, `other`...) of every `plural` block of a valid message, explicit `=n` cases left out
+pub fn extract_plural_blocks(text: &str) -> Result<Vec<BTreeSet<String>>, IcuSyntaxError> {
+    parse_icu_message(text)
+}
+
+fn parse_icu_message(text: &str) -> Result<Vec<BTreeSet<String>>, IcuSyntaxError> {
+    let mut parser = Parser { chars: text.char_indices().peekable(), length: text.len(), plural_blocks: Vec::new() };
+    parser.parse_message(false)?;
+
+    match parser.chars.next() {
+        Some((offset, _)) => Err(IcuSyntaxError::UnexpectedClosingBrace(offset)),
+        None => Ok(parser.plural_blocks),
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    length: usize,
+    plural_blocks: Vec<BTreeSet<String>>,
+}
+
+impl Parser<'_> {
+    /// Parses text and arguments until a closing brace (left unconsumed) or the end of input
+    fn parse_message(&mut self, nested: bool) -> Result<(), IcuSyntaxError> {
+        while let Some(&(offset, c)) = self.chars.peek() {
+            match c {
+                '{' => {
+                    self.chars.next();
+                    if self.chars.peek().map(|&(_, next)| next) == Some('{') {
+                        self.skip_interpolation(offset)?;
+                    } else {
+                        self.parse_argument(offset)?;
+                    }
+                }
+                '}' if nested => return Ok(()),
+                '}' => return Err(IcuSyntaxError::UnexpectedClosingBrace(offset)),
+                '\'' => self.skip_quoted(),
+                _ => {
+                    self.chars.next();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_interpolation(&mut self, opening_offset: usize) -> Result<(), IcuSyntaxError> {
+        let mut previous = '{';
+        for (_, c) in self.chars.by_ref() {
+            if c == '}' && previous == '}' {
+                return Ok(());
+            }
+            previous = c;
+        }
+        Err(IcuSyntaxError::UnclosedBrace(opening_offset))
+    }
+
+    /// Apostrophes only quote when followed by a syntax character, `''` being a literal apostrophe
+    fn skip_quoted(&mut self) {
+        self.chars.next();
+        match self.chars.peek().map(|&(_, c)| c) {
+            Some('\'') => {
+                self.chars.next();
+            }
+            Some('{') | Some('}') | Some('#') | Some('|') => {
+                for (_, c) in self.chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn parse_argument(&mut self, opening_offset: usize) -> Result<(), IcuSyntaxError> {
+        self.skip_whitespaces();
+        let name_offset = self.offset();
+        if self.read_word().is_empty() {
+            return Err(IcuSyntaxError::MissingArgumentName(name_offset));
+        }
+        self.skip_whitespaces();
+
+        match self.chars.next() {
+            Some((_, '}')) => Ok(()),
+            Some((_, ',')) => {
+                self.skip_whitespaces();
+                let type_offset = self.offset();
+                let argument_type = self.read_word();
+                self.skip_whitespaces();
+
+                match argument_type.as_str() {
+                    "plural" | "selectordinal" | "select" => {
+                        self.parse_cases(opening_offset, &argument_type)
+                    }
+                    simple_type if SIMPLE_ARGUMENT_TYPES.contains(&simple_type) => {
+                        self.skip_style(opening_offset)
+                    }
+                    _ => Err(IcuSyntaxError::UnknownArgumentType(type_offset, argument_type)),
+                }
+            }
+            _ => Err(IcuSyntaxError::UnclosedBrace(opening_offset)),
+        }
+    }
+
+    fn skip_style(&mut self, opening_offset: usize) -> Result<(), IcuSyntaxError> {
+        for (_, c) in self.chars.by_ref() {
+            if c == '}' {
+                return Ok(());
+            }
+        }
+        Err(IcuSyntaxError::UnclosedBrace(opening_offset))
+    }
+
+    fn parse_cases(&mut self, opening_offset: usize, argument_type: &str) -> Result<(), IcuSyntaxError> {
+        if self.chars.next().map(|(_, c)| c) != Some(',') {
+            return Err(IcuSyntaxError::UnclosedBrace(opening_offset));
+        }
+
+        let is_plural = argument_type != "select";
+        let mut has_other = false;
+        let mut keyword_cases: BTreeSet<String> = BTreeSet::new();
+
+        loop {
+            self.skip_whitespaces();
+            let selector_offset = self.offset();
+            match self.chars.peek().map(|&(_, c)| c) {
+                Some('}') => {
+                    self.chars.next();
+                    break;
+                }
+                None => return Err(IcuSyntaxError::UnclosedBrace(opening_offset)),
+                _ => {}
+            }
+
+            let selector = self.read_word();
+            if is_plural && selector.starts_with("offset:") {
+                continue;
+            }
+            if !is_valid_selector(&selector, is_plural) {
+                return Err(IcuSyntaxError::InvalidSelector(selector_offset, selector, argument_type.to_string()));
+            }
+            has_other |= selector == "other";
+            if is_plural && PLURAL_KEYWORDS.contains(&selector.as_str()) {
+                keyword_cases.insert(selector.clone());
+            }
+
+            self.skip_whitespaces();
+            let case_offset = self.offset();
+            if self.chars.next().map(|(_, c)| c) != Some('{') {
+                return Err(IcuSyntaxError::MissingCaseMessage(selector_offset, selector));
+            }
+            self.parse_message(true)?;
+            if self.chars.next().is_none() {
+                return Err(IcuSyntaxError::UnclosedBrace(case_offset));
+            }
+        }
+
+        if !has_other {
+            return Err(IcuSyntaxError::MissingOtherCase(opening_offset, argument_type.to_string()));
+        }
+        if argument_type == "plural" {
+            self.plural_blocks.push(keyword_cases);
+        }
+        Ok(())
+    }
+
+    fn read_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c.is_whitespace() || matches!(c, '{' | '}' | ',') {
+                break;
+            }
+            word.push(c);
+            self.chars.next();
+        }
+        word
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map_or(self.length, |&(offset, _)| offset)
+    }
+}
+
+fn is_valid_selector(selector: &str, is_plural: bool) -> bool {
+    if is_plural {
+        return PLURAL_KEYWORDS.contains(&selector)
+            || selector.strip_prefix('=').is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    }
+    !selector.is_empty() && selector.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
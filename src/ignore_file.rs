@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use regex::Regex;
+use thiserror::Error;
+use crate::glob_pattern::compile_glob;
+
+/// Ignore file read in every walked directory, its patterns applying to the directory and below
+pub const IGNORE_FILE_NAME: &str = ".translationsignore";
+
+#[derive(Error, Debug)]
+pub enum IgnoreFileError {
+    #[error("Unable to read ignore file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid pattern in {0}: {1} - {2}")]
+    InvalidPattern(String, String, String),
+}
+
+/// Pattern of an ignore file, with the gitignore meaning
+struct IgnoreRule {
+    /// Directory of the ignore file, the pattern being matched against the paths relative to it
+    base: PathBuf,
+    regex: Regex,
+    /// `!` patterns include again what a previous pattern excluded
+    negated: bool,
+    /// Patterns ending with `/` only match directories
+    directory_only: bool,
+}
+
+/// Patterns of the ignore files of a directory and of its parents, the last matching one deciding
+#[derive(Default)]
+pub struct IgnoreRules(Vec<Arc<IgnoreRule>>);
+
+impl IgnoreRules {
+    /// Rules applying in the directory: these ones then the ones of its own ignore file, if any
+    pub fn with_directory(self: &Arc<Self>, directory: &Path) -> Result<Arc<Self>, IgnoreFileError> {
+        let ignore_file_path = directory.join(IGNORE_FILE_NAME);
+        if !ignore_file_path.is_file() {
+            return Ok(self.clone());
+        }
+
+        let content = fs::read_to_string(&ignore_file_path)
+            .map_err(|e| IgnoreFileError::UnableToReadPath(ignore_file_path.to_string_lossy().to_string(), e))?;
+        let mut rules = self.0.clone();
+        for pattern in content.lines().filter_map(ignore_pattern) {
+            let rule = parse_ignore_rule(directory, pattern)
+                .map_err(|e| IgnoreFileError::InvalidPattern(ignore_file_path.to_string_lossy().to_string(), pattern.to_string(), e.to_string()))?;
+            rules.push(Arc::new(rule));
+        }
+
+        Ok(Arc::new(Self(rules)))
+    }
+
+    pub fn is_ignored(&self, path: &Path, is_directory: bool) -> bool {
+        self.0
+            .iter()
+            .rev()
+            .find(|rule| {
+                (is_directory || !rule.directory_only)
+                    && path.strip_prefix(&rule.base).is_ok_and(|relative| {
+                        let relative: Vec<_> = relative.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+                        rule.regex.is_match(&relative.join("/"))
+                    })
+            })
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// Pattern of an ignore file line, `None` for blank lines and comments
+fn ignore_pattern(line: &str) -> Option<&str> {
+    // Trailing spaces are only kept when escaped
+    let pattern = if line.ends_with("\\ ") { line } else { line.trim_end() };
+    (!pattern.is_empty() && !pattern.starts_with('#')).then_some(pattern)
+}
+
+/// Patterns with a `/` before their end are relative to the directory of the ignore file,
+/// the others match at any depth
+fn parse_ignore_rule(base: &Path, pattern: &str) -> Result<IgnoreRule, regex::Error> {
+    let (negated, pattern) = match pattern.strip_prefix('!') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern.strip_prefix('\\').filter(|rest| rest.starts_with(['!', '#'])).unwrap_or(pattern)),
+    };
+    let (directory_only, pattern) = match pattern.strip_suffix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    let pattern = pattern.replace("\\ ", " ");
+
+    let glob = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern,
+        None => format!("**/{}", pattern),
+    };
+
+    Ok(IgnoreRule { base: base.to_path_buf(), regex: compile_glob(&glob)?, negated, directory_only })
+}
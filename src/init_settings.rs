@@ -0,0 +1,219 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use thiserror::Error;
+use crate::map_translations_by_project::DEFAULT_PACKAGE_PATH_REGEX;
+use crate::search_recursive_regex::{search_recursive_regex, SearchAllTranslationsFilesError};
+use crate::safe_write::write_output_file;
+
+/// File names holding a locale, as `Messages_fr_FR.json`, `en-GB.yaml` or `app.de.json`
+static LOCALE_FILE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?P<prefix>(?:.*[_.\-])?)(?P<language>[a-z]{2})(?:(?P<separator>[_\-])(?P<region>[A-Z]{2}))?(?P<extension>\.(?:json|ya?ml|properties|po|xlf|xliff|xml|csv|strings|arb))$").unwrap()
+});
+
+/// Package layouts tried in order, the first matching every translation file being proposed
+const PACKAGE_PATH_CANDIDATES: [&str; 3] = [
+    DEFAULT_PACKAGE_PATH_REGEX,
+    // Nx
+    r"(?P<type>apps|libs)/(?P<name>[^/]+)",
+    // Lerna and Turborepo workspaces
+    r"packages/(?P<name>[^/]+)",
+];
+
+/// Words of the package names proposed as common translations modules
+const COMMON_MODULE_WORDS: [&str; 4] = ["common", "shared", "i18n", "translations"];
+
+/// Number of translation files shown as samples
+const SAMPLE_FILES: usize = 5;
+
+#[derive(Error, Debug)]
+pub enum InitSettingsError {
+    #[error(transparent)]
+    Search(#[from] SearchAllTranslationsFilesError),
+
+    #[error("Settings file {0} already exists, use --force to replace it")]
+    AlreadyExists(String),
+
+    #[error("Unable to write settings file: {0}")]
+    UnableToWritePath(String, #[source] std::io::Error),
+}
+
+/// Settings guessed from the translation files of a monorepo, proposed by the `init` command
+#[derive(Debug, Default)]
+pub struct DetectedSettings {
+    /// Translation files of the most used naming, relative to the root path
+    pub translation_files: Vec<String>,
+    pub locales_file_regex: Option<String>,
+    pub locales: BTreeSet<String>,
+    pub reference_locale: Option<String>,
+    pub package_path_regex: Option<String>,
+    pub common_translations_modules_path: Vec<String>,
+}
+
+impl DetectedSettings {
+    pub fn sample_files(&self) -> &[String] {
+        &self.translation_files[..self.translation_files.len().min(SAMPLE_FILES)]
+    }
+}
+
+/// Regex of the reference locale files: the locales regex with the reference locale in place of its first group
+pub fn reference_file_regex(locales_file_regex: &str, reference_locale: &str) -> Option<String> {
+    let start = locales_file_regex.find('(')?;
+    let end = start + locales_file_regex[start..].find(')')?;
+
+    Some(format!("{}({}){}", &locales_file_regex[..start], regex::escape(reference_locale), &locales_file_regex[end + 1..]))
+}
+
+/// Shape of a translation file name, the locale left out
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct FileNameShape {
+    prefix: String,
+    /// Separator between the language and the region, `None` for languages alone
+    separator: Option<String>,
+    extension: String,
+}
+
+impl FileNameShape {
+    fn locales_file_regex(&self) -> String {
+        let locale = match &self.separator {
+            Some(separator) => format!("[a-z]{{2}}{}[A-Z]{{2}}", regex::escape(separator)),
+            None => "[a-z]{2}".to_string(),
+        };
+        format!("^{}({}){}$", regex::escape(&self.prefix), locale, regex::escape(&self.extension))
+    }
+}
+
+/// Inspects the files of the monorepo for the naming of its translation files, its locales, its package layout
+/// and its common translations modules
+pub fn detect_settings(monorepo_path: &Path, skip_directories: &[String]) -> Result<DetectedSettings, InitSettingsError> {
+    let files = search_recursive_regex(monorepo_path, LOCALE_FILE_NAME_REGEX.as_str(), skip_directories)?;
+
+    // The most used naming is the one of the translation files, the others being other locale-named files
+    let mut files_by_shape: BTreeMap<FileNameShape, Vec<(PathBuf, String)>> = BTreeMap::new();
+    for path in files {
+        let Some(file_name) = path.file_name().map(|file_name| file_name.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let Some(captures) = LOCALE_FILE_NAME_REGEX.captures(&file_name) else {
+            continue;
+        };
+        let shape = FileNameShape {
+            prefix: captures["prefix"].to_string(),
+            separator: captures.name("separator").map(|separator| separator.as_str().to_string()),
+            extension: captures["extension"].to_string(),
+        };
+        let locale = match (captures.name("separator"), captures.name("region")) {
+            (Some(separator), Some(region)) => format!("{}{}{}", &captures["language"], separator.as_str(), region.as_str()),
+            _ => captures["language"].to_string(),
+        };
+        files_by_shape.entry(shape).or_default().push((path, locale));
+    }
+    let Some((shape, mut files)) = files_by_shape.into_iter().max_by_key(|(_, files)| files.len()) else {
+        return Ok(DetectedSettings::default());
+    };
+    files.sort();
+
+    let locales: BTreeSet<String> = files.iter().map(|(_, locale)| locale.clone()).collect();
+    let relative_paths: Vec<String> = files.iter().map(|(path, _)| relative_path(monorepo_path, path)).collect();
+    let package_path_regex = detect_package_path_regex(&relative_paths);
+    let common_translations_modules_path = package_path_regex
+        .as_deref()
+        .map(|package_path_regex| detect_common_modules(package_path_regex, &relative_paths))
+        .unwrap_or_default();
+
+    Ok(DetectedSettings {
+        locales_file_regex: Some(shape.locales_file_regex()),
+        reference_locale: detect_reference_locale(&files),
+        locales,
+        package_path_regex,
+        common_translations_modules_path,
+        translation_files: relative_paths,
+    })
+}
+
+/// Locale whose files are the largest, the reference locale usually being the one with every key
+fn detect_reference_locale(files: &[(PathBuf, String)]) -> Option<String> {
+    let mut size_by_locale: BTreeMap<&str, u64> = BTreeMap::new();
+    for (path, locale) in files {
+        *size_by_locale.entry(locale).or_default() += fs::metadata(path).map_or(0, |metadata| metadata.len());
+    }
+
+    // Ties go to the first locale in alphabetical order
+    size_by_locale
+        .into_iter()
+        .max_by(|(locale_a, size_a), (locale_b, size_b)| size_a.cmp(size_b).then_with(|| locale_b.cmp(locale_a)))
+        .map(|(locale, _)| locale.to_string())
+}
+
+/// First package layout matching every translation file, the one matching the most otherwise
+fn detect_package_path_regex(relative_paths: &[String]) -> Option<String> {
+    PACKAGE_PATH_CANDIDATES
+        .iter()
+        .map(|candidate| {
+            let regex = Regex::new(candidate).expect("the package path candidates are valid");
+            (candidate, relative_paths.iter().filter(|path| regex.is_match(path)).count())
+        })
+        .filter(|(_, matched)| *matched > 0)
+        // The first candidate wins ties, `max_by_key` keeping the last maximum
+        .rev()
+        .max_by_key(|(_, matched)| *matched)
+        .map(|(candidate, _)| candidate.to_string())
+}
+
+/// Packages holding translation files whose name tells they are shared, as `common-translations`
+fn detect_common_modules(package_path_regex: &str, relative_paths: &[String]) -> Vec<String> {
+    let Ok(regex) = Regex::new(package_path_regex) else {
+        return Vec::new();
+    };
+
+    let common_modules: BTreeSet<String> = relative_paths
+        .iter()
+        .filter_map(|path| {
+            let captures = regex.captures(path)?;
+            let name = captures.name("name")?;
+            let start = captures.get(0)?.start();
+            COMMON_MODULE_WORDS
+                .iter()
+                .any(|word| name.as_str().contains(word))
+                .then(|| path[start..name.end()].to_string())
+        })
+        .collect();
+
+    common_modules.into_iter().collect()
+}
+
+fn relative_path(monorepo_path: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(monorepo_path).unwrap_or(path);
+    let components: Vec<_> = relative.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+    components.join("/")
+}
+
+/// Starter settings file with the chosen values, the other settings keeping their default
+pub fn starter_settings(
+    translation_file_regex: &str,
+    locales_file_regex: &str,
+    reference_locale: &str,
+    package_path_regex: &str,
+    common_translations_modules_path: &[String],
+) -> Value {
+    json!({
+        "translation_file_regex": translation_file_regex,
+        "locales_file_regex": locales_file_regex,
+        "reference_locale": reference_locale,
+        "package_path_regex": package_path_regex,
+        "common_translations_modules_path": common_translations_modules_path,
+    })
+}
+
+pub fn write_starter_settings(settings_file_path: &Path, settings: &Value, force: bool) -> Result<(), InitSettingsError> {
+    if settings_file_path.exists() && !force {
+        return Err(InitSettingsError::AlreadyExists(settings_file_path.to_string_lossy().to_string()));
+    }
+
+    let content = serde_json::to_string_pretty(settings).unwrap_or_default();
+    write_output_file(settings_file_path, content + "\n")
+        .map_err(|e| InitSettingsError::UnableToWritePath(settings_file_path.to_string_lossy().to_string(), e))
+}
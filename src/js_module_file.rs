@@ -0,0 +1,202 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use regex::Regex;
+use serde_json::{Map, Value};
+use crate::translation_file::TranslationEntries;
+
+const EXPORT_MARKERS: [&str; 2] = ["export default", "module.exports ="];
+
+/// Parses the object literal exported by default by a JavaScript or TypeScript module, as
+/// `export default { "form.save": "Enregistrer" } as const;` or `module.exports = { ... };`
+/// The exported value may also be a constant of the module, as `const messages = { ... }; export default messages;`
+/// Keys may be identifiers or quoted strings and values strings, template literals without
+/// substitutions or nested objects. Anything computed (variables, calls, spreads, `${}`) is rejected,
+/// the module having to be evaluated to know its value
+pub fn parse_js_module_entries(content: &str) -> Result<TranslationEntries, String> {
+    let mut start = EXPORT_MARKERS
+        .iter()
+        .filter_map(|marker| content.find(marker).map(|position| position + marker.len()))
+        .min()
+        .ok_or("no `export default` or `module.exports =` object")?;
+
+    let exported = content[start..].trim_start();
+    if exported.starts_with(|c: char| is_identifier_char(c)) {
+        let name: String = exported.chars().take_while(|c| is_identifier_char(*c)).collect();
+        let declaration = Regex::new(&format!(r"\b(?:const|let|var)\s+{}\s*(?::[^=]*)?=", regex::escape(&name)))
+            .map_err(|e| e.to_string())?;
+        start = declaration
+            .find(content)
+            .map(|declaration| declaration.end())
+            .ok_or_else(|| format!("exported `{}` is not declared in the module", name))?;
+    }
+
+    let line = content[..start].matches('\n').count() + 1;
+    let mut parser = JsObjectParser { chars: content[start..].chars().peekable(), line };
+    parser.skip_blanks()?;
+    if parser.chars.peek() == Some(&'(') {
+        parser.next();
+        parser.skip_blanks()?;
+    }
+    Ok(TranslationEntries(parser.read_object()?))
+}
+
+struct JsObjectParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl JsObjectParser<'_> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error(&self, reason: &str) -> String {
+        format!("line {} : {}", self.line, reason)
+    }
+
+    /// Skips whitespace, `//` line comments and `/* */` block comments
+    fn skip_blanks(&mut self) -> Result<(), String> {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.next();
+                continue;
+            }
+            if c != '/' {
+                break;
+            }
+
+            self.next();
+            match self.next() {
+                Some('/') => while self.next().is_some_and(|c| c != '\n') {},
+                Some('*') => {
+                    let mut previous = ' ';
+                    loop {
+                        match self.next() {
+                            Some('/') if previous == '*' => break,
+                            Some(c) => previous = c,
+                            None => return Err(self.error("unterminated comment")),
+                        }
+                    }
+                }
+                _ => return Err(self.error("unexpected `/`")),
+            }
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_blanks()?;
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected `{}`", expected))),
+        }
+    }
+
+    /// Entries of an object literal in source order, repeated keys included
+    fn read_object(&mut self) -> Result<Vec<(String, Value)>, String> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+
+        loop {
+            self.skip_blanks()?;
+            let key = match self.chars.peek() {
+                Some('}') => {
+                    self.next();
+                    return Ok(entries);
+                }
+                Some('"' | '\'') => self.read_string()?,
+                Some('[') => return Err(self.error("computed keys are not supported")),
+                Some('.') => return Err(self.error("spread entries are not supported")),
+                Some(c) if is_identifier_char(*c) => self.read_identifier(),
+                _ => return Err(self.error("expected a key")),
+            };
+
+            self.expect(':')?;
+            self.skip_blanks()?;
+            let value = match self.chars.peek() {
+                Some('"' | '\'') => Value::String(self.read_string()?),
+                Some('`') => Value::String(self.read_template()?),
+                // A repeated nested key keeps its last value, like in JavaScript
+                Some('{') => Value::Object(self.read_object()?.into_iter().collect::<Map<String, Value>>()),
+                _ => return Err(self.error(&format!("value of `{}` is not a string literal", key))),
+            };
+            entries.push((key, value));
+
+            self.skip_blanks()?;
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(entries),
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while let Some(&c) = self.chars.peek().filter(|c| is_identifier_char(**c)) {
+            identifier.push(c);
+            self.next();
+        }
+        identifier
+    }
+
+    fn read_string(&mut self) -> Result<String, String> {
+        let Some(quote) = self.next() else {
+            return Err(self.error("expected a string"));
+        };
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(text),
+                Some('\\') => self.read_escape(&mut text)?,
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn read_template(&mut self) -> Result<String, String> {
+        self.next();
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('`') => return Ok(text),
+                Some('\\') => self.read_escape(&mut text)?,
+                Some('$') if self.chars.peek() == Some(&'{') => return Err(self.error("template substitutions are not supported")),
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated template literal")),
+            }
+        }
+    }
+
+    fn read_escape(&mut self, text: &mut String) -> Result<(), String> {
+        match self.next() {
+            Some('n') => text.push('\n'),
+            Some('t') => text.push('\t'),
+            Some('r') => text.push('\r'),
+            Some('u') => {
+                let hex: String = if self.chars.peek() == Some(&'{') {
+                    self.next();
+                    self.chars.by_ref().take_while(|c| *c != '}').collect()
+                } else {
+                    (0..4).filter_map(|_| self.next()).collect()
+                };
+                let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                text.push(c.ok_or_else(|| self.error(&format!("invalid unicode escape `{}`", hex)))?);
+            }
+            // Line continuation
+            Some('\n') => {}
+            Some(escaped) => text.push(escaped),
+            None => return Err(self.error("unterminated string")),
+        }
+        Ok(())
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$')
+}
@@ -0,0 +1,189 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Turns JSONC content into plain JSON: `//` and `/* */` comments and trailing commas are blanked out
+/// Removed characters are replaced by spaces, newlines being kept, so parse errors keep their line and column
+/// JSON5 is not supported beyond these, as unquoted keys or single-quoted strings
+pub fn strip_json_comments(content: &str) -> String {
+    strip_jsonc(content).0
+}
+
+/// Whether the content has `//` or `/* */` comments, which are lost when the file is written back as JSON
+pub fn has_json_comments(content: &str) -> bool {
+    strip_jsonc(content).1
+}
+
+fn strip_jsonc(content: &str) -> (String, bool) {
+    let mut has_comments = false;
+    let chars: Vec<char> = content.chars().collect();
+    let mut stripped: Vec<char> = Vec::with_capacity(chars.len());
+    // Position in `stripped` of the last comma outside of strings, until a value follows it
+    let mut pending_comma: Option<usize> = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        match (c, chars.get(index + 1)) {
+            ('"', _) => {
+                pending_comma = None;
+                stripped.push(c);
+                index += 1;
+                while index < chars.len() {
+                    let string_char = chars[index];
+                    stripped.push(string_char);
+                    index += 1;
+                    match string_char {
+                        '\\' if index < chars.len() => {
+                            stripped.push(chars[index]);
+                            index += 1;
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            ('/', Some('/')) => {
+                has_comments = true;
+                while index < chars.len() && chars[index] != '\n' {
+                    stripped.push(' ');
+                    index += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                has_comments = true;
+                let end = chars[index + 2..]
+                    .windows(2)
+                    .position(|pair| pair == ['*', '/'])
+                    .map_or(chars.len(), |offset| index + 2 + offset + 2);
+                stripped.extend(chars[index..end].iter().map(|&comment_char| if comment_char == '\n' { '\n' } else { ' ' }));
+                index = end;
+                continue;
+            }
+            (',', _) => pending_comma = Some(stripped.len()),
+            ('}' | ']', _) => {
+                if let Some(comma) = pending_comma.take() {
+                    stripped[comma] = ' ';
+                }
+            }
+            (c, _) if c.is_whitespace() => {}
+            _ => pending_comma = None,
+        }
+        stripped.push(c);
+        index += 1;
+    }
+
+    (stripped.into_iter().collect(), has_comments)
+}
+
+/// Line of each field of a JSON or JSONC document, by its path as `triage_rules[3].key_pattern`
+/// The document is expected to be valid, as it is scanned once parsed
+pub fn json_field_lines(content: &str) -> Vec<(String, usize)> {
+    let stripped = strip_json_comments(content);
+    let mut scanner = JsonFieldScanner { chars: stripped.chars().peekable(), line: 1, field_lines: Vec::new() };
+    scanner.read_value("");
+    scanner.field_lines
+}
+
+struct JsonFieldScanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    field_lines: Vec<(String, usize)>,
+}
+
+impl JsonFieldScanner<'_> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    /// Value at the path, its fields and items being recorded
+    fn read_value(&mut self, path: &str) {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => {
+                self.next();
+                loop {
+                    self.skip_whitespace();
+                    match self.chars.peek() {
+                        Some('"') => {
+                            let line = self.line;
+                            let key = self.read_string();
+                            let field = if path.is_empty() { key } else { format!("{}.{}", path, key) };
+                            self.field_lines.push((field.clone(), line));
+                            self.skip_whitespace();
+                            if self.chars.peek() == Some(&':') {
+                                self.next();
+                            }
+                            self.read_value(&field);
+                        }
+                        Some('}') | None => {
+                            self.next();
+                            return;
+                        }
+                        Some(_) => {
+                            self.next();
+                        }
+                    }
+                }
+            }
+            Some('[') => {
+                self.next();
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.chars.peek() {
+                        Some(',') => {
+                            self.next();
+                            index += 1;
+                        }
+                        Some(']') | None => {
+                            self.next();
+                            return;
+                        }
+                        Some(_) => {
+                            let item = format!("{}[{}]", path, index);
+                            self.field_lines.push((item.clone(), self.line));
+                            self.read_value(&item);
+                        }
+                    }
+                }
+            }
+            Some('"') => {
+                self.read_string();
+            }
+            // Numbers, booleans and null, at least a character being read so a stray one cannot stall the scan
+            Some(_) => {
+                self.next();
+                while self.chars.peek().is_some_and(|c| !c.is_whitespace() && !matches!(c, ',' | '}' | ']')) {
+                    self.next();
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// String starting at the next `"`, its escaped characters being kept without their `\`
+    fn read_string(&mut self) -> String {
+        let mut text = String::new();
+        self.next();
+        while let Some(c) = self.next() {
+            match c {
+                '\\' => text.extend(self.next()),
+                '"' => break,
+                c => text.push(c),
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::entities::Translation;
+use crate::map_translations_by_project::{map_translations_by_project, PackagePathPattern};
+use crate::safe_write::write_output_file;
+
+#[derive(Error, Debug)]
+pub enum KeyCountsBaselineError {
+    #[error("Unable to read key counts baseline: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid key counts baseline: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+
+    #[error("Unable to write key counts baseline: {0}")]
+    UnableToWritePath(String, #[source] std::io::Error),
+}
+
+/// Caps on the number of keys of the packages, and on their growth since the baseline
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct KeyCountLimits {
+    pub max_keys: Option<usize>,
+    /// Caps of some packages, overriding `max_keys`
+    pub max_keys_by_package: BTreeMap<String, usize>,
+    /// Maximum growth of the key count of a package since the baseline, in percent
+    pub max_growth_percent: Option<f64>,
+    /// Key counts of the packages at the last baseline update, relative to the root path
+    pub baseline_file_path: String,
+}
+
+impl Default for KeyCountLimits {
+    fn default() -> Self {
+        Self {
+            max_keys: None,
+            max_keys_by_package: BTreeMap::new(),
+            max_growth_percent: None,
+            baseline_file_path: "translations-key-counts.json".to_string(),
+        }
+    }
+}
+
+pub enum KeyCountAlert {
+    CapExceeded { package_path: String, count: usize, cap: usize },
+    GrowthExceeded { package_path: String, previous_count: usize, count: usize, growth_percent: f64 },
+}
+
+impl fmt::Display for KeyCountAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyCountAlert::CapExceeded { package_path, count, cap } => {
+                write!(f, "{} : {} keys, the cap being {}", package_path, count, cap)
+            }
+            KeyCountAlert::GrowthExceeded { package_path, previous_count, count, growth_percent } => {
+                write!(f, "{} : {} keys, {:.1}% more than the {} keys of the baseline", package_path, count, growth_percent, previous_count)
+            }
+        }
+    }
+}
+
+/// Number of translations of each package
+pub fn count_keys_by_package(translations: &[Translation], package_path_pattern: &PackagePathPattern) -> BTreeMap<String, usize> {
+    map_translations_by_project(translations, package_path_pattern)
+        .into_iter()
+        .map(|(package_path, translations)| (package_path, translations.len()))
+        .collect()
+}
+
+/// Reads the key counts baseline, a missing file meaning no baseline
+pub fn get_key_counts_baseline(baseline_file_path: &Path) -> Result<BTreeMap<String, usize>, KeyCountsBaselineError> {
+    match fs::read_to_string(baseline_file_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| KeyCountsBaselineError::InvalidFormat(baseline_file_path.to_string_lossy().to_string(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(KeyCountsBaselineError::UnableToReadPath(baseline_file_path.to_string_lossy().to_string(), e)),
+    }
+}
+
+pub fn write_key_counts_baseline(baseline_file_path: &Path, counts: &BTreeMap<String, usize>) -> Result<(), KeyCountsBaselineError> {
+    let content = serde_json::to_string_pretty(counts).unwrap_or_default();
+
+    write_output_file(baseline_file_path, content + "\n")
+        .map_err(|e| KeyCountsBaselineError::UnableToWritePath(baseline_file_path.to_string_lossy().to_string(), e))
+}
+
+/// Alerts for the packages above their cap or growing faster than allowed, packages missing from the baseline
+/// having no growth
+pub fn check_key_counts(counts: &BTreeMap<String, usize>, baseline: &BTreeMap<String, usize>, limits: &KeyCountLimits) -> Vec<KeyCountAlert> {
+    let mut alerts: Vec<KeyCountAlert> = Vec::new();
+
+    for (package_path, &count) in counts {
+        if let Some(&cap) = limits.max_keys_by_package.get(package_path).or(limits.max_keys.as_ref())
+            && count > cap
+        {
+            alerts.push(KeyCountAlert::CapExceeded { package_path: package_path.clone(), count, cap });
+        }
+
+        if let Some(max_growth_percent) = limits.max_growth_percent
+            && let Some(&previous_count) = baseline.get(package_path)
+            && previous_count > 0
+        {
+            let growth_percent = (count as f64 - previous_count as f64) * 100.0 / previous_count as f64;
+            if growth_percent > max_growth_percent {
+                alerts.push(KeyCountAlert::GrowthExceeded { package_path: package_path.clone(), previous_count, count, growth_percent });
+            }
+        }
+    }
+
+    alerts
+}
+
+pub fn print_key_counts_report(counts: &BTreeMap<String, usize>, baseline: &BTreeMap<String, usize>, alerts: &[KeyCountAlert]) {
+    println!("Key counts report :");
+    for (package_path, count) in counts {
+        match baseline.get(package_path) {
+            Some(previous_count) => println!("{} : {} keys (baseline {})", package_path, count, previous_count),
+            None => println!("{} : {} keys", package_path, count),
+        }
+    }
+
+    println!("\n");
+    println!("Key count alerts : {}", alerts.len());
+    for alert in alerts {
+        println!("{}", alert);
+    }
+}
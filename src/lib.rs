@@ -0,0 +1,95 @@
+//! Analysis of the translation files of a monorepo, used by the `translations-analyzer` CLI
+//! and by embedders (GUI, server, LSP) which can follow the analyses through [`progress`] events
+
+// The `json!` literal of the settings schema goes beyond the default limit
+#![recursion_limit = "256"]
+
+pub mod search_recursive_regex;
+pub mod load_translations;
+pub mod map_translations_by_key;
+pub mod entities;
+pub mod map_translations_by_project;
+pub mod analyse_project_duplication;
+pub mod settings;
+pub mod get_translation_for_project;
+pub mod analyse_embedded_blobs;
+pub mod analyse_near_duplication;
+pub mod translation_file;
+pub mod safe_write;
+pub mod split_translation_file;
+pub mod normalize_value;
+pub mod merge_translation_files;
+pub mod locales;
+pub mod analyse_placeholders;
+pub mod icu_message_format;
+pub mod analyse_icu_syntax;
+pub mod move_translation_keys;
+pub mod scan_source_files;
+pub mod analyse_deprecated_keys;
+pub mod cluster_id;
+pub mod analyse_hardcoded_strings;
+pub mod analyse_cross_locale;
+pub mod export_badge;
+pub mod analyse_duplicate_keys;
+pub mod render_diff;
+pub mod collation;
+pub mod analyse_key_casing;
+pub mod suggest_common_translations;
+pub mod lint_keys;
+pub mod export_json;
+pub mod pipeline;
+pub mod analyse_length_anomalies;
+pub mod analyse_markup_tags;
+pub mod run_summary;
+pub mod analyse_untranslated;
+pub mod analyse_unreferenced_files;
+pub mod cluster_similar_values;
+pub mod allowed_duplications;
+pub mod analyse_key_prefixes;
+pub mod analyse_dead_common_translations;
+pub mod cluster_details;
+pub mod analyse_formatted_values;
+pub mod analyse_inclusive_writing;
+pub mod analyse_plural_forms;
+pub mod analyse_key_collisions;
+pub mod analyse_quote_styles;
+pub mod analyse_encoding;
+pub mod progress;
+pub mod health_score;
+pub mod canonical_catalog;
+pub mod score_proposed_translation;
+pub mod rename_translation_keys;
+pub mod annotations;
+pub mod analyse_glossary;
+pub mod key_count_limits;
+pub mod po_file;
+pub mod package_dependencies;
+pub mod glob_pattern;
+pub mod test_fixtures;
+pub mod value_provenance;
+pub mod scaffold_package;
+pub mod strings_file;
+pub mod android_xml_file;
+pub mod analyse_locale_ambiguity;
+pub mod run_stamp;
+pub mod jsonc;
+pub mod trace;
+pub mod js_module_file;
+pub mod csv_file;
+pub mod extendable_file;
+pub mod i18next_keys;
+pub mod cluster_graph;
+pub mod translation_loader;
+pub mod auto_triage;
+pub mod analyzer_index;
+pub mod toml_file;
+pub mod self_test;
+pub mod yaml_file;
+pub mod settings_overrides;
+pub mod validate_settings;
+pub mod settings_schema;
+pub mod ignore_file;
+pub mod duplication_baseline;
+pub mod duplication_thresholds;
+pub mod init_settings;
+pub mod list_packages;
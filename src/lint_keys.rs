@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// Structure rules enforced on translation keys by `lint-keys`
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct KeyRules {
+    /// Separator between the segments of a key
+    pub segment_separator: String,
+    /// Maximum number of segments of a key
+    pub max_depth: Option<usize>,
+    /// Regex character class a segment character must match, as `[a-zA-Z0-9_]`
+    pub allowed_segment_characters: Option<String>,
+    pub forbid_leading_digits: bool,
+    /// Naming convention preset the whole key must follow
+    pub naming_preset: Option<KeyNamingPreset>,
+    /// Regex the whole key must match, checked in addition to the preset
+    pub naming_pattern: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum KeyNamingPreset {
+    #[serde(rename = "lower.dot.case")]
+    LowerDot,
+    #[serde(rename = "snake_case")]
+    Snake,
+    #[serde(rename = "camelCase")]
+    Camel,
+    #[serde(rename = "kebab-case")]
+    Kebab,
+    /// Dot separated segments written in camelCase, as `form.saveButton`
+    #[serde(rename = "dot.camelCase")]
+    DotCamel,
+}
+
+impl KeyNamingPreset {
+    fn name(&self) -> &'static str {
+        match self {
+            KeyNamingPreset::LowerDot => "lower.dot.case",
+            KeyNamingPreset::Snake => "snake_case",
+            KeyNamingPreset::Camel => "camelCase",
+            KeyNamingPreset::Kebab => "kebab-case",
+            KeyNamingPreset::DotCamel => "dot.camelCase",
+        }
+    }
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            KeyNamingPreset::LowerDot => r"^[a-z0-9]+(\.[a-z0-9]+)*$",
+            KeyNamingPreset::Snake => r"^[a-z0-9]+(_[a-z0-9]+)*$",
+            KeyNamingPreset::Camel => r"^[a-z][a-zA-Z0-9]*$",
+            KeyNamingPreset::Kebab => r"^[a-z0-9]+(-[a-z0-9]+)*$",
+            KeyNamingPreset::DotCamel => r"^[a-z][a-zA-Z0-9]*(\.[a-z][a-zA-Z0-9]*)*$",
+        }
+    }
+}
+
+impl Default for KeyRules {
+    fn default() -> Self {
+        Self {
+            segment_separator: ".".to_string(),
+            max_depth: None,
+            allowed_segment_characters: None,
+            forbid_leading_digits: false,
+            naming_preset: None,
+            naming_pattern: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum KeyRuleViolation {
+    MaxDepth(usize, usize),
+    InvalidCharacters(String),
+    LeadingDigit(String),
+    NamingConvention(String),
+}
+
+impl fmt::Display for KeyRuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyRuleViolation::MaxDepth(depth, max_depth) => write!(f, "{} segments, {} allowed", depth, max_depth),
+            KeyRuleViolation::InvalidCharacters(segment) => write!(f, "invalid characters in segment `{}`", segment),
+            KeyRuleViolation::LeadingDigit(segment) => write!(f, "segment `{}` starts with a digit", segment),
+            KeyRuleViolation::NamingConvention(convention) => write!(f, "does not follow {}", convention),
+        }
+    }
+}
+
+pub struct KeyLintReport<'a> {
+    pub translation: &'a Translation,
+    pub violations: Vec<KeyRuleViolation>,
+    /// Fixed key, only given when no other key of the file already uses it
+    pub suggested_key: Option<String>,
+}
+
+/// Checks every key against the structure rules and suggests a safe rename for the violations
+pub fn lint_keys<'a>(translations: &[&'a Translation], rules: &KeyRules) -> Result<Vec<KeyLintReport<'a>>, regex::Error> {
+    let allowed_character = rules
+        .allowed_segment_characters
+        .as_deref()
+        .map(Regex::new)
+        .transpose()?;
+
+    let mut naming_conventions: Vec<(String, Regex)> = Vec::new();
+    if let Some(preset) = rules.naming_preset {
+        naming_conventions.push((preset.name().to_string(), Regex::new(preset.pattern())?));
+    }
+    if let Some(pattern) = &rules.naming_pattern {
+        naming_conventions.push((format!("`{}`", pattern), Regex::new(pattern)?));
+    }
+
+    let existing_keys: HashSet<(&PathBuf, &str)> =
+        translations.iter().map(|translation| (&translation.path, translation.key.as_str())).collect();
+
+    let mut reports: Vec<KeyLintReport<'a>> = translations
+        .iter()
+        .filter_map(|translation| {
+            let mut violations = check_key(&translation.key, rules, allowed_character.as_ref());
+            violations.extend(
+                naming_conventions
+                    .iter()
+                    .filter(|(_, pattern)| !pattern.is_match(&translation.key))
+                    .map(|(convention, _)| KeyRuleViolation::NamingConvention(convention.clone())),
+            );
+            if violations.is_empty() {
+                return None;
+            }
+
+            let fixed_key = fix_key(&translation.key, rules, allowed_character.as_ref());
+            let suggested_key = (!existing_keys.contains(&(&translation.path, fixed_key.as_str()))).then_some(fixed_key);
+
+            Some(KeyLintReport { translation, violations, suggested_key })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.translation.path.cmp(&b.translation.path).then_with(|| a.translation.key.cmp(&b.translation.key)));
+    Ok(reports)
+}
+
+fn check_key(key: &str, rules: &KeyRules, allowed_character: Option<&Regex>) -> Vec<KeyRuleViolation> {
+    let segments: Vec<&str> = key.split(rules.segment_separator.as_str()).collect();
+    let mut violations = Vec::new();
+
+    if let Some(max_depth) = rules.max_depth.filter(|max_depth| segments.len() > *max_depth) {
+        violations.push(KeyRuleViolation::MaxDepth(segments.len(), max_depth));
+    }
+
+    for segment in &segments {
+        if segment.chars().any(|c| !is_allowed_character(c, allowed_character)) {
+            violations.push(KeyRuleViolation::InvalidCharacters(segment.to_string()));
+        }
+        if rules.forbid_leading_digits && segment.starts_with(|c: char| c.is_ascii_digit()) {
+            violations.push(KeyRuleViolation::LeadingDigit(segment.to_string()));
+        }
+    }
+
+    violations
+}
+
+fn is_allowed_character(c: char, allowed_character: Option<&Regex>) -> bool {
+    allowed_character.is_none_or(|allowed_character| allowed_character.is_match(c.encode_utf8(&mut [0; 4])))
+}
+
+/// Replaces invalid characters by `_`, prefixes leading digits with `_` and joins the segments over the maximum depth
+fn fix_key(key: &str, rules: &KeyRules, allowed_character: Option<&Regex>) -> String {
+    let mut segments: Vec<String> = key
+        .split(rules.segment_separator.as_str())
+        .map(|segment| {
+            let mut fixed: String = segment
+                .chars()
+                .map(|c| if is_allowed_character(c, allowed_character) { c } else { '_' })
+                .collect();
+            if rules.forbid_leading_digits && fixed.starts_with(|c: char| c.is_ascii_digit()) {
+                fixed.insert(0, '_');
+            }
+            fixed
+        })
+        .collect();
+
+    if let Some(max_depth) = rules.max_depth.filter(|max_depth| *max_depth > 0 && segments.len() > *max_depth) {
+        let tail = segments.split_off(max_depth - 1).join("_");
+        segments.push(tail);
+    }
+
+    segments.join(&rules.segment_separator)
+}
+
+pub fn print_lint_keys_report(monorepo_path: &Path, reports: &[KeyLintReport], package_path_pattern: &PackagePathPattern) {
+    println!("Keys lint report :");
+    println!("Keys breaking rules : {}", reports.len());
+    println!("Keys with a safe rename : {}", reports.iter().filter(|report| report.suggested_key.is_some()).count());
+
+    let mut reports_by_package: BTreeMap<String, Vec<&KeyLintReport>> = BTreeMap::new();
+    for report in reports {
+        reports_by_package.entry(package_path_pattern.package_path(&report.translation.path.to_string_lossy())).or_default().push(report);
+    }
+
+    for (package_path, package_reports) in reports_by_package {
+        println!("\n");
+        println!(" ========= {} : {} keys ==========", package_path, package_reports.len());
+        print_package_lint_keys_report(monorepo_path, &package_reports);
+    }
+}
+
+fn print_package_lint_keys_report(monorepo_path: &Path, reports: &[&KeyLintReport]) {
+    for report in reports {
+        let violations: Vec<String> = report.violations.iter().map(|violation| violation.to_string()).collect();
+        println!(
+            "{} - {} : {}{}",
+            report.translation.path.strip_prefix(monorepo_path).unwrap_or(&report.translation.path).to_string_lossy(),
+            report.translation.key,
+            violations.join(", "),
+            report.suggested_key.as_ref().map(|suggested_key| format!(" -> rename to {}", suggested_key)).unwrap_or_default()
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use crate::entities::{PackageType, Translation};
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// A package holding translations, its path being the one the `--package-path` options accept
+pub struct PackageListing {
+    pub package_path: String,
+    pub package_type: PackageType,
+    pub files: usize,
+    pub keys: usize,
+}
+
+pub struct PackagesListing {
+    /// Packages sorted by path
+    pub packages: Vec<PackageListing>,
+    /// Translation files the package path regex does not match
+    pub files_outside_packages: usize,
+}
+
+pub fn list_packages(translations: &[Translation], package_path_pattern: &PackagePathPattern) -> PackagesListing {
+    let mut packages: BTreeMap<String, (PackageType, BTreeSet<&Path>, usize)> = BTreeMap::new();
+    let mut files_outside_packages: BTreeSet<&Path> = BTreeSet::new();
+
+    for translation in translations {
+        match package_path_pattern.project_path_and_type(&translation.path.to_string_lossy()) {
+            Some((package_type, package_path)) => {
+                let (_, files, keys) = packages.entry(package_path).or_insert_with(|| (package_type, BTreeSet::new(), 0));
+                files.insert(&translation.path);
+                *keys += 1;
+            }
+            None => {
+                files_outside_packages.insert(&translation.path);
+            }
+        }
+    }
+
+    PackagesListing {
+        packages: packages
+            .into_iter()
+            .map(|(package_path, (package_type, files, keys))| PackageListing { package_path, package_type, files: files.len(), keys })
+            .collect(),
+        files_outside_packages: files_outside_packages.len(),
+    }
+}
+
+pub fn print_packages_listing(listing: &PackagesListing) {
+    println!("Packages : {}", listing.packages.len());
+
+    for package in &listing.packages {
+        println!("{} - {} - {} files - {} keys", package.package_path, package.package_type, package.files, package.keys);
+    }
+
+    if listing.files_outside_packages > 0 {
+        println!("Warning : {} translation files outside of any package, see the package_path_regex setting", listing.files_outside_packages);
+    }
+}
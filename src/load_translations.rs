@@ -1,14 +1,26 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use std::fs;
+use std::fmt;
+use serde_json::json;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use rayon::prelude::*;
-use serde_json::{Value};
-pub(crate) use crate::entities::Translation;
+use serde::{Deserialize, Serialize};
+use regex::Regex;
+pub use crate::entities::Translation;
+use crate::entities::TranslationValue;
+use crate::locales::{capture_glob_locale, capture_locale, LocaleFilePatterns};
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::progress::{NoProgress, ProgressEvent, ProgressListener};
+use crate::trace::trace_span;
+use crate::translation_file::TranslationEntries;
+use crate::translation_loader::TranslationLoaders;
 
 #[derive(Error, Debug)]
 pub enum LoadTranslationsFilesError {
-    #[error("Unable to read or parse JSON format: {0}")]
+    #[error("Unable to read format: {0}")]
     UnableReadFormat(String),
 
     #[error("Failed to read file: {0}")]
@@ -16,77 +28,287 @@ pub enum LoadTranslationsFilesError {
 
     #[error("JSON parsing error: {0}")]
     JsonError(String, #[source] serde_json::error::Error),
+
+    #[error("{0} has comments, rewriting it as JSON would drop them")]
+    CommentedJson(String),
+
+    #[error("PO parsing error in {0}, {1}")]
+    PoError(String, String),
+
+    #[error("Strings parsing error in {0}, {1}")]
+    StringsError(String, String),
+
+    #[error("Android XML parsing error in {0}, {1}")]
+    AndroidXmlError(String, String),
+
+    #[error("JavaScript module parsing error in {0}, {1}")]
+    JsModuleError(String, String),
+
+    #[error("CSV parsing error in {0}, {1}")]
+    CsvError(String, String),
+
+    #[error("TOML parsing error in {0}, {1}")]
+    TomlError(String, String),
+
+    /// Raised by the loaders registered downstream
+    #[error("Parsing error in {0}, {1}")]
+    ParseError(String, String),
+}
+
+/// A key defined several times in the same file, only the last value being kept by JSON parsers
+#[derive(Clone, Debug)]
+pub struct DuplicateKey {
+    pub path: PathBuf,
+    pub key: String,
+    pub values: Vec<String>,
+}
+
+/// How translation files are turned into translations
+#[derive(Clone)]
+pub struct LoadOptions {
+    /// Joins the keys of nested objects, as `form.save` for `{"form": {"save": "Enregistrer"}}`
+    pub nested_key_separator: String,
+    /// Only locale loaded from the files holding several locales (CSV exports), every locale being loaded when unset
+    pub multi_locale_filter: Option<String>,
+    /// Parsers of the file formats, the built-in ones by default
+    pub loaders: TranslationLoaders,
+    /// Locales of the files whose name matches their pattern, used before `locale_regexes`
+    pub locale_file_patterns: LocaleFilePatterns,
+    /// Globs capturing the locale of a file at their `{locale}` placeholder, used before `locale_regexes`
+    pub locale_globs: Vec<Regex>,
+    /// Regexes capturing the locale of a file from its name, the first one matching being used
+    pub locale_regexes: Vec<Regex>,
+    /// Locale of the files whose locale nothing else tells, as the reference locale for the files of the
+    /// reference glob
+    pub default_locale: Option<String>,
+    /// Name or number of the capture group holding the locale, as in `capture_locale`
+    pub locale_capture_group: Option<String>,
+    /// Whether a file which cannot be loaded stops the loading
+    pub error_policy: LoadErrorPolicy,
+    /// Files larger than this many bytes are skipped without being read, no limit when unset
+    pub max_file_size: Option<u64>,
+    /// Finds the package of the files, for the traces
+    pub package_path_pattern: PackagePathPattern,
+}
+
+/// What happens when a translation file cannot be read or parsed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadErrorPolicy {
+    /// The loading fails with the error of the first file
+    #[default]
+    FailFast,
+    /// The other files are loaded, the errors being listed in `LoadedTranslations::errors`
+    Continue,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            nested_key_separator: ".".to_string(),
+            multi_locale_filter: None,
+            loaders: TranslationLoaders::default(),
+            locale_file_patterns: LocaleFilePatterns::default(),
+            locale_globs: Vec::new(),
+            locale_regexes: Vec::new(),
+            default_locale: None,
+            locale_capture_group: None,
+            error_policy: LoadErrorPolicy::default(),
+            max_file_size: None,
+            package_path_pattern: PackagePathPattern::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LoadedTranslations {
+    pub translations: Vec<Translation>,
+    pub duplicate_keys: Vec<DuplicateKey>,
+    /// Files which could not be loaded, always empty with `LoadErrorPolicy::FailFast`
+    pub errors: Vec<LoadTranslationsFilesError>,
+    /// Files matched by mistake, as generated bundles, left out without being parsed
+    pub skipped_files: Vec<SkippedFile>,
+}
+
+/// A matched file which does not look like a translation file
+#[derive(Debug)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug)]
+pub enum SkipReason {
+    /// Larger than `LoadOptions::max_file_size`
+    TooLarge { size: u64, limit: u64 },
+    /// Holds NUL bytes, as images or compiled files
+    Binary,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::TooLarge { size, limit } => write!(f, "{} bytes, more than the {} bytes limit", size, limit),
+            SkipReason::Binary => f.write_str("binary content"),
+        }
+    }
 }
 
-/// Recursively searches for regex matches in all files within a path
-/// Returns a vector of tuples: (file_path, line_number, matched_text)
+/// Bytes looked at for NUL bytes, as git does to tell binary files
+const BINARY_SNIFF_LENGTH: usize = 8000;
+
+/// Whether the content is binary, UTF-16 files starting with a byte order mark being text
+fn is_binary_content(content: &[u8]) -> bool {
+    if content.starts_with(&[0xFF, 0xFE]) || content.starts_with(&[0xFE, 0xFF]) {
+        return false;
+    }
+    content.iter().take(BINARY_SNIFF_LENGTH).any(|byte| *byte == 0)
+}
+
+/// Loads the translations of every file in parallel
+/// Keys defined twice in a file are reported in `duplicate_keys`, the last value being kept
 pub fn load_translations(
-    translation_files_path: Vec<Box<PathBuf>>,
-) -> Result<Vec<Translation>, LoadTranslationsFilesError> {
-    let results = Arc::new(parking_lot::Mutex::new(Vec::new()));
-    
-    load_translations_parallel(translation_files_path, results.clone())?;
-
-    let final_results = results.lock().clone();
-    
+    translation_files_path: Vec<PathBuf>,
+) -> Result<LoadedTranslations, LoadTranslationsFilesError> {
+    load_translations_with_progress(translation_files_path, &LoadOptions::default(), &NoProgress)
+}
+
+/// Loads the translations like `load_translations`, notifying the listener of the discovered and parsed files
+pub fn load_translations_with_progress(
+    translation_files_path: Vec<PathBuf>,
+    options: &LoadOptions,
+    progress: &dyn ProgressListener,
+) -> Result<LoadedTranslations, LoadTranslationsFilesError> {
+    let results = Arc::new(parking_lot::Mutex::new(LoadedTranslations::default()));
+
+    progress.on_progress(ProgressEvent::FilesDiscovered { count: translation_files_path.len() });
+    load_translations_parallel(translation_files_path, results.clone(), options, progress)?;
+
+    let final_results = std::mem::take(&mut *results.lock());
+
     Ok(final_results)
 }
 
 fn load_translations_parallel(
-    translation_files_path: Vec<Box<PathBuf>>,
-    results: Arc<parking_lot::Mutex<Vec<Translation>>>,
+    translation_files_path: Vec<PathBuf>,
+    results: Arc<parking_lot::Mutex<LoadedTranslations>>,
+    options: &LoadOptions,
+    progress: &dyn ProgressListener,
 ) -> Result<(), LoadTranslationsFilesError> {
-    translation_files_path.par_iter().for_each(|entry_path| {
-        load_translation_file(&entry_path, results.clone()).expect(&format!("Unable to process: {}", entry_path.to_string_lossy()));
-    });
+    let total = translation_files_path.len();
+    let parsed = AtomicUsize::new(0);
 
-    Ok(())
+    translation_files_path.par_iter().try_for_each(|entry_path| {
+        let loaded = load_translation_file(entry_path, results.clone(), options);
+        let parsed = parsed.fetch_add(1, Ordering::Relaxed) + 1;
+        progress.on_progress(ProgressEvent::FileParsed { path: entry_path.clone(), parsed, total });
+
+        match (loaded, options.error_policy) {
+            (Err(error), LoadErrorPolicy::Continue) => {
+                results.lock().errors.push(error);
+                Ok(())
+            }
+            (loaded, _) => loaded,
+        }
+    })
 }
 
 fn load_translation_file(
     path: &Path,
-    results: Arc<parking_lot::Mutex<Vec<Translation>>>,
+    results: Arc<parking_lot::Mutex<LoadedTranslations>>,
+    options: &LoadOptions,
 ) -> Result<(), LoadTranslationsFilesError> {
-    // Verify the file has .json extension
-    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+    let _span = trace_span("parse", path.to_string_lossy(), json!({ "package": options.package_path_pattern.package_path(&path.to_string_lossy()) }));
+
+    // Verify a loader handles the file
+    let Some(loader) = options.loaders.find(path) else {
         return Err(LoadTranslationsFilesError::UnableReadFormat(
-            format!("File is not a JSON file: {}", path.display()),
+            format!("No loader for {}, handled extensions are {}", path.display(), options.loaders.extensions().join(", ")),
         ));
+    };
+
+    let read_error = |e| LoadTranslationsFilesError::FileReadError(format!("Cannot read file: {}", path.display()), e);
+    let skip = |reason| results.lock().skipped_files.push(SkippedFile { path: path.to_path_buf(), reason });
+
+    // Checked before reading, so a huge generated file is never loaded in memory
+    if let Some(limit) = options.max_file_size {
+        let size = fs::metadata(path).map_err(read_error)?.len();
+        if size > limit {
+            skip(SkipReason::TooLarge { size, limit });
+            return Ok(());
+        }
     }
 
-    // Read the file content
-    let content = fs::read_to_string(path)
-        .map_err(|e| LoadTranslationsFilesError::FileReadError(
-            format!("Cannot read file: {}", path.display()),
-            e,
-        ))?;
-
-    // Parse JSON into Map<String, String>
-    let json_value: Value = serde_json::from_str(&content)
-        .map_err(|e| LoadTranslationsFilesError::JsonError(
-            format!("Invalid JSON format in {}", path.display()),
-            e,
-        ))?;
-
-    // Extract the object and convert to Vec<Translation>
-    if let Value::Object(map) = json_value {
-        let translations: Vec<Translation> = map
-            .into_iter()
-            .map(|(key, value)| Translation {
-                path: path.to_path_buf(),
-                translations: value.to_string(),
-                key,
-            })
-            .collect();
-
-        // Extend results with the new translations
-        let mut results_lock = results.lock();
-        results_lock.extend(translations);
-    } else {
-        return Err(LoadTranslationsFilesError::UnableReadFormat(
-            format!("Root element is not a JSON object: {}", path.display()),
-        ));
+    let content = fs::read(path).map_err(read_error)?;
+    if is_binary_content(&content) {
+        skip(SkipReason::Binary);
+        return Ok(());
+    }
+
+    // Multi-locale files give the entries of each of their locales, the file name telling it for the others
+    let file_locale = options.locale_file_patterns.locale(path)
+        .or_else(|| options.locale_globs.iter().find_map(|locale_glob| capture_glob_locale(path, locale_glob)))
+        .or_else(|| {
+            options
+                .locale_regexes
+                .iter()
+                .find_map(|locale_regex| capture_locale(path, locale_regex, options.locale_capture_group.as_deref()))
+        })
+        .or_else(|| options.default_locale.clone());
+    for mut loaded in loader.load(path, &content)? {
+        loaded.locale = loaded.locale.or_else(|| file_locale.clone());
+        let filtered = loaded.locale.as_ref().is_some_and(|locale| {
+            options.multi_locale_filter.as_ref().is_some_and(|filter| filter != locale)
+        });
+        if !filtered {
+            store_translation_entries(path, loaded.locale, loaded.entries, &results, options);
+        }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Adds the entries of a file, for one locale, to the results, reporting the keys defined twice
+fn store_translation_entries(
+    path: &Path,
+    locale: Option<String>,
+    entries: TranslationEntries,
+    results: &parking_lot::Mutex<LoadedTranslations>,
+    options: &LoadOptions,
+) {
+    let mut translations: Vec<Translation> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut duplicate_keys: Vec<DuplicateKey> = Vec::new();
+
+    for (key, value) in entries.flattened(&options.nested_key_separator).0 {
+        let value = TranslationValue::from(value);
+        match positions.get(&key) {
+            Some(&position) => {
+                let existing = &mut translations[position];
+                match duplicate_keys.iter_mut().find(|duplicate| duplicate.key == key) {
+                    Some(duplicate) => duplicate.values.push(value.to_string()),
+                    None => duplicate_keys.push(DuplicateKey {
+                        path: path.to_path_buf(),
+                        key: key.clone(),
+                        values: vec![existing.translations.to_string(), value.to_string()],
+                    }),
+                }
+                existing.translations = value;
+            }
+            None => {
+                positions.insert(key.clone(), translations.len());
+                translations.push(Translation {
+                    path: path.to_path_buf(),
+                    translations: value,
+                    key,
+                    locale: locale.clone(),
+                });
+            }
+        }
+    }
+
+    // Extend results with the new translations
+    let mut results_lock = results.lock();
+    results_lock.translations.extend(translations);
+    results_lock.duplicate_keys.extend(duplicate_keys);
+}
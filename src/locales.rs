@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use crate::entities::Translation;
+use crate::glob_pattern::{compile_locale_glob, LOCALE_PLACEHOLDER};
+use crate::settings::Settings;
+
+/// Values of a same key in every locale file of a folder, indexed by locale
+pub type LocalizedValues<'a> = BTreeMap<String, &'a Translation>;
+
+const LOCALE_GROUP_NAME: &str = "locale";
+
+/// File name patterns of the locales, the locale of a file being the one whose pattern matches its name
+#[derive(Clone, Default)]
+pub struct LocaleFilePatterns(Vec<(String, Regex)>);
+
+impl LocaleFilePatterns {
+    pub fn compile(patterns: &BTreeMap<String, String>) -> Result<Self, regex::Error> {
+        patterns
+            .iter()
+            .map(|(locale, pattern)| Ok((locale.clone(), Regex::new(pattern)?)))
+            .collect::<Result<Vec<_>, regex::Error>>()
+            .map(Self)
+    }
+
+    pub fn locale(&self, path: &Path) -> Option<String> {
+        let file_name = path.file_name()?.to_string_lossy();
+        self.0.iter().find(|(_, regex)| regex.is_match(&file_name)).map(|(locale, _)| locale.clone())
+    }
+}
+
+/// Finds the locale of a translation file: by the `locales` patterns when set, by the `{locale}` of the locales glob
+/// or a capture of the locales regex otherwise
+#[derive(Clone)]
+pub struct LocaleMatcher {
+    patterns: LocaleFilePatterns,
+    locale_glob: Option<Regex>,
+    locale_regex: Option<Regex>,
+    /// Capture group of the locale in the file name regexes, as `locale_capture_group`
+    locale_capture_group: Option<String>,
+}
+
+impl LocaleMatcher {
+    pub fn compile(settings: &Settings) -> Result<Self, regex::Error> {
+        let patterns = settings.locales.patterns();
+        let locale_glob = settings.locales_file_glob.as_deref().and_then(compile_locale_glob_of).transpose()?;
+        let locale_regex = if patterns.is_empty() { Some(Regex::new(&settings.locales_file_regex)?) } else { None };
+
+        Ok(Self {
+            patterns: LocaleFilePatterns::compile(patterns)?,
+            locale_glob,
+            locale_regex,
+            locale_capture_group: settings.locale_capture_group.clone(),
+        })
+    }
+
+    /// Matcher capturing the locale with the regex alone, as `extract_locale`
+    pub fn from_regex(locale_regex: Regex) -> Self {
+        Self { patterns: LocaleFilePatterns::default(), locale_glob: None, locale_regex: Some(locale_regex), locale_capture_group: None }
+    }
+
+    pub fn locale(&self, path: &Path) -> Option<String> {
+        self.patterns.locale(path)
+            .or_else(|| self.locale_glob.as_ref().and_then(|locale_glob| capture_glob_locale(path, locale_glob)))
+            .or_else(|| self.locale_regex.as_ref().and_then(|locale_regex| self.capture(path, locale_regex)))
+    }
+
+    /// Folder of a translation file, the part of its segment captured by the `{locale}` of the locales glob being
+    /// replaced by the placeholder, so the locales of `{locale}/messages.json` or `values-{locale}/` share a folder
+    pub fn locale_folder(&self, path: &Path) -> PathBuf {
+        let folder = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let segments: Vec<String> = path.components().map(|component| component.as_os_str().to_string_lossy().to_string()).collect();
+        let joined = segments.join("/");
+        let Some(locale) = self.locale_glob.as_ref().and_then(|locale_glob| locale_glob.captures(&joined)?.name(LOCALE_GROUP_NAME)) else {
+            return folder;
+        };
+
+        let mut locale_folder = PathBuf::new();
+        let mut start = 0;
+        // The file name is left out, the folder being its parent
+        for segment in segments.iter().take(segments.len().saturating_sub(1)) {
+            let end = start + segment.len();
+            if locale.start() >= start && locale.end() <= end {
+                locale_folder.push(format!("{}{}{}", &segment[..locale.start() - start], LOCALE_PLACEHOLDER, &segment[locale.end() - start..]));
+            } else {
+                locale_folder.push(segment);
+            }
+            start = end + 1;
+        }
+        locale_folder
+    }
+
+    /// Locale captured from a file name by another regex, as the regex of a settings override, with the same
+    /// capture group as the locales regex
+    pub fn capture(&self, path: &Path, locale_regex: &Regex) -> Option<String> {
+        capture_locale(path, locale_regex, self.locale_capture_group.as_deref())
+    }
+}
+
+/// Regex capturing the locale of the files selected by a glob, `None` when the glob has no `{locale}` placeholder
+pub fn compile_locale_glob_of(glob: &str) -> Option<Result<Regex, regex::Error>> {
+    glob.contains(LOCALE_PLACEHOLDER).then(|| compile_locale_glob(glob))
+}
+
+/// Extracts the locale from the path of a translation file at the `{locale}` placeholder of a glob compiled by
+/// `compile_locale_glob`
+pub fn capture_glob_locale(path: &Path, locale_glob: &Regex) -> Option<String> {
+    let segments: Vec<_> = path.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+    let path = segments.join("/");
+    let captures = locale_glob.captures(&path)?;
+    Some(captures.name(LOCALE_GROUP_NAME)?.as_str().to_string())
+}
+
+/// Regex matching the file names of every locale pattern, to walk the files of all the locales at once
+pub fn any_locale_file_regex(patterns: &BTreeMap<String, String>) -> String {
+    patterns.values().map(|pattern| format!("(?:{})", pattern)).collect::<Vec<_>>().join("|")
+}
+
+/// Extracts the locale from a translation file name using the group named `locale` of the regex, its first group otherwise
+pub fn extract_locale(path: &Path, locale_regex: &Regex) -> Option<String> {
+    capture_locale(path, locale_regex, None)
+}
+
+/// Extracts the locale from a translation file name using the capture group of the given name or number,
+/// the default group of `extract_locale` when unset
+pub fn capture_locale(path: &Path, locale_regex: &Regex, group: Option<&str>) -> Option<String> {
+    let file_name = path.file_name()?.to_string_lossy();
+    let captures = locale_regex.captures(&file_name)?;
+    let locale = match group {
+        Some(group) => match group.parse::<usize>() {
+            Ok(index) => captures.get(index),
+            Err(_) => captures.name(group),
+        },
+        None => captures.name(LOCALE_GROUP_NAME).or_else(|| captures.get(1)),
+    };
+    Some(locale?.as_str().to_string())
+}
+
+/// Locale of a translation, as found when loading it, by its file name for translations built otherwise
+pub fn translation_locale(translation: &Translation, locale_matcher: &LocaleMatcher) -> Option<String> {
+    translation.locale.clone().or_else(|| locale_matcher.locale(&translation.path))
+}
+
+/// Groups translations sharing the same folder and key, so each group holds one key in every locale
+/// Folders named after their locale are grouped together, as `{locale}/messages.json`
+pub fn group_by_key_across_locales<'a>(
+    translations: &[&'a Translation],
+    locale_matcher: &LocaleMatcher,
+) -> BTreeMap<(PathBuf, String), LocalizedValues<'a>> {
+    let mut groups: BTreeMap<(PathBuf, String), LocalizedValues<'a>> = BTreeMap::new();
+
+    for translation in translations {
+        let Some(locale) = translation_locale(translation, locale_matcher) else {
+            continue;
+        };
+        let folder = locale_matcher.locale_folder(&translation.path);
+
+        groups.entry((folder, translation.key.clone())).or_default().insert(locale, translation);
+    }
+
+    groups
+}
+
+/// Splits translations by the locale of their file, translations without locale being dropped
+pub fn split_by_locale(translations: Vec<Translation>, locale_matcher: &LocaleMatcher) -> BTreeMap<String, Vec<Translation>> {
+    let mut translations_by_locale: BTreeMap<String, Vec<Translation>> = BTreeMap::new();
+
+    for translation in translations {
+        if let Some(locale) = translation_locale(&translation, locale_matcher) {
+            translations_by_locale.entry(locale).or_default().push(translation);
+        }
+    }
+
+    translations_by_locale
+}
@@ -1,36 +1,113 @@
-mod search_recursive_regex;
-mod load_translations;
-mod map_translations_by_key;
-mod entities;
-mod map_translations_by_project;
-mod analyse_project_duplication;
-mod settings;
-mod get_translation_for_project;
-
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
+use std::fs;
+use std::io::{self, Write};
 use std::error::Error;
 use std::path::{Path, PathBuf};
-use clap::{Parser, Subcommand};
+use std::process;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use regex::Regex;
 use thiserror::Error;
-use crate::analyse_project_duplication::{analyse_duplication, print_global_duplication_report};
-use crate::get_translation_for_project::get_translations_for_project;
-use crate::load_translations::load_translations;
-use crate::map_translations_by_key::map_translations_by_translation;
-use crate::map_translations_by_project::{get_package_path, map_translations_by_project};
-use crate::search_recursive_regex::search_recursive_regex;
-use crate::settings::Settings;
+use translations_analyzer::analyzer_index::AnalyzerIndex;
+use translations_analyzer::annotations::{get_annotations, Annotations};
+use translations_analyzer::allowed_duplications::{get_allowlist, AllowedDuplications};
+use translations_analyzer::analyse_cross_locale::{analyse_identical_across_locales, print_identical_across_locales_report};
+use translations_analyzer::analyse_dead_common_translations::{analyse_dead_common_translations, print_dead_common_translations_report};
+use translations_analyzer::analyse_deprecated_keys::{analyse_deprecated_keys, get_deprecations, print_deprecated_keys_report, IsoDate};
+use translations_analyzer::analyse_duplicate_keys::print_duplicate_keys_report;
+use translations_analyzer::analyse_embedded_blobs::{analyse_embedded_blobs, print_embedded_blobs_report};
+use translations_analyzer::analyse_encoding::{analyse_encoding, print_encoding_report};
+use translations_analyzer::analyse_formatted_values::{analyse_formatted_values, print_formatted_values_report};
+use translations_analyzer::analyse_glossary::{analyse_glossary, get_glossary, print_glossary_report, GlossaryError};
+use translations_analyzer::analyse_hardcoded_strings::{analyse_hardcoded_strings, render_hardcoded_strings_report, JSX_FILE_EXTENSIONS};
+use translations_analyzer::analyse_icu_syntax::{analyse_icu_syntax, print_icu_syntax_report};
+use translations_analyzer::analyse_inclusive_writing::{analyse_inclusive_writing, print_inclusive_writing_report};
+use translations_analyzer::analyse_key_casing::{analyse_key_casing, print_key_casing_report};
+use translations_analyzer::analyse_key_collisions::{analyse_key_collisions, print_key_collisions_report};
+use translations_analyzer::analyse_key_prefixes::{analyse_key_prefixes, print_key_prefixes_report};
+use translations_analyzer::analyse_length_anomalies::{analyse_length_anomalies, print_length_anomalies_report};
+use translations_analyzer::analyse_locale_ambiguity::analyse_locale_ambiguities;
+use translations_analyzer::analyse_markup_tags::{analyse_markup_tags, print_markup_tags_report};
+use translations_analyzer::analyse_near_duplication::{analyse_near_duplication, render_near_duplication_report};
+use translations_analyzer::analyse_placeholders::{analyse_placeholders, print_placeholders_report};
+use translations_analyzer::analyse_plural_forms::{analyse_plural_forms, print_plural_forms_report};
+use translations_analyzer::auto_triage::{apply_triage_rules, apply_triage_rules_to, triage_order, TriageRules};
+use translations_analyzer::analyse_project_duplication::{analyse_duplication, analyse_packages_duplication, print_global_duplication_report, render_global_duplication_report, DuplicationReport, DuplicationSummary};
+use translations_analyzer::analyse_unreferenced_files::{analyse_unreferenced_files, print_unreferenced_files_report};
+use translations_analyzer::analyse_quote_styles::{analyse_quote_styles, fix_quote_styles, print_quote_styles_report};
+use translations_analyzer::analyse_untranslated::{analyse_untranslated, print_coverage_matrix, print_untranslated_report};
+use translations_analyzer::export_badge::{badge_color_for_duplications, badge_color_for_score, render_badge, stamp_badge, write_badge, BadgeMetric};
+use translations_analyzer::export_json::{build_json_report, read_json_report, write_json_report, JSON_REPORT_SCHEMA_VERSION};
+use translations_analyzer::get_translation_for_project::get_translations_for_project;
+use translations_analyzer::canonical_catalog::{analyse_canonical_catalog, get_canonical_catalog, print_canonical_catalog_report, CanonicalCatalogError};
+use translations_analyzer::cluster_graph::{build_cluster_graph, render_cluster_graph, write_cluster_graph, GraphFormat};
+use translations_analyzer::cluster_details::{collect_cluster_occurrences, print_cluster_details, ClusterDetails};
+use translations_analyzer::cluster_id::{matches_cluster_id, translation_cluster_id};
+use translations_analyzer::cluster_similar_values::{cluster_similar_values, print_similar_values_clusters};
+use translations_analyzer::collation::compare_text;
+use translations_analyzer::entities::Translation;
+use translations_analyzer::health_score::{analyse_package_health, locales_by_package, overall_score, print_package_health, PackageHealth};
+use translations_analyzer::list_packages::{list_packages, print_packages_listing};
+use translations_analyzer::init_settings::{detect_settings, reference_file_regex, starter_settings, write_starter_settings};
+use translations_analyzer::key_count_limits::{check_key_counts, count_keys_by_package, get_key_counts_baseline, print_key_counts_report, write_key_counts_baseline};
+use translations_analyzer::lint_keys::{lint_keys, print_lint_keys_report};
+use translations_analyzer::load_translations::{load_translations_with_progress, LoadOptions, LoadedTranslations};
+use translations_analyzer::locales::{compile_locale_glob_of, split_by_locale, translation_locale, LocaleFilePatterns, LocaleMatcher};
+use translations_analyzer::map_translations_by_key::map_translations_by_translation;
+use translations_analyzer::merge_translation_files::merge_translation_files;
+use translations_analyzer::move_translation_keys::{move_translation_keys, KeySelection, PackageFiles};
+use translations_analyzer::package_dependencies::{get_package_dependencies, PackageDependencies};
+use translations_analyzer::progress::{NoProgress, ProgressEvent, ProgressListener};
+use translations_analyzer::pipeline::{parse_pipeline, PipelineStep, PipelineTask, DEFAULT_BADGE_OUTPUT, DEFAULT_JSON_OUTPUT};
+use translations_analyzer::map_translations_by_project::{map_translations_by_project, PackagePathPattern};
+use translations_analyzer::rename_translation_keys::{parse_rename_mapping, rename_source_references, rename_translation_keys};
+use translations_analyzer::render_diff::DiffFormat;
+use translations_analyzer::run_stamp::{parse_run_id, RunStamp};
+use translations_analyzer::trace::{enable_tracing, trace_span, write_trace};
+use serde_json::{json, Value};
+use translations_analyzer::run_summary::{write_run_summary, RunSummary};
+use translations_analyzer::safe_write::{enable_read_only, undo_last_session, write_output_file, WriteSession};
+use translations_analyzer::self_test::{build_analyses_report, diff_reports, find_self_test_fixtures, normalize_report, read_expected_analyses};
+use translations_analyzer::scaffold_package::scaffold_package;
+use translations_analyzer::scan_source_files::{find_source_files, scan_string_literals};
+use translations_analyzer::score_proposed_translation::print_proposed_translation_score;
+use translations_analyzer::search_recursive_regex::{enable_strict_io, search_recursive_pattern, search_recursive_patterns, skipped_directories, FilePattern};
+use translations_analyzer::settings::{self, Settings, SettingsFileManagerError, DEFAULT_DUPLICATION_BASELINE_FILE_PATH};
+use translations_analyzer::duplication_thresholds::{check_duplication_thresholds, DuplicationCounts};
+use translations_analyzer::duplication_baseline::{apply_duplication_baseline, create_duplication_baseline, get_duplication_baseline, write_duplication_baseline, DuplicationBaseline, DuplicationBaselineError};
+use translations_analyzer::validate_settings::{locate_settings_issues, validate_settings, SettingsIssueSeverity};
+use translations_analyzer::settings_schema::{settings_json_schema, settings_schema_drift, DEFAULT_SETTINGS_SCHEMA_OUTPUT};
+use translations_analyzer::settings_overrides::{PackageAnalysis, SettingsOverride, SettingsOverrides};
+use translations_analyzer::test_fixtures::TestFixtures;
+use translations_analyzer::value_provenance::ValueHistory;
+use translations_analyzer::suggest_common_translations::{print_common_translations_suggestions, suggest_common_translations, CommonModulePath};
+use translations_analyzer::split_translation_file::{parse_split_mapping, split_translation_file};
 
 #[derive(Error, Debug)]
 pub enum CliError {
     #[error("Customer {0} not found")]
     CustomerNotFound(String),
 
-    #[error("Not implemented yet")]
-    NotImplementedYet(),
-
     #[error("{0}")]
     CommandNotExists(String),
+
+    #[error("Check failed : {0}")]
+    CheckFailed(String),
+
+    #[error("Cluster #{0} not found")]
+    ClusterNotFound(String),
+
+    #[error("Task {0} not found in the settings, available tasks : {1}")]
+    TaskNotFound(String, String),
+
+    #[error("The {0} command writes files and cannot run with --read-only")]
+    ReadOnly(String),
+
+    #[error("The {0} output writes a file and cannot be set with --read-only")]
+    ReadOnlyOutput(String),
+
+    #[error("The settings schema has drifted from the settings : {0}")]
+    SchemaDrift(String),
 }
 
 #[derive(Parser)]
@@ -40,14 +117,87 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     root_path: Option<PathBuf>,
 
-    /// Sets a custom config file
+    /// Sets a custom config file, searched from the current directory up to the root path by default
     #[arg(long, value_name = "FILE")]
     config_file_path: Option<PathBuf>,
 
+    /// Applies a profile of the settings file, as `ci` or `local`, over the rest of the settings
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Writes a JSON summary of the run (counts, thresholds, pass/fail, duration) to this file
+    #[arg(long, value_name = "FILE")]
+    summary_file: Option<PathBuf>,
+
+    /// Prints the progress of the `run` and `task` pipelines (parsed files, analysed packages) to stderr
+    #[arg(long)]
+    progress: bool,
+
+    /// Refuses to run the commands changing translation or source files, dry runs and reports being allowed
+    #[arg(long)]
+    read_only: bool,
+
+    /// Identifier stamped on the artifacts of the run (reports, badge, summary), a new ULID by default
+    /// Letters, digits, `.`, `_` and `-` only, as a CI build number
+    #[arg(long, value_parser = parse_run_id)]
+    run_id: Option<String>,
+
+    /// Leaves the date out of the artifacts, so identical runs give identical files
+    #[arg(long)]
+    no_timestamp: bool,
+
+    /// Fails on the first directory that cannot be read, instead of skipping it with a warning
+    #[arg(long)]
+    strict_io: bool,
+
+    /// Writes the walk, parse, index and analyze spans of the run to this file, in the Chrome tracing format
+    #[arg(long)]
+    trace_out: Option<PathBuf>,
+
+    /// Reports every duplication, the ones of the duplication baseline included
+    #[arg(long)]
+    no_baseline: bool,
+
+    /// Overrides the `translation_file_regex` setting
+    #[arg(long, value_name = "REGEX")]
+    translation_file_regex: Option<String>,
+
+    /// Overrides the `skip_directories` setting, repeated for each skipped directory
+    #[arg(long = "skip-dir", value_name = "DIRECTORY")]
+    skip_directories: Vec<String>,
+
+    /// Overrides the `common_translations_modules_path` setting, repeated for each common translations module
+    #[arg(long = "common-translations-path", value_name = "PATH")]
+    common_translations_paths: Vec<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+impl Cli {
+    /// Settings given on the command line take precedence over the settings file
+    fn override_settings(&self, config: &mut Settings) {
+        if let Some(translation_file_regex) = &self.translation_file_regex {
+            config.translation_file_regex = translation_file_regex.clone();
+        }
+        if !self.skip_directories.is_empty() {
+            config.skip_directories = self.skip_directories.clone();
+        }
+        if !self.common_translations_paths.is_empty() {
+            config.common_translations_modules_path = self.common_translations_paths.clone();
+        }
+        if self.no_baseline {
+            config.duplication_baseline_file_path = None;
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum BaselineAction {
+    /// Record the current duplications as known, so later runs only report the new ones
+    Create,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Init invoices path
@@ -60,128 +210,2011 @@ enum Commands {
         /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
         #[arg(long)]
         package_path: Option<String>,
+
+        /// Also reports values similar to others according to the configured similarity threshold
+        #[arg(long)]
+        near_duplicates: bool,
+
+        /// Rendering of the differences between near-duplicate values
+        #[arg(long, value_enum, default_value_t = DiffFormat::Auto)]
+        diff_format: DiffFormat,
+
+        /// Also reports JSX texts and attributes of the package that are not translated
+        #[arg(long)]
+        hardcoded_strings: bool,
+
+        /// Writes the report of each package to its own file of this directory instead of printing it, as
+        /// `packages/manager/apps/zimbra.txt`
+        #[arg(long)]
+        output_dir: Option<PathBuf>,
+    },
+    /// Report values with replacement characters, double-encoded UTF-8 or control characters
+    EncodingReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
     },
-}
+    /// Report values embedding a JSON structure or a full HTML document
+    EmbeddedBlobs {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report keys whose placeholders differ between locales
+    PlaceholdersReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report keys whose markup tags differ between locales
+    MarkupReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report plural keys and ICU plural blocks missing forms required by their language
+    PluralsReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report values that are not valid ICU MessageFormat messages
+    IcuReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report duplications of every locale and values identical between locales
+    CrossLocaleReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report values identical to the reference locale and the translation completeness of each package
+    UntranslatedReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Show the percentage of translated keys of every package and locale as a matrix
+    Coverage {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report translation files that no source file of their package references
+    UnreferencedFiles {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Group similar values into clusters, each with a suggested canonical value
+    SimilarClusters {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report values embedding hardcoded formatted numbers or dates instead of placeholders
+    FormattedValuesReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report French words written with inconsistent inclusive writing styles
+    InclusiveWritingReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report apostrophes and quotes differing from the style expected for the locale
+    QuotesReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
 
-const DEFAULT_SETTINGS_PATH_FILE: &str = "settings.json";
+        /// Rendering of the fixes
+        #[arg(long, value_enum, default_value_t = DiffFormat::Auto)]
+        diff_format: DiffFormat,
 
-fn main() {
-    let cli = Cli::parse();
+        /// Rewrites the reported values with the expected style
+        #[arg(long)]
+        fix: bool,
 
-    let current_dir = env::current_dir().unwrap();
-    let monorepo_path = cli.root_path.as_deref().unwrap_or(current_dir.as_path());
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Report values much longer or shorter than the reference locale value
+    LengthReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Export an SVG badge with the total duplication count
+    ExportBadge {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
 
-    println!("Root path : {}", monorepo_path.to_string_lossy());
+        /// Badge file to write
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_BADGE_OUTPUT)]
+        output: PathBuf,
 
-    let config_file_path = cli.config_file_path.as_deref().unwrap_or(Path::new(DEFAULT_SETTINGS_PATH_FILE));
+        #[arg(long, value_enum, default_value_t)]
+        metric: BadgeMetric,
+    },
+    /// Export the duplications of every package as JSON
+    ExportJson {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
 
-    let config = settings::get_settings(config_file_path).unwrap_or(Settings::default());
+        /// Report file to write
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_JSON_OUTPUT)]
+        output: PathBuf,
+    },
+    /// Convert a JSON report written by a previous release to the current schema version
+    UpgradeReport {
+        /// Report file to convert
+        #[arg(long, value_name = "FILE")]
+        input: PathBuf,
 
+        /// Converted report file, the input file being overwritten when not given
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Run the analyses over fixture monorepos and compare their JSON results with the expected ones,
+    /// to validate a build of the analyzer and its settings
+    SelfTest {
+        /// Directory of the fixture monorepos, as `tests/self-test`, each with its `expected-report.json` of the
+        /// duplications, its `expected-analyses.json` of the other analyses and optionally its `settings.json`
+        #[arg(long, value_name = "DIR")]
+        fixtures: PathBuf,
 
-    let result: Result<(), Box<dyn Error + Sync + Send + 'static>> = match &cli.command {
-        Some(Commands::GlobalReport { package_path }) => match package_path {
-            Some(package_path) => global_report_for_project(monorepo_path, config, package_path),
-            None => global_report_all(monorepo_path, config),
-        }
-        Some(Commands::DetailedReport { package_path }) => match package_path {
-            Some(package_path) => detailled_report_for_project(monorepo_path, config, package_path),
-            None => Err(Box::new(CliError::NotImplementedYet())),
-        }
-        None => Err(Box::new(CliError::CommandNotExists("The option is not correct. Try to get help".to_string())))
-    };
+        /// Writes the reports produced as the expected ones
+        #[arg(long)]
+        update: bool,
+    },
+    /// List the packages holding translations, with their type and their number of translation files and keys
+    ListPackages,
+    /// Inspect the monorepo for its translation files, locales, package layout and common modules, and write a
+    /// starter settings file from the confirmed values
+    Init {
+        /// Accepts the detected values without asking
+        #[arg(long)]
+        yes: bool,
 
-    result.unwrap_or_else(|error| println!("Error : {}", error));
-}
+        /// Replaces an existing settings file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write the JSON Schema of the settings file, for editors and CI to validate it
+    Schema {
+        /// Schema file to write, referred to by the `$schema` field of the settings file
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_SETTINGS_SCHEMA_OUTPUT)]
+        output: PathBuf,
+    },
+    /// Report keys defined several times in a same translation file
+    DuplicateKeys {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report keys differing only by their case
+    KeyCasingReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report values using a non-approved variant of a glossary term
+    GlossaryReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report values of the canonical catalog used under other keys, and catalog entries without users
+    CatalogReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Report keys defined with different values by several packages
+    KeyCollisionsReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Aggregate the keys of each package by prefix, showing prefixes shared between packages
+    KeyPrefixesReport {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Show every occurrence of a duplicate cluster, its suppression status and the suggested action
+    Cluster {
+        /// Cluster identifier, as displayed by the reports
+        id: String,
+    },
+    /// Export the packages, files, keys and source call sites of a cluster as a graph, to see what a consolidation would touch
+    ClusterGraph {
+        /// Cluster identifier, as displayed by the reports
+        id: String,
 
-fn global_report_all(monorepo_path: &Path, config: Settings) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
-    let matches = search_recursive_regex(
-        monorepo_path,
-        &config.translation_file_regex,
-        &config.skip_directories
-    ).unwrap();
-    println!("Found {} files", matches.len());
+        #[arg(long, value_enum, default_value_t)]
+        format: GraphFormat,
 
-    let translations = load_translations(matches).expect("Cannot map translations");
+        /// Graph file to write
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Tell whether a new translation would be a duplication, and the existing key to use instead
+    Score {
+        /// Translation file the translation would be added to, relative to the root path
+        #[arg(long, value_name = "FILE")]
+        file: PathBuf,
 
-    let translations_indexed = map_translations_by_translation(&translations);
+        #[arg(long)]
+        key: String,
 
-    let mapped_by_project = map_translations_by_project(&translations);
+        #[arg(long)]
+        value: String,
+    },
+    /// Report common translations that no source file references and no package duplicates
+    DeadCommonTranslations,
+    /// Suggest a common translation key for each duplicated value
+    Suggest {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+        /// Finds in the git history the package each value to move comes from, to adopt its key naming
+        #[arg(long)]
+        provenance: bool,
+    },
+    /// Report keys breaking the configured key rules, with a rename when it is safe
+    LintKeys {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+    },
+    /// Manage the baseline of the known duplications, which are not reported again
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+    /// Run the checks and fail when one of them does not pass
+    Check,
+    /// Fail when a package has more keys than its cap, or grew faster than allowed since the baseline
+    KeyCounts {
+        /// Writes the current key counts as the new baseline
+        #[arg(long)]
+        update_baseline: bool,
+    },
+    /// Run several commands on a single scan of the monorepo, as `check + export-json + export-badge`
+    Run {
+        /// Steps separated by `+` among global-report, suggest, check, export-json and export-badge
+        pipeline: String,
 
-    for package_path in mapped_by_project.keys() {
-        println!("Analyse project : {}", package_path);
-        let reports_duplication = analyse_duplication(&package_path, &mapped_by_project[package_path], &translations_indexed);
-        print_global_duplication_report(&reports_duplication);
-    }
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
 
-    Ok(())
-}
+        /// Report file written by the export-json step
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_JSON_OUTPUT)]
+        json_output: PathBuf,
 
+        /// Badge file written by the export-badge step
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_BADGE_OUTPUT)]
+        badge_output: PathBuf,
 
-fn global_report_for_project(monorepo_path: &Path, config: Settings, package_path: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
-    let matches = search_recursive_regex(
-        monorepo_path,
-        &config.translation_file_regex,
-        &config.skip_directories
-    ).unwrap();
-    println!("Found {} files", matches.len());
+        /// Fails when the total duplication count is above
+        #[arg(long)]
+        max_duplications: Option<usize>,
+    },
+    /// Run a task defined in the `tasks` of the settings file
+    Task {
+        /// Name of the task
+        name: String,
+    },
+    /// Split a translation file into several files according to key prefixes
+    Split {
+        /// Translation file to split
+        #[arg(long, value_name = "FILE")]
+        file: PathBuf,
 
-    let translations = load_translations(matches).expect("Cannot map translations");
+        /// Moves keys starting with a prefix into a file relative to the split one, as `prefix=target-file` (repeatable)
+        #[arg(long = "map", value_name = "PREFIX=FILE", required = true, value_parser = parse_split_mapping)]
+        mappings: Vec<(String, PathBuf)>,
 
-    let translations_indexed = map_translations_by_translation(&translations);
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge the translation files of a package into one file per locale
+    MergeFiles {
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: String,
 
-    let project_translations = get_translations_for_project(package_path, &translations);
+        /// Folder, relative to the package, receiving the merged files
+        #[arg(long, default_value = "translations")]
+        target_folder: PathBuf,
 
-    println!("Analyse project : {}", package_path);
-    let reports_duplication = analyse_duplication(&package_path, &project_translations, &translations_indexed);
-    print_global_duplication_report(&reports_duplication);
+        /// Name of the merged file of each locale, as `Messages_{locale}.json`, the name shared by the files of the
+        /// locale by default
+        #[arg(long)]
+        target_name: Option<String>,
 
-    Ok(())
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move keys from a package to another one in every locale
+    MoveKeys {
+        /// Package path the keys are moved from
+        #[arg(long)]
+        from: String,
+
+        /// Package path the keys are moved to
+        #[arg(long)]
+        to: String,
+
+        /// Key to move (repeatable)
+        #[arg(long = "key")]
+        keys: Vec<String>,
+
+        /// Moves every key starting with this prefix (repeatable)
+        #[arg(long = "prefix")]
+        prefixes: Vec<String>,
+
+        /// Leaves a note listing the moved keys in the source package
+        #[arg(long)]
+        deprecation_note: bool,
+
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename keys in the translation files of every locale from an `old,new` CSV mapping
+    RenameKeys {
+        /// CSV file of `old,new` key renames
+        #[arg(long = "map", value_name = "FILE")]
+        mapping: PathBuf,
+
+        /// Sets a custom package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        #[arg(long)]
+        package_path: Option<String>,
+
+        /// Also renames the string literals of the source files equal to a renamed key
+        #[arg(long)]
+        sources: bool,
+
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create the translation files of a new package for every locale, with a starter key in its namespace
+    Scaffold {
+        /// Package path folder as `packages/manager/apps/zimbra` or `packages/manager/modules/backup-agent`
+        package_path: String,
+
+        /// Prints the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Revert the files changed by the last write command
+    Undo,
 }
 
+impl Commands {
+    /// Whether the command changes translation or source files, the state the analyzer keeps in the monorepo, or
+    /// writes an output file
+    /// The steps of a task are only known once the settings are read, the writes of its exports being refused then
+    fn writes_files(&self) -> bool {
+        match self {
+            Commands::DetailedReport { output_dir, .. } => output_dir.is_some(),
+            Commands::Run { pipeline, .. } => parse_pipeline(pipeline)
+                .is_ok_and(|steps| steps.iter().any(|step| matches!(step, PipelineStep::ExportJson | PipelineStep::ExportBadge))),
+            Commands::ExportBadge { .. }
+            | Commands::ExportJson { .. }
+            | Commands::ClusterGraph { .. }
+            | Commands::Schema { .. }
+            | Commands::UpgradeReport { .. } => true,
+            Commands::QuotesReport { fix, dry_run, .. } => *fix && !*dry_run,
+            Commands::Split { dry_run, .. }
+            | Commands::MergeFiles { dry_run, .. }
+            | Commands::MoveKeys { dry_run, .. }
+            | Commands::RenameKeys { dry_run, .. }
+            | Commands::Scaffold { dry_run, .. } => !*dry_run,
+            Commands::KeyCounts { update_baseline } => *update_baseline,
+            Commands::SelfTest { update, .. } => *update,
+            Commands::Baseline { action: BaselineAction::Create } => true,
+            Commands::Init { .. } => true,
+            Commands::Undo => true,
+            _ => false,
+        }
+    }
+}
 
-fn detailled_report_for_project(monorepo_path: &Path, config: Settings, package_path: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
-    let matches = search_recursive_regex(
-        monorepo_path,
-        &config.translation_file_regex,
-        &config.skip_directories
-    ).unwrap();
-    println!("Found {} files", matches.len());
+fn main() {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let mut summary = RunSummary::start(matches.subcommand_name().unwrap_or_default(), RunStamp::new(cli.run_id.clone(), !cli.no_timestamp));
+
+    // A removed working directory still lets `--root-path` and `--config-file-path` be used
+    let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let monorepo_path = cli.root_path.as_deref().unwrap_or(current_dir.as_path());
 
-    let translations = load_translations(matches).expect("Cannot map translations");
+    println!("Root path : {}", monorepo_path.to_string_lossy());
 
-    let translations_indexed = map_translations_by_translation(&translations);
+    let config_file_path = cli.config_file_path.clone()
+        .or_else(|| settings::find_settings_file(&current_dir, monorepo_path))
+        .unwrap_or_else(|| PathBuf::from(settings::SETTINGS_FILE_NAMES[0]));
+    if cli.config_file_path.is_none() && config_file_path.is_file() {
+        println!("Settings file : {}", config_file_path.to_string_lossy());
+    }
 
-    let project_translations = get_translations_for_project(package_path, &translations);
+    let (mut config, file_layer) = match load_config_layers(&config_file_path, cli.profile.as_deref()) {
+        Ok(config) => config,
+        Err(error) => {
+            println!("Error : {}", error);
+            process::exit(1);
+        }
+    };
+    cli.override_settings(&mut config);
 
-    println!("Analyse project : {}", package_path);
-    let reports_duplication = analyse_duplication(&package_path, &project_translations, &translations_indexed);
-    print_global_duplication_report(&reports_duplication);
+    let env_names: Vec<String> = env::vars().map(|(name, _)| name).collect();
+    let mut settings_issues = validate_settings(&config, &file_layer, &env_names, monorepo_path);
+    if let Ok(content) = fs::read_to_string(&config_file_path) {
+        locate_settings_issues(&mut settings_issues, &config_file_path, &content);
+    }
+    for issue in &settings_issues {
+        match issue.severity {
+            SettingsIssueSeverity::Error => println!("Error : invalid settings, {}", issue),
+            SettingsIssueSeverity::Warning => println!("Warning : settings {}", issue),
+        }
+    }
+    if settings_issues.iter().any(|issue| issue.severity == SettingsIssueSeverity::Error) {
+        process::exit(1);
+    }
 
-    let mut displayed_translations: HashSet<String> = HashSet::new();
+    let progress: &dyn ProgressListener = if cli.progress { &print_progress } else { &NoProgress };
 
-    for duplication in reports_duplication {
-        if !displayed_translations.insert(duplication.translation.translations.clone()) {
-            continue;
+    let summary_file_path = cli.summary_file.clone().or_else(|| config.summary_file_path.as_ref().map(PathBuf::from));
+
+    if cli.read_only {
+        enable_read_only();
+
+        // Refused before any analysis, rather than failing when written once the run is over
+        let output = if cli.summary_file.is_some() {
+            Some("--summary-file")
+        } else if summary_file_path.is_some() {
+            Some("summary_file_path setting")
+        } else if cli.trace_out.is_some() {
+            Some("--trace-out")
+        } else {
+            None
+        };
+        if let Some(output) = output {
+            println!("Error : {}", CliError::ReadOnlyOutput(output.to_string()));
+            process::exit(1);
+        }
+    }
+
+    if cli.trace_out.is_some() {
+        enable_tracing();
+    }
+
+    if cli.strict_io {
+        enable_strict_io();
+    }
+
+    let result: Result<(), Box<dyn Error + Sync + Send + 'static>> = match &cli.command {
+        Some(command) if cli.read_only && command.writes_files() => {
+            Err(Box::new(CliError::ReadOnly(matches.subcommand_name().unwrap_or_default().to_string())))
+        }
+        Some(Commands::GlobalReport { package_path }) => match package_path {
+            Some(package_path) => global_report_for_project(monorepo_path, config, package_path),
+            None => global_report_all(monorepo_path, config, &mut summary),
+        }
+        Some(Commands::DetailedReport { package_path, near_duplicates, diff_format, hardcoded_strings, output_dir }) => {
+            let options = DetailedReportOptions { near_duplicates: *near_duplicates, diff_format: *diff_format, hardcoded_strings: *hardcoded_strings };
+            detailled_report(monorepo_path, config, package_path.as_deref(), &options, output_dir.as_deref())
+        }
+        Some(Commands::EncodingReport { package_path }) => encoding_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::EmbeddedBlobs { package_path }) => embedded_blobs_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::PlaceholdersReport { package_path }) => placeholders_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::MarkupReport { package_path }) => markup_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::PluralsReport { package_path }) => plurals_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::IcuReport { package_path }) => icu_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::CrossLocaleReport { package_path }) => cross_locale_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::UntranslatedReport { package_path }) => untranslated_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::Coverage { package_path }) => coverage_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::UnreferencedFiles { package_path }) => unreferenced_files_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::SimilarClusters { package_path }) => similar_clusters_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::FormattedValuesReport { package_path }) => formatted_values_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::InclusiveWritingReport { package_path }) => inclusive_writing_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::QuotesReport { package_path, diff_format, fix, dry_run }) => quotes_report(monorepo_path, config, package_path.as_deref(), *diff_format, *fix, *dry_run),
+        Some(Commands::LengthReport { package_path }) => length_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::ExportBadge { package_path, output, metric }) => export_badge(monorepo_path, config, package_path.as_deref(), output, *metric, &mut summary),
+        Some(Commands::ExportJson { package_path, output }) => export_json(monorepo_path, config, package_path.as_deref(), output, &mut summary),
+        Some(Commands::UpgradeReport { input, output }) => upgrade_report(input, output.as_deref().unwrap_or(input)),
+        Some(Commands::SelfTest { fixtures, update }) => self_test(config, fixtures, *update),
+        Some(Commands::ListPackages) => list_packages_report(monorepo_path, config),
+        Some(Commands::Init { yes, force }) => init(monorepo_path, config, *yes, *force),
+        Some(Commands::Schema { output }) => settings_schema(output),
+        Some(Commands::Baseline { action: BaselineAction::Create }) => create_baseline(monorepo_path, config, &mut summary),
+        Some(Commands::DuplicateKeys { package_path }) => duplicate_keys_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::KeyCasingReport { package_path }) => key_casing_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::GlossaryReport { package_path }) => glossary_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::CatalogReport { package_path }) => catalog_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::KeyCollisionsReport { package_path }) => key_collisions_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::KeyPrefixesReport { package_path }) => key_prefixes_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::Score { file, key, value }) => score(monorepo_path, config, file, key, value),
+        Some(Commands::Cluster { id }) => show_cluster(monorepo_path, config, id.trim_start_matches('#')),
+        Some(Commands::ClusterGraph { id, format, output }) => cluster_graph(monorepo_path, config, id.trim_start_matches('#'), *format, output),
+        Some(Commands::DeadCommonTranslations) => dead_common_translations_report(monorepo_path, config),
+        Some(Commands::Suggest { package_path, provenance }) => suggest(monorepo_path, config, package_path.as_deref(), *provenance, &mut summary),
+        Some(Commands::LintKeys { package_path }) => lint_keys_report(monorepo_path, config, package_path.as_deref()),
+        Some(Commands::KeyCounts { update_baseline }) => key_counts(monorepo_path, config, *update_baseline, &mut summary),
+        Some(Commands::Check) => check(monorepo_path, config, &mut summary),
+        Some(Commands::Run { pipeline, package_path, json_output, badge_output, max_duplications }) => {
+            let task = PipelineTask {
+                pipeline: pipeline.clone(),
+                package_path: package_path.clone(),
+                json_output: json_output.clone(),
+                badge_output: badge_output.clone(),
+                max_duplications: *max_duplications,
+            };
+            run_pipeline(monorepo_path, &config, &task, &mut summary, progress)
         }
-        println!("\n");
-        
-        let other_usages = translations_indexed.get(&duplication.translation.translations).unwrap();
-        
-        println!(" ========= Duplication seen : {} times, type : {:?} ==========", other_usages.len(), duplication.duplication_type);
-        println!(" ========= {} ==========", duplication.translation.translations);
+        Some(Commands::Task { name }) => run_task(monorepo_path, config, name, &mut summary, progress),
+        Some(Commands::Split { file, mappings, dry_run }) => split_file(monorepo_path, config, file, mappings, *dry_run),
+        Some(Commands::MergeFiles { package_path, target_folder, target_name, dry_run }) => merge_files(monorepo_path, config, package_path, target_folder, target_name.as_deref(), *dry_run),
+        Some(Commands::MoveKeys { from, to, keys, prefixes, deprecation_note, dry_run }) => {
+            let selection = KeySelection { keys: keys.clone(), prefixes: prefixes.clone() };
+            move_keys(monorepo_path, config, from, to, &selection, *deprecation_note, *dry_run)
+        }
+        Some(Commands::RenameKeys { mapping, package_path, sources, dry_run }) => rename_keys(monorepo_path, config, mapping, package_path.as_deref(), *sources, *dry_run),
+        Some(Commands::Scaffold { package_path, dry_run }) => scaffold(monorepo_path, config, package_path, *dry_run),
+        Some(Commands::Undo) => undo_last_session(monorepo_path)
+            .map(|count| println!("Restored {} files", count))
+            .map_err(|e| e.into()),
+        None => Err(Box::new(CliError::CommandNotExists("The option is not correct. Try to get help".to_string())))
+    };
 
-        for other_usage in other_usages {
-            println!("{} {} - {}", add_star_if_own_package(package_path, &other_usage.path.to_string_lossy().to_string()), other_usage.path.strip_prefix(&monorepo_path).unwrap().to_string_lossy(), other_usage.key);
+    let skipped_directories = skipped_directories();
+    if !skipped_directories.is_empty() {
+        println!("Warning : {} directories skipped, permission denied", skipped_directories.len());
+        for path in &skipped_directories {
+            println!(" - {}", path.display());
         }
     }
+    summary.record_count("skipped_directories", skipped_directories.len());
+
+    summary.finish(result.as_ref().err().map(|error| error.to_string()));
+    if let Some(trace_path) = &cli.trace_out {
+        match write_trace(trace_path) {
+            Ok(count) => println!("{} trace spans written to {}", count, trace_path.display()),
+            Err(error) => println!("Error : unable to write the trace, {}", error),
+        }
+    }
+    if let Some(summary_file_path) = summary_file_path
+        && let Err(error) = write_run_summary(&summary_file_path, &summary) {
+        println!("Error : {}", error);
+        process::exit(1);
+    }
+
+    if let Err(error) = result {
+        println!("Error : {}", error);
+        process::exit(1);
+    }
+}
+
+fn print_progress(event: ProgressEvent) {
+    match event {
+        ProgressEvent::FilesDiscovered { count } => eprintln!("Discovered {} files", count),
+        ProgressEvent::FileParsed { parsed, total, .. } => eprintln!("Parsed {}/{} files", parsed, total),
+        ProgressEvent::PackageAnalyzed { package_path, analyzed, total, findings } => {
+            eprintln!("Analysed {}/{} packages ({}), {} duplications so far", analyzed, total, package_path, findings)
+        }
+    }
+}
+
+/// Settings of the environment variables overridden by the settings file, the file layer being returned
+/// for validation, null when there is no settings file
+fn load_config_layers(config_file_path: &Path, profile: Option<&str>) -> Result<(Settings, Value), SettingsFileManagerError> {
+    let environment = settings::read_settings_env(env::vars())?;
+    let file = match settings::read_settings_file(config_file_path) {
+        Err(SettingsFileManagerError::UnableToReadPath(..)) => Value::Null,
+        file => file?,
+    };
+    let profile = match profile {
+        Some(profile) => settings::read_settings_profile(&file, profile)?,
+        None => Value::Null,
+    };
+
+    Ok((settings::merge_settings(vec![environment, file.clone(), profile])?, file))
+}
+
+fn load_all_translations(monorepo_path: &Path, config: &Settings) -> Result<Vec<Translation>, Box<dyn Error + Sync + Send + 'static>> {
+    Ok(load_translations_matching(monorepo_path, &config.reference_file_pattern(), |settings_override| settings_override.translation_file_regex.as_ref(), config, Some(&config.reference_locale), &NoProgress)?.translations)
+}
+
+fn load_all_locales_translations(monorepo_path: &Path, config: &Settings) -> Result<Vec<Translation>, Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_translations_matching(monorepo_path, &config.locale_files_pattern(), |settings_override| settings_override.locales_file_regex.as_ref(), config, None, &NoProgress)?.translations;
+
+    // An invalid regex is reported by the analyses themselves, the `locales` patterns telling the locale of their files
+    if config.locales.patterns().is_empty()
+        && let Ok(locale_regex) = Regex::new(&config.locales_file_regex)
+    {
+        let paths: BTreeSet<&Path> = translations.iter().map(|translation| translation.path.as_path()).collect();
+        for ambiguity in analyse_locale_ambiguities(paths, &locale_regex, config.locale_capture_group.as_deref(), &package_path_pattern) {
+            println!("Warning : ambiguous locales in {}", ambiguity);
+        }
+    }
+
+    Ok(translations)
+}
+
+/// `multi_locale_filter` is the locale kept from the files holding several locales, all of them being kept when unset
+/// `override_regex` gives the regex replacing `translation_file_regex` in the packages of a settings override
+fn load_translations_matching(monorepo_path: &Path, translation_file_pattern: &FilePattern, override_regex: fn(&SettingsOverride) -> Option<&String>, config: &Settings, multi_locale_filter: Option<&str>, progress: &dyn ProgressListener) -> Result<LoadedTranslations, Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let walk_span = trace_span("walk", "walk", json!({ "pattern": translation_file_pattern.to_string() }));
+    let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+    let matches = search_recursive_patterns(
+        monorepo_path,
+        monorepo_path,
+        &overrides.file_patterns(translation_file_pattern, override_regex),
+        &config.skip_directories
+    )?;
+    let matches = overrides.select_files(matches, override_regex);
+    drop(walk_span);
+    println!("Found {} files", matches.len());
+
+    let options = translations_load_options(translation_file_pattern, override_regex, config, multi_locale_filter)?;
+    let loaded = load_translations_with_progress(matches, &options, progress)?;
+    if !loaded.duplicate_keys.is_empty() {
+        println!("Warning : {} keys are defined several times in a same file", loaded.duplicate_keys.len());
+    }
+    for skipped in &loaded.skipped_files {
+        println!("Warning : skipped {}, {}", skipped.path.display(), skipped.reason);
+    }
+    if !loaded.errors.is_empty() {
+        println!("Warning : {} translation files could not be loaded", loaded.errors.len());
+        for error in &loaded.errors {
+            println!("  {}", error);
+        }
+    }
+
+    Ok(loaded)
+}
+
+/// Options loading the files of a translation file pattern, the locale of their translations being told by the
+/// `locales` patterns, the reference glob or the locale regexes
+fn translations_load_options(translation_file_pattern: &FilePattern, override_regex: fn(&SettingsOverride) -> Option<&String>, config: &Settings, multi_locale_filter: Option<&str>) -> Result<LoadOptions, Box<dyn Error + Sync + Send + 'static>> {
+    Ok(LoadOptions {
+        nested_key_separator: config.nested_key_separator.clone(),
+        multi_locale_filter: multi_locale_filter.map(str::to_string),
+        // Files matched by the reference locale regex may not capture their locale, the locales regex does, the
+        // `locales` patterns telling the locale of their files without any capture
+        locale_file_patterns: LocaleFilePatterns::compile(config.locales.patterns())?,
+        // The reference glob selects files of the reference locale, unless its `{locale}` tells their locale
+        locale_globs: match translation_file_pattern {
+            FilePattern::Glob(glob) => compile_locale_glob_of(glob).transpose()?.into_iter().collect(),
+            FilePattern::Regex(_) => Vec::new(),
+        },
+        default_locale: match translation_file_pattern {
+            FilePattern::Glob(_) => multi_locale_filter.map(str::to_string),
+            FilePattern::Regex(_) => None,
+        },
+        locale_regexes: translation_file_pattern.regex()
+            .filter(|_| config.locales.patterns().is_empty())
+            .into_iter()
+            .chain(config.locales.patterns().is_empty().then_some(config.locales_file_regex.as_str()))
+            .chain(config.overrides.iter().filter_map(|settings_override| override_regex(settings_override).map(String::as_str)))
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect(),
+        locale_capture_group: config.locale_capture_group.clone(),
+        error_policy: config.load_error_policy,
+        max_file_size: config.max_translation_file_size,
+        ..LoadOptions::default()
+    })
+}
+
+fn select_translations<'a>(package_path: Option<&str>, translations: &'a [Translation], package_path_pattern: &PackagePathPattern) -> Vec<&'a Translation> {
+    match package_path {
+        Some(package_path) => get_translations_for_project(package_path, translations, package_path_pattern),
+        None => translations.iter().collect(),
+    }
+}
+
+fn global_report_all(monorepo_path: &Path, config: Settings, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, None, summary, &NoProgress)?;
+
+    print_packages_global_report(monorepo_path, &config, &context)
+}
+
+
+fn global_report_for_project(monorepo_path: &Path, config: Settings, package_path: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let allowed_duplications = compile_allowed_duplications(monorepo_path, &config)?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let dependencies = get_package_dependencies(monorepo_path, &translations, &package_path_pattern)?;
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+    let test_fixtures = TestFixtures::compile(monorepo_path, &config.test_fixtures)?;
+
+    let translations_indexed = map_translations_by_translation(&translations, &config.normalization, &config.duplication_filters);
+
+    let project_translations = get_translations_for_project(package_path, &translations, &package_path_pattern);
+
+    println!("Analyse project : {}", package_path);
+    let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+    let mut reports_duplication = Vec::new();
+    if overrides.is_disabled(package_path, PackageAnalysis::Duplication) {
+        println!("Warning : the duplication analysis of {} is disabled by its settings override", package_path);
+    } else {
+        reports_duplication = analyse_duplication(package_path, &project_translations, &translations_indexed, &config.normalization, &allowed_duplications, &dependencies, &common_modules_path, &test_fixtures, &config.i18next_keys, &locale_matcher, &package_path_pattern);
+        apply_triage_rules_to(&mut reports_duplication, &translations_indexed, &config.normalization, &TriageRules::compile(&config.triage_rules)?, &package_path_pattern);
+        if let Some(baseline) = load_duplication_baseline(monorepo_path, &config)? {
+            apply_duplication_baseline(monorepo_path, &mut reports_duplication, &baseline);
+        }
+        print_global_duplication_report(&reports_duplication);
+    }
+
+    if !overrides.is_disabled(package_path, PackageAnalysis::Health) {
+        let package_locales = find_package_locales(monorepo_path, &config, &overrides, &package_path_pattern)?;
+        let health = analyse_package_health(
+            &project_translations,
+            &reports_duplication,
+            &config.key_rules,
+            package_locales.of(package_path),
+            &package_locales.expected,
+        )?;
+        print_package_health(&health);
+    }
+
+    Ok(())
+}
+
+
+/// Corpus of the detailed report, loaded once and shared by the report of every package
+struct DetailedReportCorpus<'a> {
+    locale_matcher: LocaleMatcher,
+    package_path_pattern: PackagePathPattern,
+    allowed_duplications: AllowedDuplications,
+    annotations: Annotations,
+    dependencies: PackageDependencies,
+    common_modules_path: Vec<CommonModulePath>,
+    test_fixtures: TestFixtures,
+    triage_rules: TriageRules,
+    baseline: Option<DuplicationBaseline>,
+    translations: &'a [Translation],
+    translations_indexed: HashMap<String, Vec<&'a Translation>>,
+}
+
+struct DetailedReportOptions {
+    near_duplicates: bool,
+    diff_format: DiffFormat,
+    hardcoded_strings: bool,
+}
+
+fn detailled_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>, options: &DetailedReportOptions, output_dir: Option<&Path>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let corpus = DetailedReportCorpus {
+        locale_matcher: LocaleMatcher::compile(&config)?,
+        package_path_pattern: package_path_pattern.clone(),
+        allowed_duplications: compile_allowed_duplications(monorepo_path, &config)?,
+        annotations: get_annotations(&monorepo_path.join(&config.annotations_file_path))?,
+        dependencies: get_package_dependencies(monorepo_path, &translations, &package_path_pattern)?,
+        common_modules_path: config.common_modules_paths(monorepo_path),
+        test_fixtures: TestFixtures::compile(monorepo_path, &config.test_fixtures)?,
+        triage_rules: TriageRules::compile(&config.triage_rules)?,
+        baseline: load_duplication_baseline(monorepo_path, &config)?,
+        translations: &translations,
+        translations_indexed: map_translations_by_translation(&translations, &config.normalization, &config.duplication_filters),
+    };
+
+    // Every package of the monorepo without a package path, as the global report
+    let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+    let packages_path: Vec<String> = match package_path {
+        Some(package_path) if overrides.is_disabled(package_path, PackageAnalysis::Duplication) => {
+            println!("Warning : the duplication analysis of {} is disabled by its settings override", package_path);
+            Vec::new()
+        }
+        Some(package_path) => vec![package_path.to_string()],
+        None => {
+            let mut packages_path: Vec<String> = map_translations_by_project(&translations, &package_path_pattern)
+                .into_keys()
+                .filter(|package_path| !overrides.is_disabled(package_path, PackageAnalysis::Duplication))
+                .collect();
+            packages_path.sort();
+            packages_path
+        }
+    };
+
+    for package_path in &packages_path {
+        let report = render_detailled_report_for_project(monorepo_path, &config, &corpus, package_path, options)?;
+        match output_dir {
+            Some(output_dir) => {
+                // The directories of the package path are kept, so no two packages share a report file
+                let report_file_path = output_dir.join(format!("{}.txt", package_path));
+                if let Some(report_folder) = report_file_path.parent() {
+                    fs::create_dir_all(report_folder)?;
+                }
+                write_output_file(&report_file_path, report)?;
+                println!("Detailed report of {} written to {}", package_path, report_file_path.display());
+            }
+            None => print!("{}", report),
+        }
+    }
+
+    Ok(())
+}
+
+fn render_detailled_report_for_project(monorepo_path: &Path, config: &Settings, corpus: &DetailedReportCorpus, package_path: &str, options: &DetailedReportOptions) -> Result<String, Box<dyn Error + Sync + Send + 'static>> {
+    let translations_indexed = &corpus.translations_indexed;
+    let project_translations = get_translations_for_project(package_path, corpus.translations, &corpus.package_path_pattern);
+
+    let mut report = format!("Analyse project : {}\n", package_path);
+    let mut reports_duplication = analyse_duplication(package_path, &project_translations, translations_indexed, &config.normalization, &corpus.allowed_duplications, &corpus.dependencies, &corpus.common_modules_path, &corpus.test_fixtures, &config.i18next_keys, &corpus.locale_matcher, &corpus.package_path_pattern);
+    let auto_ignored = apply_triage_rules_to(&mut reports_duplication, translations_indexed, &config.normalization, &corpus.triage_rules, &corpus.package_path_pattern);
+    let baseline_duplications = match &corpus.baseline {
+        Some(baseline) => apply_duplication_baseline(monorepo_path, &mut reports_duplication, baseline),
+        None => 0,
+    };
+    report.push_str(&render_global_duplication_report(&reports_duplication));
+    if auto_ignored > 0 {
+        report.push_str(&format!("Auto-ignored by triage rules : {}\n", auto_ignored));
+    }
+    if baseline_duplications > 0 {
+        report.push_str(&format!("Known duplications in the baseline : {}\n", baseline_duplications));
+    }
+
+    // Values tagged high priority by the triage rules first, low priority ones last, then values a dependency
+    // already provides, as they can be reused today, then large and frequently duplicated ones
+    reports_duplication.sort_by(|a, b| {
+        triage_order(&a.triage).cmp(&triage_order(&b.triage))
+            .then_with(|| b.dependency_package.is_some().cmp(&a.dependency_package.is_some()))
+            .then_with(|| b.savings.cmp(&a.savings))
+            .then_with(|| compare_text(&a.translation.translations.to_string(), &b.translation.translations.to_string()))
+    });
+    let mut displayed_translations: HashSet<String> = HashSet::new();
+
+    for duplication in reports_duplication {
+        let normalized_value = duplication.translation.translations.normalized(&config.normalization);
+        if !displayed_translations.insert(normalized_value.clone()) {
+            continue;
+        }
+        let Some(other_usages) = translations_indexed.get(&normalized_value) else {
+            continue;
+        };
+        report.push_str("\n\n");
+
+        report.push_str(&format!(" ========= Cluster #{} - Duplication seen : {} times, type : {:?}, savings : {} ==========\n", duplication.cluster_id, other_usages.len(), duplication.duplication_type, duplication.savings));
+        report.push_str(&format!(" ========= {} ==========\n", duplication.translation.translations));
+        report.push_str(&format!("Canonical key : {}\n", duplication.canonical_key));
+        if let Some(common_module) = &duplication.common_module {
+            report.push_str(&format!("Common translations module : {}\n", common_module));
+        }
+        if let Some(dependency_package) = &duplication.dependency_package {
+            report.push_str(&format!("Reusable from dependency : {}\n", dependency_package));
+        }
+        if let Some(triage) = &duplication.triage {
+            report.push_str(&format!("Triage : {} ({})\n", triage.action, triage.rule));
+        }
+        if let Some(annotation) = corpus.annotations.get(&duplication.cluster_id) {
+            report.push_str(&format!("Annotation : {}\n", annotation));
+        }
+
+        let formats: BTreeSet<String> = other_usages.iter().filter_map(|other_usage| other_usage.format()).map(|format| format!("{:?}", format)).collect();
+        if formats.len() > 1 {
+            report.push_str(&format!("Formats : {}\n", formats.into_iter().collect::<Vec<String>>().join(", ")));
+        }
+
+        for (path, keys) in config.i18next_keys.group_variants(other_usages) {
+            report.push_str(&format!("{} {} - {}\n", add_star_if_own_package(package_path, &path.to_string_lossy(), &corpus.package_path_pattern), path.strip_prefix(monorepo_path).unwrap_or(path).to_string_lossy(), keys));
+        }
+    }
+
+    report.push_str("\n\n\n");
+
+    if options.near_duplicates {
+        let reports_near_duplication = analyse_near_duplication(
+            &project_translations,
+            translations_indexed,
+            config.near_duplicate_algorithm,
+            config.near_duplicate_threshold,
+            &config.normalization,
+            &corpus.locale_matcher,
+        );
+        report.push_str(&render_near_duplication_report(monorepo_path, &reports_near_duplication, options.diff_format));
+    }
+
+    if options.hardcoded_strings {
+        let extensions: Vec<String> = JSX_FILE_EXTENSIONS.iter().map(|extension| extension.to_string()).collect();
+        let source_files = find_source_files(&monorepo_path.join(package_path), &extensions, &config.skip_directories)?;
+        report.push_str(&render_hardcoded_strings_report(monorepo_path, &analyse_hardcoded_strings(&source_files)));
+    }
+
+    Ok(report)
+}
+
+fn encoding_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_encoding(&translations_to_check);
+    print_encoding_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn embedded_blobs_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_embedded_blobs(&translations_to_check);
+    print_embedded_blobs_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn placeholders_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_placeholders(&translations_to_check, &locale_matcher);
+    print_placeholders_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn markup_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_markup_tags(&translations_to_check, &locale_matcher);
+    print_markup_tags_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn plurals_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_plural_forms(&translations_to_check, &locale_matcher, &config.i18next_keys);
+    print_plural_forms_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn icu_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_icu_syntax(&translations_to_check);
+    print_icu_syntax_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn cross_locale_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let allowed_duplications = compile_allowed_duplications(monorepo_path, &config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+    let dependencies = get_package_dependencies(monorepo_path, &translations, &package_path_pattern)?;
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+    let test_fixtures = TestFixtures::compile(monorepo_path, &config.test_fixtures)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+    let reports = analyse_identical_across_locales(&translations_to_check, &locale_matcher);
+
+    for (locale, locale_translations) in split_by_locale(translations.clone(), &locale_matcher) {
+        let translations_indexed = map_translations_by_translation(&locale_translations, &config.normalization, &config.duplication_filters);
+        let mapped_by_project = map_translations_by_project(&locale_translations, &package_path_pattern);
+
+        let reports_duplication: Vec<DuplicationReport> = mapped_by_project
+            .iter()
+            .filter(|(project_path, _)| package_path.is_none_or(|package_path| package_path == project_path.as_str()))
+            .flat_map(|(project_path, project_translations)| {
+                analyse_duplication(project_path, project_translations, &translations_indexed, &config.normalization, &allowed_duplications, &dependencies, &common_modules_path, &test_fixtures, &config.i18next_keys, &locale_matcher, &package_path_pattern)
+            })
+            .collect();
+
+        println!("Analyse locale : {}", locale);
+        print_global_duplication_report(&reports_duplication);
+    }
+
+    print_identical_across_locales_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn untranslated_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let expected_locales: Vec<String> = config.locales.names();
+    let report = analyse_untranslated(&translations_to_check, &locale_matcher, &config.reference_locale, &expected_locales, &package_path_pattern);
+    print_untranslated_report(monorepo_path, &config.reference_locale, &report);
+
+    Ok(())
+}
+
+fn coverage_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let expected_locales: Vec<String> = config.locales.names();
+    let report = analyse_untranslated(&translations_to_check, &locale_matcher, &config.reference_locale, &expected_locales, &package_path_pattern);
+    print_coverage_matrix(&config.reference_locale, &report);
+
+    Ok(())
+}
+
+fn unreferenced_files_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let search_path = monorepo_path.join(package_path.unwrap_or_default());
+    let translation_files = search_recursive_pattern(monorepo_path, &search_path, &config.locale_files_pattern(), &config.skip_directories)?;
+    println!("Found {} files", translation_files.len());
+
+    let source_files = find_source_files(&search_path, &config.source_file_extensions, &config.skip_directories)?;
+    let literals = scan_string_literals(&source_files);
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+
+    let reports = analyse_unreferenced_files(monorepo_path, &translation_files, &literals, &common_modules_path, &package_path_pattern);
+    print_unreferenced_files_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn similar_clusters_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let clusters = cluster_similar_values(&translations_to_check, config.near_duplicate_algorithm, config.near_duplicate_threshold);
+    print_similar_values_clusters(&clusters);
+
+    Ok(())
+}
+
+fn formatted_values_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_formatted_values(&translations_to_check, &config.formatted_value_patterns, &locale_matcher)?;
+    print_formatted_values_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn inclusive_writing_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let clusters = analyse_inclusive_writing(&translations_to_check, &locale_matcher);
+    print_inclusive_writing_report(&clusters, &package_path_pattern);
+
+    Ok(())
+}
+
+fn quotes_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>, diff_format: DiffFormat, fix: bool, dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_quote_styles(&translations_to_check, &config.quote_styles, &locale_matcher);
+    print_quote_styles_report(monorepo_path, &reports, diff_format);
+
+    if fix {
+        let mut session = WriteSession::new(monorepo_path, dry_run);
+        fix_quote_styles(&reports, &mut session)?;
+
+        let count = session.commit()?;
+        println!("{} files changed", count);
+    }
+
+    Ok(())
+}
+
+fn length_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = analyse_length_anomalies(&translations_to_check, &locale_matcher, &config.reference_locale, config.length_anomaly_ratio);
+    print_length_anomalies_report(monorepo_path, &config.reference_locale, &reports);
+
+    Ok(())
+}
+
+fn export_badge(monorepo_path: &Path, config: Settings, package_path: Option<&str>, output: &Path, metric: BadgeMetric, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, package_path, summary, &NoProgress)?;
+
+    match metric {
+        BadgeMetric::Duplications => export_badge_file(&context, output, &summary.stamp),
+        BadgeMetric::Score => {
+            let score = overall_score(&analyse_packages_health(monorepo_path, &config, &context)?);
+            let badge = render_badge("translations health", &format!("{}/100", score), badge_color_for_score(score));
+            write_badge(output, &stamp_badge(&badge, &summary.stamp))?;
+            println!("Badge written to {}", output.to_string_lossy());
+            Ok(())
+        }
+    }
+}
+
+fn export_json(monorepo_path: &Path, config: Settings, package_path: Option<&str>, output: &Path, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, package_path, summary, &NoProgress)?;
+
+    export_json_file(monorepo_path, &context, output, &summary.stamp)
+}
+
+fn duplicate_keys_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let loaded = load_translations_matching(monorepo_path, &config.locale_files_pattern(), |settings_override| settings_override.locales_file_regex.as_ref(), &config, None, &NoProgress)?;
+
+    let mut duplicate_keys = loaded.duplicate_keys;
+    if let Some(package_path) = package_path {
+        duplicate_keys.retain(|duplicate_key| package_path_pattern.package_path(&duplicate_key.path.to_string_lossy()) == package_path);
+    }
+    duplicate_keys.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| compare_text(&a.key, &b.key)));
+
+    print_duplicate_keys_report(monorepo_path, &duplicate_keys);
+
+    Ok(())
+}
+
+fn key_casing_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let all_translations: Vec<&Translation> = translations.iter().collect();
+
+    let reports = analyse_key_casing(&all_translations, package_path, &package_path_pattern);
+    print_key_casing_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn score(monorepo_path: &Path, config: Settings, file: &Path, key: &str, value: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let index = AnalyzerIndex::new(monorepo_path, config, translations)?;
+
+    print_proposed_translation_score(&index.score(file, key, value));
+
+    Ok(())
+}
+
+fn glossary_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let glossary_file_path = config.glossary_file_path.as_ref().ok_or(GlossaryError::NotConfigured)?;
+    let glossary = get_glossary(&monorepo_path.join(glossary_file_path))?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+    let translations = select_translations(package_path, &translations, &package_path_pattern);
+
+    print_glossary_report(monorepo_path, &analyse_glossary(&translations, &glossary, &locale_matcher)?);
+
+    Ok(())
+}
+
+fn catalog_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let catalog_file_path = config.canonical_catalog_file_path.as_ref().ok_or(CanonicalCatalogError::NotConfigured)?;
+    let catalog = get_canonical_catalog(&monorepo_path.join(catalog_file_path))?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+    let all_translations: Vec<&Translation> = translations.iter().collect();
+
+    // Catalog entries are unused when no package at all defines them
+    let mut report = analyse_canonical_catalog(&all_translations, &catalog, &config.normalization, &locale_matcher);
+    if let Some(package_path) = package_path {
+        report.non_canonical_keys.retain(|non_canonical_key| package_path_pattern.package_path(&non_canonical_key.translation.path.to_string_lossy()) == package_path);
+    }
+    print_canonical_catalog_report(monorepo_path, &report);
+
+    Ok(())
+}
+
+fn key_collisions_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+    let all_translations: Vec<&Translation> = translations.iter().collect();
+
+    let mut reports = analyse_key_collisions(&all_translations, &locale_matcher, &package_path_pattern);
+    if let Some(package_path) = package_path {
+        reports.retain(|report| report.definitions.contains_key(package_path));
+    }
+    print_key_collisions_report(monorepo_path, &reports);
+
+    Ok(())
+}
+
+fn key_prefixes_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let all_translations: Vec<&Translation> = translations.iter().collect();
+
+    let mut report = analyse_key_prefixes(&all_translations, &config.key_rules.segment_separator, &package_path_pattern);
+    if let Some(package_path) = package_path {
+        report.keys_by_package_prefix.retain(|project_path, _| project_path == package_path);
+        report.shared_prefixes.retain(|_, packages| packages.contains(package_path));
+    }
+    print_key_prefixes_report(&report);
+
+    Ok(())
+}
+
+fn show_cluster(monorepo_path: &Path, config: Settings, cluster_id: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let allowed_duplications = compile_allowed_duplications(monorepo_path, &config)?;
+    let annotations = get_annotations(&monorepo_path.join(&config.annotations_file_path))?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    // The whole id of the cluster, the given one may be legacy
+    let Some((cluster_id, locale, normalized_value)) = translations
+        .iter()
+        .map(|translation| (translation, translation_cluster_id(translation, &config.normalization, &locale_matcher)))
+        .find(|(_, translation_cluster_id)| matches_cluster_id(translation_cluster_id, cluster_id))
+        .map(|(translation, cluster_id)| (cluster_id, translation_locale(translation, &locale_matcher).unwrap_or_default(), translation.translations.normalized(&config.normalization)))
+    else {
+        return Err(Box::new(CliError::ClusterNotFound(cluster_id.to_string())));
+    };
+
+    // Clusters are per locale, the other locales are not involved
+    let locale_translations: Vec<Translation> = translations
+        .into_iter()
+        .filter(|translation| translation_locale(translation, &locale_matcher).unwrap_or_default() == locale)
+        .collect();
+    let dependencies = get_package_dependencies(monorepo_path, &locale_translations, &package_path_pattern)?;
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+    let test_fixtures = TestFixtures::compile(monorepo_path, &config.test_fixtures)?;
+    let translations_indexed = map_translations_by_translation(&locale_translations, &config.normalization, &config.duplication_filters);
+    let mut duplications_by_package = analyse_packages_duplication(&locale_translations, &translations_indexed, None, &config.normalization, &allowed_duplications, &dependencies, &common_modules_path, &test_fixtures, &config.i18next_keys, &locale_matcher, &package_path_pattern, &NoProgress);
+    let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+    duplications_by_package.retain(|package_path, _| !overrides.is_disabled(package_path, PackageAnalysis::Duplication));
+
+    let duplications: Vec<&DuplicationReport> = duplications_by_package
+        .values()
+        .flatten()
+        .filter(|duplication| duplication.cluster_id == cluster_id)
+        .collect();
+    // Values left out by the duplication filters are not in any cluster
+    let Some(occurrences) = translations_indexed.get(&normalized_value) else {
+        return Err(Box::new(CliError::ClusterNotFound(cluster_id)));
+    };
+
+    let details = ClusterDetails {
+        cluster_id: cluster_id.clone(),
+        locale,
+        value: &occurrences[0].translations,
+        occurrences: collect_cluster_occurrences(occurrences, &duplications),
+        suggestion: suggest_common_translations(&duplications, &translations_indexed, &config.normalization, &common_modules_path, &package_path_pattern).into_iter().next(),
+        annotation: annotations.get(&cluster_id),
+    };
+    print_cluster_details(monorepo_path, &details, &package_path_pattern);
+
+    Ok(())
+}
+
+fn cluster_graph(monorepo_path: &Path, config: Settings, cluster_id: &str, format: GraphFormat, output: &Path) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let translations = load_all_locales_translations(monorepo_path, &config)?;
+
+    let occurrences: Vec<&Translation> = translations
+        .iter()
+        .filter(|translation| matches_cluster_id(&translation_cluster_id(translation, &config.normalization, &locale_matcher), cluster_id))
+        .collect();
+    // Values left out by the duplication filters are not in any cluster
+    let cluster: Vec<Translation> = occurrences.iter().map(|occurrence| (*occurrence).clone()).collect();
+    if map_translations_by_translation(&cluster, &config.normalization, &config.duplication_filters).is_empty() {
+        return Err(Box::new(CliError::ClusterNotFound(cluster_id.to_string())));
+    }
+
+    let source_files = find_source_files(monorepo_path, &config.source_file_extensions, &config.skip_directories)?;
+    let graph = build_cluster_graph(monorepo_path, cluster_id, &occurrences, &scan_string_literals(&source_files), &package_path_pattern);
+    write_cluster_graph(output, &render_cluster_graph(&graph, format))?;
+    println!("Graph of {} nodes written to {}", graph.nodes.len(), output.display());
+
+    Ok(())
+}
+
+fn dead_common_translations_report(monorepo_path: &Path, config: Settings) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let all_translations: Vec<&Translation> = translations.iter().collect();
+    let translations_indexed = map_translations_by_translation(&translations, &config.normalization, &config.duplication_filters);
+
+    let source_files = find_source_files(monorepo_path, &config.source_file_extensions, &config.skip_directories)?;
+    let literals = scan_string_literals(&source_files);
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+
+    let dead_translations = analyse_dead_common_translations(&all_translations, &translations_indexed, &literals, &config.normalization, &common_modules_path);
+    print_dead_common_translations_report(monorepo_path, &dead_translations);
+
+    Ok(())
+}
+
+fn suggest(monorepo_path: &Path, config: Settings, package_path: Option<&str>, provenance: bool, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, package_path, summary, &NoProgress)?;
+
+    print_suggestions(monorepo_path, &config, &context, provenance)
+}
+
+fn lint_keys_report(monorepo_path: &Path, config: Settings, package_path: Option<&str>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+
+    let translations_to_check = select_translations(package_path, &translations, &package_path_pattern);
+
+    let reports = lint_keys(&translations_to_check, &config.key_rules)?;
+    print_lint_keys_report(monorepo_path, &reports, &package_path_pattern);
+
+    Ok(())
+}
+
+fn check(monorepo_path: &Path, config: Settings, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let deprecated_keys_result = check_deprecated_keys(monorepo_path, &config, summary);
+    if config.thresholds.is_empty() {
+        return deprecated_keys_result;
+    }
+
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, None, summary, &NoProgress)?;
+    let thresholds_result = check_thresholds(&config, &context, summary);
+
+    deprecated_keys_result.and(thresholds_result)
+}
+
+/// Enforces the duplication thresholds of the settings, printing every exceeded one
+fn check_thresholds(config: &Settings, context: &DuplicationContext, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let thresholds = &config.thresholds;
+    if let Some(max) = thresholds.max_duplications {
+        summary.record_threshold("max_duplications", max);
+    }
+    if let Some(max) = thresholds.max_duplications_by_package {
+        summary.record_threshold("max_duplications_by_package", max);
+    }
+    if let Some(max) = thresholds.max_new_duplications {
+        summary.record_threshold("max_new_duplications", max);
+    }
+
+    let counts = context.duplication_counts();
+    summary.record_count("new_duplications", counts.new);
+    let alerts = check_duplication_thresholds(&counts, thresholds);
+    if alerts.is_empty() {
+        return Ok(());
+    }
+
+    println!("Duplication thresholds exceeded : {}", alerts.len());
+    for alert in &alerts {
+        println!("{}", alert);
+    }
+
+    Err(Box::new(CliError::CheckFailed(format!("{} duplication thresholds exceeded", alerts.len()))))
+}
+
+fn run_task(monorepo_path: &Path, config: Settings, name: &str, summary: &mut RunSummary, progress: &dyn ProgressListener) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let Some(task) = config.tasks.get(name) else {
+        let available: Vec<&str> = config.tasks.keys().map(String::as_str).collect();
+        return Err(Box::new(CliError::TaskNotFound(name.to_string(), available.join(", "))));
+    };
+
+    println!("Run task {} : {}", name, task.pipeline);
+    run_pipeline(monorepo_path, &config, task, summary, progress)
+}
+
+fn run_pipeline(monorepo_path: &Path, config: &Settings, task: &PipelineTask, summary: &mut RunSummary, progress: &dyn ProgressListener) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let steps = parse_pipeline(&task.pipeline)?;
+    let package_path = task.package_path.as_deref();
+
+    // The check only scans source files unless duplications are capped, the translations are loaded once for the
+    // other steps
+    let duplications_capped = !config.thresholds.is_empty() || task.max_duplications.is_some();
+    let translations = if duplications_capped || steps.iter().any(|step| *step != PipelineStep::Check) {
+        load_translations_matching(monorepo_path, &config.reference_file_pattern(), |settings_override| settings_override.translation_file_regex.as_ref(), config, Some(&config.reference_locale), progress)?.translations
+    } else {
+        Vec::new()
+    };
+    let context = analyse_duplication_context(monorepo_path, &translations, config, package_path, summary, progress)?;
+
+    // A failing step does not prevent the next ones from writing their output
+    let mut first_error: Option<Box<dyn Error + Sync + Send + 'static>> = None;
+    for step in steps {
+        let result = match step {
+            PipelineStep::GlobalReport => print_packages_global_report(monorepo_path, config, &context),
+            PipelineStep::Suggest => print_suggestions(monorepo_path, config, &context, false),
+            PipelineStep::Check => check_deprecated_keys(monorepo_path, config, summary).and(check_thresholds(config, &context, summary)),
+            PipelineStep::ExportJson => export_json_file(monorepo_path, &context, &task.json_output, &summary.stamp),
+            PipelineStep::ExportBadge => export_badge_file(&context, &task.badge_output, &summary.stamp),
+        };
+
+        if let Err(error) = result {
+            println!("Step {:?} failed : {}", step, error);
+            first_error.get_or_insert(error);
+        }
+    }
+
+    if let Some(max_duplications) = task.max_duplications {
+        summary.record_threshold("max_duplications", max_duplications);
+        let total = context.summary().total();
+        if total > max_duplications {
+            first_error.get_or_insert(Box::new(CliError::CheckFailed(format!(
+                "{} duplications, the maximum being {}",
+                total, max_duplications
+            ))));
+        }
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Index and duplications of the loaded translations, shared by the duplication commands
+struct DuplicationContext<'a> {
+    annotations: Annotations,
+    package_path_pattern: PackagePathPattern,
+    translations_by_package: HashMap<String, Vec<&'a Translation>>,
+    translations_indexed: HashMap<String, Vec<&'a Translation>>,
+    duplications_by_package: BTreeMap<String, Vec<DuplicationReport<'a>>>,
+    /// Duplications of each package left out because they are in the baseline
+    baseline_duplications_by_package: BTreeMap<String, usize>,
+}
+
+impl DuplicationContext<'_> {
+    fn summary(&self) -> DuplicationSummary {
+        let mut summary = DuplicationSummary::default();
+        for reports_duplication in self.duplications_by_package.values() {
+            summary.add(&DuplicationSummary::from_reports(reports_duplication));
+        }
+        summary
+    }
+
+    /// Duplication counts of the packages, the ones of the baseline included, and count of the new ones
+    fn duplication_counts(&self) -> DuplicationCounts {
+        let mut by_package = BTreeMap::new();
+        let mut new = 0;
+        for (package_path, duplications) in &self.duplications_by_package {
+            let count = DuplicationSummary::from_reports(duplications).total();
+            new += count;
+            by_package.insert(package_path.clone(), count + self.baseline_duplications_by_package.get(package_path).copied().unwrap_or(0));
+        }
+
+        DuplicationCounts { by_package, new }
+    }
+}
+
+fn analyse_duplication_context<'a>(monorepo_path: &Path, translations: &'a [Translation], config: &Settings, package_path: Option<&str>, summary: &mut RunSummary, progress: &dyn ProgressListener) -> Result<DuplicationContext<'a>, Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locale_matcher = LocaleMatcher::compile(config)?;
+    let allowed_duplications = compile_allowed_duplications(monorepo_path, config)?;
+    let dependencies = get_package_dependencies(monorepo_path, translations, &package_path_pattern)?;
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+    let test_fixtures = TestFixtures::compile(monorepo_path, &config.test_fixtures)?;
+    let index_span = trace_span("index", "index", json!({ "translations": translations.len() }));
+    let translations_indexed = map_translations_by_translation(translations, &config.normalization, &config.duplication_filters);
+    drop(index_span);
+    let mut duplications_by_package = analyse_packages_duplication(translations, &translations_indexed, package_path, &config.normalization, &allowed_duplications, &dependencies, &common_modules_path, &test_fixtures, &config.i18next_keys, &locale_matcher, &package_path_pattern, progress);
+    let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+    duplications_by_package.retain(|package_path, _| !overrides.is_disabled(package_path, PackageAnalysis::Duplication));
+    let triage_rules = TriageRules::compile(&config.triage_rules)?;
+    let auto_ignored = apply_triage_rules(&mut duplications_by_package, &translations_indexed, &config.normalization, &triage_rules, &package_path_pattern);
+    let baseline_duplications_by_package: BTreeMap<String, usize> = match load_duplication_baseline(monorepo_path, config)? {
+        Some(baseline) => duplications_by_package
+            .iter_mut()
+            .map(|(package_path, duplications)| (package_path.clone(), apply_duplication_baseline(monorepo_path, duplications, &baseline)))
+            .collect(),
+        None => BTreeMap::new(),
+    };
+
+    let translations_by_package = map_translations_by_project(translations, &package_path_pattern);
+    let annotations = get_annotations(&monorepo_path.join(&config.annotations_file_path))?;
+
+    let context = DuplicationContext { annotations, package_path_pattern, translations_by_package, translations_indexed, duplications_by_package, baseline_duplications_by_package };
+    summary.record_duplications(&context.summary());
+    summary.record_count("auto_ignored_duplications", auto_ignored);
+    summary.record_count("baseline_duplications", context.baseline_duplications_by_package.values().sum());
+
+    Ok(context)
+}
+
+/// Duplication baseline of the settings, `None` when it is disabled or was never created
+fn load_duplication_baseline(monorepo_path: &Path, config: &Settings) -> Result<Option<DuplicationBaseline>, DuplicationBaselineError> {
+    match &config.duplication_baseline_file_path {
+        Some(baseline_file_path) => get_duplication_baseline(&monorepo_path.join(baseline_file_path)),
+        None => Ok(None),
+    }
+}
+
+fn create_baseline(monorepo_path: &Path, mut config: Settings, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    // Every duplication is recorded, the ones of the previous baseline included
+    let baseline_file_path = config.duplication_baseline_file_path.take().unwrap_or_else(|| DEFAULT_DUPLICATION_BASELINE_FILE_PATH.to_string());
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let context = analyse_duplication_context(monorepo_path, &translations, &config, None, summary, &NoProgress)?;
+
+    let baseline = create_duplication_baseline(monorepo_path, &context.duplications_by_package);
+    let baseline_file_path = monorepo_path.join(baseline_file_path);
+    write_duplication_baseline(&baseline_file_path, &baseline)?;
+    println!("Duplication baseline of {} duplications written to {}", baseline.fingerprints.len(), baseline_file_path.to_string_lossy());
+
+    Ok(())
+}
+
+fn compile_allowed_duplications(monorepo_path: &Path, config: &Settings) -> Result<AllowedDuplications, Box<dyn Error + Sync + Send + 'static>> {
+    let allowlist = get_allowlist(&monorepo_path.join(&config.allowlist_file_path))?;
+
+    Ok(AllowedDuplications::compile(&config.allowed_duplications, &config.normalization)?.with_allowlist(&allowlist, &config.normalization))
+}
+
+/// Locales of the files of each package, with the locales every package is expected to have
+struct PackageLocales {
+    by_package: BTreeMap<String, BTreeSet<String>>,
+    /// The `locales` of the settings, or else every locale found
+    expected: BTreeSet<String>,
+}
+
+impl PackageLocales {
+    fn of(&self, package_path: &str) -> &BTreeSet<String> {
+        static NONE: BTreeSet<String> = BTreeSet::new();
+        self.by_package.get(package_path).unwrap_or(&NONE)
+    }
+}
+
+fn find_package_locales(monorepo_path: &Path, config: &Settings, overrides: &SettingsOverrides, package_path_pattern: &PackagePathPattern) -> Result<PackageLocales, Box<dyn Error + Sync + Send + 'static>> {
+    let override_regex: fn(&SettingsOverride) -> Option<&String> = |settings_override| settings_override.locales_file_regex.as_ref();
+    let file_patterns = overrides.file_patterns(&config.locale_files_pattern(), override_regex);
+    let locale_files = overrides.select_files(search_recursive_patterns(monorepo_path, monorepo_path, &file_patterns, &config.skip_directories)?, override_regex);
+    // The files holding several locales tell them only by their translations
+    let options = translations_load_options(&config.locale_files_pattern(), override_regex, config, None)?;
+    let translations = load_translations_with_progress(locale_files.clone(), &options, &NoProgress)?.translations;
+    let override_regexes: Vec<Regex> = config.overrides.iter()
+        .filter_map(|settings_override| settings_override.locales_file_regex.as_deref())
+        .map(Regex::new)
+        .collect::<Result<_, _>>()?;
+    let locales_by_package = locales_by_package(&locale_files, &translations, &LocaleMatcher::compile(config)?, &override_regexes, package_path_pattern);
+    let expected_locales: BTreeSet<String> = if config.locales.is_empty() {
+        locales_by_package.values().flatten().cloned().collect()
+    } else {
+        config.locales.names().into_iter().collect()
+    };
+
+    Ok(PackageLocales { by_package: locales_by_package, expected: expected_locales })
+}
+
+/// Health of every analysed package, except the ones whose settings override disables it
+fn analyse_packages_health(monorepo_path: &Path, config: &Settings, context: &DuplicationContext) -> Result<Vec<PackageHealth>, Box<dyn Error + Sync + Send + 'static>> {
+    let overrides = SettingsOverrides::compile(&config.overrides, &context.package_path_pattern)?;
+    let package_locales = find_package_locales(monorepo_path, config, &overrides, &context.package_path_pattern)?;
+
+    let mut healths = Vec::new();
+    for (package_path, reports_duplication) in &context.duplications_by_package {
+        if overrides.is_disabled(package_path, PackageAnalysis::Health) {
+            continue;
+        }
+        healths.push(analyse_package_health(
+            context.translations_by_package.get(package_path).map(Vec::as_slice).unwrap_or_default(),
+            reports_duplication,
+            &config.key_rules,
+            package_locales.of(package_path),
+            &package_locales.expected,
+        )?);
+    }
+
+    Ok(healths)
+}
+
+fn print_packages_global_report(monorepo_path: &Path, config: &Settings, context: &DuplicationContext) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let overrides = SettingsOverrides::compile(&config.overrides, &context.package_path_pattern)?;
+    let package_locales = find_package_locales(monorepo_path, config, &overrides, &context.package_path_pattern)?;
+
+    for (package_path, reports_duplication) in &context.duplications_by_package {
+        println!("Analyse project : {}", package_path);
+        print_global_duplication_report(reports_duplication);
+        if overrides.is_disabled(package_path, PackageAnalysis::Health) {
+            continue;
+        }
+
+        let health = analyse_package_health(
+            context.translations_by_package.get(package_path).map(Vec::as_slice).unwrap_or_default(),
+            reports_duplication,
+            &config.key_rules,
+            package_locales.of(package_path),
+            &package_locales.expected,
+        )?;
+        print_package_health(&health);
+    }
+
+    Ok(())
+}
+
+fn print_suggestions(monorepo_path: &Path, config: &Settings, context: &DuplicationContext, provenance: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let common_modules_path = config.common_modules_paths(monorepo_path);
+    let reports_duplication: Vec<&DuplicationReport> = context.duplications_by_package.values().flatten().collect();
+
+    let mut suggestions = suggest_common_translations(&reports_duplication, &context.translations_indexed, &config.normalization, &common_modules_path, &context.package_path_pattern);
+    let history = if provenance { ValueHistory::open(monorepo_path)? } else { None };
+    if provenance && history.is_none() {
+        println!("Warning : {} has no git history, the origin of the values is unknown", monorepo_path.to_string_lossy());
+    }
+    if let Some(history) = history {
+        // Values already in common translations keep their common key, no need to trace them
+        for suggestion in suggestions.iter_mut().filter(|suggestion| suggestion.existing_common_translation.is_none()) {
+            let occurrences = context.translations_indexed.get(&suggestion.value.normalized(&config.normalization)).map(Vec::as_slice).unwrap_or_default();
+            suggestion.origin = history.find_value_origin(occurrences, &context.package_path_pattern)?;
+        }
+    }
+    print_common_translations_suggestions(&suggestions, &context.annotations, &context.package_path_pattern);
+
+    Ok(())
+}
+
+fn export_badge_file(context: &DuplicationContext, output: &Path, stamp: &RunStamp) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let summary = context.summary();
+
+    let badge = render_badge("duplicated translations", &summary.total().to_string(), badge_color_for_duplications(summary.total()));
+    write_badge(output, &stamp_badge(&badge, stamp))?;
+    println!("Badge written to {}", output.to_string_lossy());
+
+    Ok(())
+}
+
+fn upgrade_report(input: &Path, output: &Path) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let report = read_json_report(input)?;
+
+    write_json_report(output, &report)?;
+    println!("Report written to {} (schema version {})", output.to_string_lossy(), JSON_REPORT_SCHEMA_VERSION);
+
+    Ok(())
+}
+
+fn list_packages_report(monorepo_path: &Path, config: Settings) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let translations = load_all_translations(monorepo_path, &config)?;
+    print_packages_listing(&list_packages(&translations, &package_path_pattern));
+
+    Ok(())
+}
+
+fn init(monorepo_path: &Path, config: Settings, yes: bool, force: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let detected = detect_settings(monorepo_path, &config.skip_directories)?;
+    println!("Found {} translation files", detected.translation_files.len());
+    for sample_file in detected.sample_files() {
+        println!("  {}", sample_file);
+    }
+    if !detected.locales.is_empty() {
+        println!("Locales : {}", detected.locales.iter().cloned().collect::<Vec<_>>().join(", "));
+    }
+
+    let ask = |question: &str, default: String| ask_setting(question, default, yes);
+    let locales_file_regex = ask("Regex of the translation files of every locale", detected.locales_file_regex.clone().unwrap_or(config.locales_file_regex))?;
+    let reference_locale = ask("Reference locale", detected.reference_locale.clone().unwrap_or(config.reference_locale))?;
+    let translation_file_regex = ask(
+        "Regex of the translation files of the reference locale",
+        reference_file_regex(&locales_file_regex, &reference_locale).unwrap_or(config.translation_file_regex),
+    )?;
+    let package_path_regex = ask("Regex of the package paths", detected.package_path_regex.clone().unwrap_or(config.package_path_regex))?;
+    let common_modules = if detected.common_translations_modules_path.is_empty() { config.common_translations_modules_path } else { detected.common_translations_modules_path };
+    let common_modules = ask("Common translations modules, comma separated", common_modules.join(", "))?;
+    let common_modules: Vec<String> = common_modules.split(',').map(str::trim).filter(|path| !path.is_empty()).map(str::to_string).collect();
+
+    for (name, pattern) in [("translation files", &translation_file_regex), ("locale files", &locales_file_regex), ("package paths", &package_path_regex)] {
+        if let Err(error) = Regex::new(pattern) {
+            println!("Warning : invalid regex for the {}, {}", name, error);
+        }
+    }
+
+    let settings = starter_settings(&translation_file_regex, &locales_file_regex, &reference_locale, &package_path_regex, &common_modules);
+    let settings_file_path = monorepo_path.join(settings::SETTINGS_FILE_NAMES[0]);
+    write_starter_settings(&settings_file_path, &settings, force)?;
+    println!("Settings written to {}", settings_file_path.to_string_lossy());
+
+    Ok(())
+}
+
+/// Asks for a setting on the standard input, an empty answer or the end of the input keeping the default
+fn ask_setting(question: &str, default: String, yes: bool) -> io::Result<String> {
+    print!("{} [{}] : ", question, default);
+    if yes {
+        println!();
+        return Ok(default);
+    }
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+
+    Ok(if answer.is_empty() { default } else { answer.to_string() })
+}
+
+fn settings_schema(output: &Path) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    check_settings_schema()?;
+    write_json_report(output, &settings_json_schema())?;
+    println!("Settings schema written to {}", output.to_string_lossy());
+
+    Ok(())
+}
+
+/// Fails when a setting has no documented schema property or a property no setting, as `settings_schema_drift`
+fn check_settings_schema() -> Result<(), CliError> {
+    let drift = settings_schema_drift();
+    if drift.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::SchemaDrift(drift.join(", ")))
+}
+
+fn self_test(config: Settings, fixtures_path: &Path, update: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    // The build is checked along with the fixtures, the schema being generated from it
+    check_settings_schema()?;
+    let fixtures = find_self_test_fixtures(fixtures_path)?;
+    let stamp = RunStamp { run_id: "self-test".to_string(), generated_at: None };
+    let mut failed: Vec<&str> = Vec::new();
+
+    for fixture in &fixtures {
+        println!("Self-test fixture : {}", fixture.name);
+        let fixture_config = match &fixture.settings_file_path {
+            Some(settings_file_path) => settings::get_settings(settings_file_path)?,
+            None => config.clone(),
+        };
+        let translations = load_all_translations(&fixture.root_path, &fixture_config)?;
+        let mut fixture_summary = RunSummary::start("self-test", stamp.clone());
+        let context = analyse_duplication_context(&fixture.root_path, &translations, &fixture_config, None, &mut fixture_summary, &NoProgress)?;
+        let report = normalize_report(serde_json::to_value(build_json_report(&fixture.root_path, &context.duplications_by_package, &context.annotations, &stamp))?);
+        let analyses = self_test_analyses(&fixture.root_path, &fixture_config, &translations)?;
+
+        type ReadExpected = fn(&Path) -> Result<Value, Box<dyn Error + Sync + Send + 'static>>;
+        let expected_files: [(&Path, Value, ReadExpected); 2] = [
+            (&fixture.expected_report_path, report, |path| Ok(normalize_report(read_json_report(path)?))),
+            (&fixture.expected_analyses_path, analyses, |path| Ok(read_expected_analyses(path)?)),
+        ];
+        if update {
+            for (expected_path, actual, _) in &expected_files {
+                write_json_report(expected_path, actual)?;
+                println!("Expected results written to {}", expected_path.to_string_lossy());
+            }
+            continue;
+        }
+
+        let mut fixture_failed = false;
+        for (expected_path, actual, read_expected) in &expected_files {
+            let file_name = expected_path.file_name().unwrap_or_default().to_string_lossy();
+            if !expected_path.is_file() {
+                println!("FAILED : no {}, run with --update to write it", file_name);
+                fixture_failed = true;
+                continue;
+            }
+
+            if let Some(diff) = diff_reports(&read_expected(expected_path)?, actual) {
+                println!("FAILED : the results differ from {} (- expected, + actual)", file_name);
+                print!("{}", diff);
+                fixture_failed = true;
+            }
+        }
+        if fixture_failed {
+            failed.push(&fixture.name);
+        } else {
+            println!("OK");
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(Box::new(CliError::CheckFailed(format!("{} of {} fixtures failed : {}", failed.len(), fixtures.len(), failed.join(", ")))));
+    }
+    println!("{} fixtures passed", fixtures.len());
+
+    Ok(())
+}
+
+/// Results of the analyses other than the duplications on a fixture, the key counts and lint being of the reference
+/// locale as their commands
+fn self_test_analyses(root_path: &Path, config: &Settings, translations: &[Translation]) -> Result<Value, Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locales_translations = load_all_locales_translations(root_path, config)?;
+    let locales_translations: Vec<&Translation> = locales_translations.iter().collect();
+    let untranslated = analyse_untranslated(&locales_translations, &LocaleMatcher::compile(config)?, &config.reference_locale, &config.locales.names(), &package_path_pattern);
+    let reference_translations: Vec<&Translation> = translations.iter().collect();
+    let key_lint = lint_keys(&reference_translations, &config.key_rules)?;
+
+    Ok(build_analyses_report(root_path, &count_keys_by_package(translations, &package_path_pattern), &untranslated, &key_lint))
+}
+
+fn export_json_file(monorepo_path: &Path, context: &DuplicationContext, output: &Path, stamp: &RunStamp) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let report = build_json_report(monorepo_path, &context.duplications_by_package, &context.annotations, stamp);
+    write_json_report(output, &report)?;
+    println!("Report written to {}", output.to_string_lossy());
+
+    Ok(())
+}
+
+fn key_counts(monorepo_path: &Path, config: Settings, update_baseline: bool, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let baseline_file_path = monorepo_path.join(&config.key_count_limits.baseline_file_path);
+    let translations = load_all_translations(monorepo_path, &config)?;
+    let counts = count_keys_by_package(&translations, &package_path_pattern);
+
+    if update_baseline {
+        write_key_counts_baseline(&baseline_file_path, &counts)?;
+        println!("Baseline written to {}", baseline_file_path.to_string_lossy());
+        return Ok(());
+    }
+
+    let baseline = get_key_counts_baseline(&baseline_file_path)?;
+    let alerts = check_key_counts(&counts, &baseline, &config.key_count_limits);
+    print_key_counts_report(&counts, &baseline, &alerts);
+
+    summary.record_count("total_keys", counts.values().sum());
+    summary.record_count("key_count_alerts", alerts.len());
+    if !alerts.is_empty() {
+        return Err(Box::new(CliError::CheckFailed(format!("{} key count alerts", alerts.len()))));
+    }
+
+    Ok(())
+}
+
+fn check_deprecated_keys(monorepo_path: &Path, config: &Settings, summary: &mut RunSummary) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let deprecations = get_deprecations(&monorepo_path.join(&config.deprecations_file_path))?;
+
+    let source_files = find_source_files(monorepo_path, &config.source_file_extensions, &config.skip_directories)?;
+    let literals = scan_string_literals(&source_files);
+
+    let usages = analyse_deprecated_keys(&deprecations, &literals, IsoDate::today());
+    print_deprecated_keys_report(monorepo_path, &usages);
+
+    let expired_usages = usages.iter().filter(|usage| usage.expired).count();
+    summary.record_count("deprecated_key_usages", usages.len());
+    summary.record_count("expired_deprecated_key_usages", expired_usages);
+    if expired_usages > 0 {
+        return Err(Box::new(CliError::CheckFailed(format!(
+            "{} usages of deprecated keys after their deadline",
+            expired_usages
+        ))));
+    }
+
+    Ok(())
+}
+
+fn split_file(monorepo_path: &Path, config: Settings, file: &Path, mappings: &[(String, PathBuf)], dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let mut session = WriteSession::new(monorepo_path, dry_run);
+
+    split_translation_file(file, mappings, &config.nested_key_separator, &mut session)?;
+
+    let count = session.commit()?;
+    println!("{} files changed", count);
+
+    Ok(())
+}
+
+fn merge_files(monorepo_path: &Path, config: Settings, package_path: &str, target_folder: &Path, target_name: Option<&str>, dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let locale_matcher = LocaleMatcher::compile(&config)?;
+    let package_folder = monorepo_path.join(package_path);
+    let matches = search_recursive_pattern(
+        monorepo_path,
+        &package_folder,
+        &config.locale_files_pattern(),
+        &config.skip_directories
+    )?;
+    println!("Found {} files", matches.len());
+
+    let mut session = WriteSession::new(monorepo_path, dry_run);
+
+    merge_translation_files(&matches, &locale_matcher, &package_folder.join(target_folder), target_name, &config.nested_key_separator, &mut session)?;
+
+    let count = session.commit()?;
+    println!("{} files changed", count);
+
+    Ok(())
+}
+
+fn rename_keys(monorepo_path: &Path, config: Settings, mapping: &Path, package_path: Option<&str>, sources: bool, dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let renames = parse_rename_mapping(mapping)?;
+    let folder = package_path.map_or(monorepo_path.to_path_buf(), |package_path| monorepo_path.join(package_path));
+    let translation_files = search_recursive_pattern(monorepo_path, &folder, &config.locale_files_pattern(), &config.skip_directories)?;
+
+    let mut session = WriteSession::new(monorepo_path, dry_run);
+
+    let summary = rename_translation_keys(&translation_files, &renames, &config.nested_key_separator, &mut session)?;
+    println!("{} keys renamed in {} translation files", summary.renamed_keys, summary.changed_files);
+    for missing_key in &summary.missing_keys {
+        println!("Warning : key {} not found", missing_key);
+    }
+
+    if sources {
+        let source_files = find_source_files(&folder, &config.source_file_extensions, &config.skip_directories)?;
+        let changed_files = rename_source_references(&source_files, &renames, &mut session);
+        println!("References renamed in {} source files", changed_files);
+    }
+
+    let count = session.commit()?;
+    println!("{} files changed", count);
+
+    Ok(())
+}
+
+fn scaffold(monorepo_path: &Path, config: Settings, package_path: &str, dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let package_path_pattern = config.package_path_pattern()?;
+    let locales: BTreeSet<String> = if config.locales.is_empty() {
+        let overrides = SettingsOverrides::compile(&config.overrides, &package_path_pattern)?;
+        find_package_locales(monorepo_path, &config, &overrides, &package_path_pattern)?.expected
+    } else {
+        config.locales.names().into_iter().collect()
+    };
+
+    let mut session = WriteSession::new(monorepo_path, dry_run);
+
+    scaffold_package(&monorepo_path.join(package_path), &locales, &config.scaffold, &config.key_rules.segment_separator, &mut session)?;
+
+    let count = session.commit()?;
+    println!("{} files changed", count);
+
+    Ok(())
+}
+
+fn move_keys(monorepo_path: &Path, config: Settings, from: &str, to: &str, selection: &KeySelection, deprecation_note: bool, dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let find_package_files = |package_path: &str| -> Result<PackageFiles, Box<dyn Error + Sync + Send + 'static>> {
+        let folder = monorepo_path.join(package_path);
+        let files = search_recursive_pattern(monorepo_path, &folder, &config.locale_files_pattern(), &config.skip_directories)?;
+        Ok(PackageFiles { path: package_path.to_string(), folder, files })
+    };
+
+    let source = find_package_files(from)?;
+    let target = find_package_files(to)?;
+
+    let mut session = WriteSession::new(monorepo_path, dry_run);
+
+    move_translation_keys(&source, &target, selection, &config.nested_key_separator, deprecation_note, &mut session)?;
 
-    println!("\n\n");
+    let count = session.commit()?;
+    println!("{} files changed", count);
 
     Ok(())
 }
 
-fn add_star_if_own_package(package_path: &str, translations_path: &str) -> String {
-    if get_package_path(translations_path) == package_path {
+fn add_star_if_own_package(package_path: &str, translations_path: &str, package_path_pattern: &PackagePathPattern) -> String {
+    if package_path_pattern.package_path(translations_path) == package_path {
         return "**".to_string()
     }
     
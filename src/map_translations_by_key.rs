@@ -1,19 +1,65 @@
-use std::collections::HashMap;
-use crate::load_translations::Translation;
+use std::collections::{HashMap, HashSet};
+use crate::entities::{Translation, TranslationValue};
+use crate::settings::{DuplicationFilters, ValueNormalization};
 
-/// Recursively searches for regex matches in all files within a path
-/// Returns a vector of tuples: (file_path, line_number, matched_text)
-pub fn map_translations_by_translation(
-    translation: &[Translation],
-) -> HashMap<String, Vec<&Translation>> {
+/// Indexes translations by their normalized value, leaving out the string values rejected by the filters
+pub fn map_translations_by_translation<'a>(
+    translation: &'a [Translation],
+    normalization: &ValueNormalization,
+    filters: &DuplicationFilters,
+) -> HashMap<String, Vec<&'a Translation>> {
     let mut hashmap: HashMap<String, Vec<&Translation>> = HashMap::new();
 
-    translation.iter().for_each(|translation| {
+    for (position, normalized_value) in indexed_values(translation, normalization, filters) {
+        hashmap
+            .entry(normalized_value)
+            .or_default()
+            .push(&translation[position]);
+    }
+
+    hashmap
+}
+
+/// Same index as `map_translations_by_translation` holding positions in `translation`, for owners of the
+/// translations which cannot keep references to them
+pub fn map_translation_positions_by_translation(
+    translation: &[Translation],
+    normalization: &ValueNormalization,
+    filters: &DuplicationFilters,
+) -> HashMap<String, Vec<usize>> {
+    let mut hashmap: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (position, normalized_value) in indexed_values(translation, normalization, filters) {
         hashmap
-            .entry(translation.translations.clone())
-            .or_insert_with(Vec::new)
-            .push(translation);
-    });
+            .entry(normalized_value)
+            .or_default()
+            .push(position);
+    }
 
     hashmap
-}
\ No newline at end of file
+}
+
+fn indexed_values(
+    translation: &[Translation],
+    normalization: &ValueNormalization,
+    filters: &DuplicationFilters,
+) -> Vec<(usize, String)> {
+    let ignored_values: HashSet<String> = filters
+        .ignored_values
+        .iter()
+        .map(|value| TranslationValue::String(value.clone()).normalized(normalization))
+        .collect();
+
+    translation
+        .iter()
+        .enumerate()
+        .filter_map(|(position, translation)| {
+            let normalized_value = translation.translations.normalized(normalization);
+            let filtered = ignored_values.contains(&normalized_value)
+                || translation.text().is_some_and(|text| {
+                    text.trim().chars().count() < filters.min_characters || text.split_whitespace().count() < filters.min_words
+                });
+            (!filtered).then_some((position, normalized_value))
+        })
+        .collect()
+}
@@ -5,34 +5,55 @@ use once_cell::sync::Lazy;
 use crate::entities::PackageType;
 use crate::load_translations::Translation;
 
-pub fn map_translations_by_project(
-    translation: &[Translation],
-) -> HashMap<String, Vec<&Translation>> {
-    let mut hashmap: HashMap<String, Vec<&Translation>> = HashMap::new();
+pub fn map_translations_by_project<'a>(
+    translation: &'a [Translation],
+    package_path_pattern: &PackagePathPattern,
+) -> HashMap<String, Vec<&'a Translation>> {
+    let mut hashmap: HashMap<String, Vec<&'a Translation>> = HashMap::new();
 
     translation.iter().for_each(|translation| {
         hashmap
-            .entry(get_package_path(translation.path.to_str().unwrap()))
-            .or_insert_with(Vec::new)
+            .entry(package_path_pattern.package_path(&translation.path.to_string_lossy()))
+            .or_default()
             .push(translation);
     });
 
     hashmap
 }
 
-static PROJECT_PATH_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(packages/manager/(apps|modules)/[^/]+)").unwrap()
-});
+/// Package paths of the OVHcloud manager monorepo, as `packages/manager/apps/zimbra`
+pub const DEFAULT_PACKAGE_PATH_REGEX: &str = r"packages/manager/(?P<type>apps|modules)/(?P<name>[^/]+)";
 
-pub(crate) fn determinate_project_path_and_type(path: &str) -> Option<(PackageType, String)> {
-    if let Some(caps) = PROJECT_PATH_REGEX.captures(path) {
-        let identifier = caps.get(1)?.as_str().to_string();
-        let pkg_type = PackageType::from_str(caps.get(2)?.as_str()).unwrap_or(PackageType::Modules);
-        return Some((pkg_type, identifier));
+static DEFAULT_PACKAGE_PATH_PATTERN: Lazy<PackagePathPattern> = Lazy::new(|| PackagePathPattern(Regex::new(DEFAULT_PACKAGE_PATH_REGEX).unwrap()));
+
+/// Regex finding the package of a path, compiled once from the `package_path_regex` setting so other monorepo
+/// layouts are supported
+/// The package path ends with the `name` group, the whole match being used without it, and the `type` group tells
+/// the package type, modules being assumed without it
+#[derive(Clone)]
+pub struct PackagePathPattern(Regex);
+
+impl Default for PackagePathPattern {
+    fn default() -> Self {
+        DEFAULT_PACKAGE_PATH_PATTERN.clone()
     }
-    None
 }
 
-pub(crate) fn get_package_path(path: &str) -> String {
-    determinate_project_path_and_type(path).map_or_else(|| "unknown".to_string(), |package| package.1)
+impl PackagePathPattern {
+    pub fn compile(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self(Regex::new(pattern)?))
+    }
+
+    pub fn project_path_and_type(&self, path: &str) -> Option<(PackageType, String)> {
+        let caps = self.0.captures(path)?;
+        let whole = caps.get(0)?;
+        let end = caps.name("name").map_or(whole.end(), |name| name.end());
+        let identifier = path[whole.start()..end].to_string();
+        let pkg_type = caps.name("type").and_then(|package_type| PackageType::from_str(package_type.as_str()).ok()).unwrap_or(PackageType::Modules);
+        Some((pkg_type, identifier))
+    }
+
+    pub fn package_path(&self, path: &str) -> String {
+        self.project_path_and_type(path).map_or_else(|| "unknown".to_string(), |package| package.1)
+    }
 }
@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use crate::glob_pattern::LOCALE_PLACEHOLDER;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::locales::LocaleMatcher;
+use crate::safe_write::WriteSession;
+use crate::translation_file::{read_flattened_entries, serialize_flattened_entries, TranslationEntries};
+
+#[derive(Error, Debug)]
+pub enum MergeTranslationFilesError {
+    #[error("Key {0} has different values in {1} and {2}")]
+    KeyConflict(String, String, String),
+
+    #[error("Files of {0} have different names, {1}, a target name is needed")]
+    AmbiguousTargetName(String, String),
+
+    #[error("Target name {0} has no {1} placeholder, the files of {2} locales would be merged into it")]
+    TargetNameWithoutLocale(String, &'static str, usize),
+
+    #[error(transparent)]
+    Load(#[from] LoadTranslationsFilesError),
+}
+
+/// Merges the translation files of a package into one file per locale in `target_folder`
+/// The files of a locale are merged into `target_name` with its `{locale}` placeholder replaced, or into their
+/// file name when they all share it, files without locale being grouped by file name
+/// All conflicts are reported before failing so they can be fixed in one pass
+/// Keys of nested objects are merged by their path joined with `nested_key_separator`, so files sharing a top-level
+/// object are merged into it, the merged file being nested when any of its files is
+pub fn merge_translation_files(
+    translation_files: &[PathBuf],
+    locale_matcher: &LocaleMatcher,
+    target_folder: &Path,
+    target_name: Option<&str>,
+    nested_key_separator: &str,
+    session: &mut WriteSession,
+) -> Result<(), MergeTranslationFilesError> {
+    let mut files_by_locale: BTreeMap<(Option<String>, String), Vec<&PathBuf>> = BTreeMap::new();
+    for file in translation_files {
+        let Some(file_name) = file.file_name().map(|file_name| file_name.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let group = match locale_matcher.locale(file) {
+            Some(locale) => (Some(locale), String::new()),
+            None => (None, file_name),
+        };
+        files_by_locale.entry(group).or_default().push(file);
+    }
+
+    if let Some(target_name) = target_name
+        && !target_name.contains(LOCALE_PLACEHOLDER)
+        && files_by_locale.len() > 1
+    {
+        return Err(MergeTranslationFilesError::TargetNameWithoutLocale(target_name.to_string(), LOCALE_PLACEHOLDER, files_by_locale.len()));
+    }
+
+    let mut conflicts: Vec<MergeTranslationFilesError> = Vec::new();
+    let mut merged_files: Vec<(PathBuf, TranslationEntries, bool, Vec<&PathBuf>)> = Vec::new();
+
+    for ((locale, file_name), mut files) in files_by_locale {
+        files.sort();
+        let file_names: BTreeSet<String> = files.iter().filter_map(|file| file.file_name()).map(|name| name.to_string_lossy().to_string()).collect();
+        let target_file_name = match (target_name, &locale) {
+            (Some(target_name), Some(locale)) => target_name.replace(LOCALE_PLACEHOLDER, locale),
+            (Some(target_name), None) => target_name.to_string(),
+            (None, None) => file_name,
+            (None, Some(locale)) if file_names.len() > 1 => {
+                let file_names = file_names.into_iter().collect::<Vec<String>>().join(", ");
+                return Err(MergeTranslationFilesError::AmbiguousTargetName(locale.clone(), file_names));
+            }
+            (None, Some(_)) => file_names.into_iter().next().unwrap_or_default(),
+        };
+        let target_path = target_folder.join(target_file_name);
+
+        let mut merged = TranslationEntries::default();
+        let mut origins: BTreeMap<String, &PathBuf> = BTreeMap::new();
+        let mut nested = false;
+
+        for file in &files {
+            let (entries, file_nested) = read_flattened_entries(file, nested_key_separator)?;
+            nested |= file_nested;
+            for (key, value) in entries.0 {
+                match merged.get(&key) {
+                    Some(existing) if *existing == value => continue,
+                    Some(_) => conflicts.push(MergeTranslationFilesError::KeyConflict(
+                        key.clone(),
+                        origins[&key].to_string_lossy().to_string(),
+                        file.to_string_lossy().to_string(),
+                    )),
+                    None => {
+                        origins.insert(key.clone(), file);
+                        merged.0.push((key, value));
+                    }
+                }
+            }
+        }
+
+        merged_files.push((target_path, merged, nested, files));
+    }
+
+    for conflict in &conflicts {
+        println!("Conflict : {}", conflict);
+    }
+    if let Some(conflict) = conflicts.into_iter().next() {
+        return Err(conflict);
+    }
+
+    for (target_path, merged, nested, files) in merged_files {
+        println!("{} files merged into {} ({} keys)", files.len(), target_path.to_string_lossy(), merged.0.len());
+
+        for file in files.into_iter().filter(|file| **file != target_path) {
+            session.remove(file);
+        }
+        session.write(&target_path, serialize_flattened_entries(merged, nested, nested_key_separator));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::safe_write::WriteSession;
+use crate::translation_file::{read_flattened_entries, serialize_flattened_entries, TranslationEntries};
+
+const MOVED_KEYS_NOTE_FILE: &str = "translations-moved.json";
+const DEFAULT_TRANSLATIONS_FOLDER: &str = "translations";
+
+#[derive(Error, Debug)]
+pub enum MoveTranslationKeysError {
+    #[error("Key {0} already exists in {1} with a different value")]
+    KeyConflict(String, String),
+
+    #[error("No key to move from {0}")]
+    NothingToMove(String),
+
+    #[error("Unable to read the moved keys note {0}")]
+    UnableToReadNote(String, #[source] std::io::Error),
+
+    #[error("Invalid moved keys note {0}, {1}")]
+    InvalidNote(String, String),
+
+    #[error(transparent)]
+    Load(#[from] LoadTranslationsFilesError),
+}
+
+/// Keys to move, given explicitly or by prefix
+pub struct KeySelection {
+    pub keys: Vec<String>,
+    pub prefixes: Vec<String>,
+}
+
+impl KeySelection {
+    pub fn matches(&self, key: &str) -> bool {
+        self.keys.iter().any(|selected| selected == key)
+            || self.prefixes.iter().any(|prefix| key.starts_with(prefix.as_str()))
+    }
+}
+
+/// Translation files of a package, `path` being the package path relative to the monorepo
+pub struct PackageFiles {
+    pub path: String,
+    pub folder: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MovedKeyNote {
+    key: String,
+    moved_to: String,
+}
+
+/// Moves the selected keys of every locale file of the source package into the target package
+/// Each locale goes to the target file with the same name, created under `translations` when missing
+/// Keys of nested objects are selected by their path joined with `nested_key_separator`, as `form.save`, and the
+/// files are written back nested, a created target file being nested as its source
+pub fn move_translation_keys(
+    source: &PackageFiles,
+    target: &PackageFiles,
+    selection: &KeySelection,
+    nested_key_separator: &str,
+    write_note: bool,
+    session: &mut WriteSession,
+) -> Result<(), MoveTranslationKeysError> {
+    let mut moved_by_file_name: BTreeMap<String, (TranslationEntries, bool)> = BTreeMap::new();
+
+    for source_file in &source.files {
+        let (entries, nested) = read_flattened_entries(source_file, nested_key_separator)?;
+        let (moved, kept): (Vec<_>, Vec<_>) = entries.0.into_iter().partition(|(key, _)| selection.matches(key));
+        if moved.is_empty() {
+            continue;
+        }
+
+        let file_name = source_file.file_name().unwrap_or_default().to_string_lossy().to_string();
+        println!("{} keys moved from {}", moved.len(), source_file.to_string_lossy());
+        let (moved_entries, moved_nested) = moved_by_file_name.entry(file_name).or_default();
+        moved_entries.0.extend(moved);
+        *moved_nested |= nested;
+        session.write(source_file, serialize_flattened_entries(TranslationEntries(kept), nested, nested_key_separator));
+    }
+
+    if moved_by_file_name.is_empty() {
+        return Err(MoveTranslationKeysError::NothingToMove(source.path.clone()));
+    }
+
+    let mut moved_keys: BTreeSet<String> = BTreeSet::new();
+
+    for (file_name, (moved, source_nested)) in moved_by_file_name {
+        let target_file = target
+            .files
+            .iter()
+            .filter(|file| file.file_name().is_some_and(|name| name.to_string_lossy() == file_name))
+            .min_by_key(|file| file.components().count())
+            .cloned()
+            .unwrap_or_else(|| target.folder.join(DEFAULT_TRANSLATIONS_FOLDER).join(&file_name));
+
+        let (mut target_entries, target_nested) = if target_file.exists() {
+            read_flattened_entries(&target_file, nested_key_separator)?
+        } else {
+            (TranslationEntries::default(), source_nested)
+        };
+
+        for (key, value) in moved.0 {
+            moved_keys.insert(key.clone());
+            match target_entries.get(&key) {
+                Some(existing) if *existing == value => continue,
+                Some(_) => {
+                    return Err(MoveTranslationKeysError::KeyConflict(key, target_file.to_string_lossy().to_string()));
+                }
+                None => target_entries.0.push((key, value)),
+            }
+        }
+
+        session.write(&target_file, serialize_flattened_entries(target_entries, target_nested, nested_key_separator));
+    }
+
+    if write_note {
+        // Keys moved by previous runs stay noted, a key moved again being noted at its new package
+        let note_file = source.folder.join(MOVED_KEYS_NOTE_FILE);
+        let mut moved_to: BTreeMap<String, String> = read_moved_keys_note(&note_file)?
+            .into_iter()
+            .map(|note| (note.key, note.moved_to))
+            .collect();
+        moved_to.extend(moved_keys.into_iter().map(|key| (key, target.path.clone())));
+
+        let notes: Vec<MovedKeyNote> = moved_to.into_iter().map(|(key, moved_to)| MovedKeyNote { key, moved_to }).collect();
+        let mut content = serde_json::to_string_pretty(&notes).unwrap();
+        content.push('\n');
+        session.write(&note_file, content);
+    }
+
+    Ok(())
+}
+
+/// Notes of the keys already moved out of a package, none when the note file does not exist
+fn read_moved_keys_note(note_file: &Path) -> Result<Vec<MovedKeyNote>, MoveTranslationKeysError> {
+    let display_path = note_file.to_string_lossy().to_string();
+    match fs::read_to_string(note_file) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| MoveTranslationKeysError::InvalidNote(display_path, e.to_string())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(MoveTranslationKeysError::UnableToReadNote(display_path, e)),
+    }
+}
@@ -0,0 +1,25 @@
+use crate::settings::ValueNormalization;
+
+const TRAILING_PUNCTUATION: [char; 8] = ['.', ',', ';', ':', '!', '?', '…', '\u{a0}'];
+
+/// Applies the configured normalization to the content of a string value
+pub fn normalize_text(text: &str, normalization: &ValueNormalization) -> String {
+    let mut text = text.to_string();
+
+    if normalization.collapse_whitespace {
+        text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    }
+    if normalization.trim {
+        text = text.trim().to_string();
+    }
+    if normalization.case_fold {
+        text = text.to_lowercase();
+    }
+    if normalization.strip_trailing_punctuation {
+        text = text
+            .trim_end_matches(|c: char| TRAILING_PUNCTUATION.contains(&c) || c.is_whitespace())
+            .to_string();
+    }
+
+    text
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use serde::Deserialize;
+use thiserror::Error;
+use crate::entities::Translation;
+use crate::map_translations_by_project::{map_translations_by_project, PackagePathPattern};
+
+const PACKAGE_MANIFEST: &str = "package.json";
+
+#[derive(Error, Debug)]
+pub enum PackageManifestError {
+    #[error("Unable to read package manifest: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid package manifest: {0}")]
+    InvalidFormat(String, #[source] serde_json::Error),
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PackageManifest {
+    name: Option<String>,
+    dependencies: BTreeMap<String, String>,
+    dev_dependencies: BTreeMap<String, String>,
+    peer_dependencies: BTreeMap<String, String>,
+}
+
+/// Workspace packages each package directly depends on, by package path
+#[derive(Default)]
+pub struct PackageDependencies(BTreeMap<String, BTreeSet<String>>);
+
+impl PackageDependencies {
+    pub fn depends_on(&self, package_path: &str, dependency_path: &str) -> bool {
+        self.0.get(package_path).is_some_and(|dependencies| dependencies.contains(dependency_path))
+    }
+}
+
+/// Reads the `package.json` of the packages holding translations, keeping the dependencies on one another
+/// Packages without manifest have no dependency and cannot be depended on
+pub fn get_package_dependencies(monorepo_path: &Path, translations: &[Translation], package_path_pattern: &PackagePathPattern) -> Result<PackageDependencies, PackageManifestError> {
+    let mut manifests: BTreeMap<String, PackageManifest> = BTreeMap::new();
+    for package_path in map_translations_by_project(translations, package_path_pattern).into_keys() {
+        if let Some(manifest) = get_package_manifest(&monorepo_path.join(&package_path).join(PACKAGE_MANIFEST))? {
+            manifests.insert(package_path, manifest);
+        }
+    }
+
+    let package_path_by_name: HashMap<&str, &str> = manifests
+        .iter()
+        .filter_map(|(package_path, manifest)| Some((manifest.name.as_deref()?, package_path.as_str())))
+        .collect();
+
+    Ok(PackageDependencies(
+        manifests
+            .iter()
+            .map(|(package_path, manifest)| {
+                let dependencies = manifest
+                    .dependencies
+                    .keys()
+                    .chain(manifest.dev_dependencies.keys())
+                    .chain(manifest.peer_dependencies.keys())
+                    .filter_map(|name| package_path_by_name.get(name.as_str()))
+                    .map(|dependency_path| dependency_path.to_string())
+                    .collect();
+                (package_path.clone(), dependencies)
+            })
+            .collect(),
+    ))
+}
+
+fn get_package_manifest(manifest_path: &Path) -> Result<Option<PackageManifest>, PackageManifestError> {
+    match fs::read_to_string(manifest_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| PackageManifestError::InvalidFormat(manifest_path.to_string_lossy().to_string(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(PackageManifestError::UnableToReadPath(manifest_path.to_string_lossy().to_string(), e)),
+    }
+}
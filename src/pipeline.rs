@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const DEFAULT_JSON_OUTPUT: &str = "translations-report.json";
+pub const DEFAULT_BADGE_OUTPUT: &str = "translations-duplication.svg";
+
+#[derive(Error, Debug)]
+pub enum PipelineError {
+    #[error("Empty pipeline, expected steps as `check + export-json`")]
+    Empty,
+
+    #[error("Unknown pipeline step `{0}`, available steps : {1}")]
+    UnknownStep(String, String),
+}
+
+/// A command of the `run` pipeline, every step sharing the same scan of the monorepo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineStep {
+    GlobalReport,
+    Suggest,
+    Check,
+    ExportJson,
+    ExportBadge,
+}
+
+const PIPELINE_STEPS: [(&str, PipelineStep); 5] = [
+    ("global-report", PipelineStep::GlobalReport),
+    ("suggest", PipelineStep::Suggest),
+    ("check", PipelineStep::Check),
+    ("export-json", PipelineStep::ExportJson),
+    ("export-badge", PipelineStep::ExportBadge),
+];
+
+/// Parses a pipeline description as `check + export-json + export-badge`, steps keeping their order
+pub fn parse_pipeline(description: &str) -> Result<Vec<PipelineStep>, PipelineError> {
+    let steps = description
+        .split('+')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            PIPELINE_STEPS
+                .iter()
+                .find(|(step_name, _)| *step_name == name)
+                .map(|(_, step)| *step)
+                .ok_or_else(|| {
+                    let available: Vec<&str> = PIPELINE_STEPS.iter().map(|(step_name, _)| *step_name).collect();
+                    PipelineError::UnknownStep(name.to_string(), available.join(", "))
+                })
+        })
+        .collect::<Result<Vec<PipelineStep>, PipelineError>>()?;
+
+    if steps.is_empty() {
+        return Err(PipelineError::Empty);
+    }
+
+    Ok(steps)
+}
+
+/// A pipeline with its filters, outputs and thresholds, as given to `run` or defined as a named task in the settings
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PipelineTask {
+    pub pipeline: String,
+    pub package_path: Option<String>,
+    pub json_output: PathBuf,
+    pub badge_output: PathBuf,
+    /// Fails the task when the total duplication count is above
+    pub max_duplications: Option<usize>,
+}
+
+impl Default for PipelineTask {
+    fn default() -> Self {
+        Self {
+            pipeline: String::new(),
+            package_path: None,
+            json_output: PathBuf::from(DEFAULT_JSON_OUTPUT),
+            badge_output: PathBuf::from(DEFAULT_BADGE_OUTPUT),
+            max_duplications: None,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use serde_json::Value;
+use crate::translation_file::TranslationEntries;
+
+/// Separates the msgctxt from the msgid in keys
+const CONTEXT_SEPARATOR: char = '|';
+
+#[derive(Default)]
+struct PoEntry {
+    context: Option<String>,
+    id: Option<String>,
+    plural_id: Option<String>,
+    values: Vec<(usize, String)>,
+}
+
+enum PoField {
+    Context,
+    Id,
+    PluralId,
+    Value(usize),
+}
+
+/// Parses a gettext catalog into entries keyed by msgid, prefixed by the msgctxt when there is one
+/// Plural forms are keyed `msgid[n]`. The header and untranslated entries are skipped, except in
+/// templates (`.pot`) where the msgid, being the source text, is used as value
+pub fn parse_po_entries(content: &str, template: bool) -> Result<TranslationEntries, String> {
+    let mut entries = TranslationEntries::default();
+    let mut entry = PoEntry::default();
+    let mut field: Option<PoField> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let line_error = |reason: &str| format!("line {} : {}", index + 1, reason);
+
+        // Comments, flags and obsolete `#~` entries are left out
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let text = unquote(line).ok_or_else(|| line_error("invalid string"))?;
+            match field {
+                Some(PoField::Context) => entry.context.get_or_insert_default().push_str(&text),
+                Some(PoField::Id) => entry.id.get_or_insert_default().push_str(&text),
+                Some(PoField::PluralId) => entry.plural_id.get_or_insert_default().push_str(&text),
+                Some(PoField::Value(form)) => match entry.values.iter_mut().find(|(value_form, _)| *value_form == form) {
+                    Some((_, value)) => value.push_str(&text),
+                    None => return Err(line_error("string outside of an entry")),
+                },
+                None => return Err(line_error("string outside of an entry")),
+            }
+            continue;
+        }
+
+        let (keyword, rest) = line.split_once(char::is_whitespace).ok_or_else(|| line_error("expected a keyword and a string"))?;
+        let text = unquote(rest.trim()).ok_or_else(|| line_error("invalid string"))?;
+
+        // A msgctxt or msgid after a msgstr starts the next entry
+        if matches!(keyword, "msgctxt" | "msgid") && entry.id.is_some() && !entry.values.is_empty() {
+            push_entry(&mut entries, std::mem::take(&mut entry), template);
+        }
+
+        field = Some(match keyword {
+            "msgctxt" => {
+                entry.context = Some(text);
+                PoField::Context
+            }
+            "msgid" => {
+                entry.id = Some(text);
+                PoField::Id
+            }
+            "msgid_plural" => {
+                entry.plural_id = Some(text);
+                PoField::PluralId
+            }
+            "msgstr" => {
+                entry.values.push((0, text));
+                PoField::Value(0)
+            }
+            _ => {
+                let form = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|form| form.strip_suffix(']'))
+                    .and_then(|form| form.parse::<usize>().ok())
+                    .ok_or_else(|| line_error(&format!("unknown keyword {}", keyword)))?;
+                entry.values.push((form, text));
+                PoField::Value(form)
+            }
+        });
+    }
+
+    if entry.id.is_some() {
+        push_entry(&mut entries, entry, template);
+    }
+
+    Ok(entries)
+}
+
+fn push_entry(entries: &mut TranslationEntries, entry: PoEntry, template: bool) {
+    let Some(id) = entry.id.filter(|id| !id.is_empty()) else {
+        return;
+    };
+    let key = match &entry.context {
+        Some(context) => format!("{}{}{}", context, CONTEXT_SEPARATOR, id),
+        None => id.clone(),
+    };
+
+    for (form, value) in entry.values {
+        let value = match (value.is_empty(), template) {
+            (false, _) => value,
+            (true, true) if form == 0 => id.clone(),
+            (true, true) => entry.plural_id.clone().unwrap_or_else(|| id.clone()),
+            (true, false) => continue,
+        };
+        let form_key = if entry.plural_id.is_some() { format!("{}[{}]", key, form) } else { key.clone() };
+        entries.0.push((form_key, Value::String(value)));
+    }
+}
+
+/// Content of a double quoted PO string, with its escape sequences decoded
+fn unquote(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => unquoted.push('\n'),
+            't' => unquoted.push('\t'),
+            'r' => unquoted.push('\r'),
+            escaped => unquoted.push(escaped),
+        }
+    }
+
+    Some(unquoted)
+}
@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Step reached by an analysis, streamed while it runs so embedders can display real progress
+#[derive(Clone, Debug)]
+pub enum ProgressEvent {
+    FilesDiscovered { count: usize },
+    /// Files are parsed in parallel, `parsed` counting the files done so far
+    FileParsed { path: PathBuf, parsed: usize, total: usize },
+    /// `findings` counts the duplications found so far in all the analysed packages
+    PackageAnalyzed { package_path: String, analyzed: usize, total: usize, findings: usize },
+}
+
+/// Receives the progress events of an analysis, possibly from several threads
+/// Implemented by closures and by channel senders, whose events are dropped once the receiver is gone
+pub trait ProgressListener: Sync {
+    fn on_progress(&self, event: ProgressEvent);
+}
+
+impl<F: Fn(ProgressEvent) + Sync> ProgressListener for F {
+    fn on_progress(&self, event: ProgressEvent) {
+        self(event)
+    }
+}
+
+impl ProgressListener for Sender<ProgressEvent> {
+    fn on_progress(&self, event: ProgressEvent) {
+        let _ = self.send(event);
+    }
+}
+
+/// Listener ignoring every event
+pub struct NoProgress;
+
+impl ProgressListener for NoProgress {
+    fn on_progress(&self, _event: ProgressEvent) {}
+}
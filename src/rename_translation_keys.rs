@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::safe_write::WriteSession;
+use crate::scan_source_files::{replace_string_literals, SourceFile};
+use crate::translation_file::{read_flattened_entries, serialize_flattened_entries};
+
+const MAPPING_HEADER: &str = "old,new";
+
+#[derive(Error, Debug)]
+pub enum RenameKeysError {
+    #[error("Unable to read rename mapping: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid rename mapping line {0} `{1}`, expected `old,new`")]
+    InvalidMapping(usize, String),
+
+    #[error("Key {0} is renamed several times in the mapping")]
+    DuplicateRename(String),
+
+    #[error("Key {0} already exists in {1}")]
+    TargetExists(String, String),
+
+    #[error(transparent)]
+    Load(#[from] LoadTranslationsFilesError),
+}
+
+/// Renames indexed by old key
+pub struct KeyRenames(pub BTreeMap<String, String>);
+
+/// Changes made by a rename
+#[derive(Default)]
+pub struct RenameSummary {
+    pub renamed_keys: usize,
+    pub changed_files: usize,
+    /// Keys of the mapping found in no translation file
+    pub missing_keys: BTreeSet<String>,
+}
+
+/// Reads a two columns `old,new` CSV file, with an optional `old,new` header line
+pub fn parse_rename_mapping(mapping_path: &Path) -> Result<KeyRenames, RenameKeysError> {
+    let content = fs::read_to_string(mapping_path)
+        .map_err(|e| RenameKeysError::UnableToReadPath(mapping_path.to_string_lossy().to_string(), e))?;
+
+    let mut renames: BTreeMap<String, String> = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (index == 0 && line.eq_ignore_ascii_case(MAPPING_HEADER)) {
+            continue;
+        }
+
+        let unquote = |field: &str| field.trim().trim_matches('"').to_string();
+        let (old, new) = match line.split_once(',') {
+            Some((old, new)) if !unquote(old).is_empty() && !unquote(new).is_empty() => (unquote(old), unquote(new)),
+            _ => return Err(RenameKeysError::InvalidMapping(index + 1, line.to_string())),
+        };
+        if renames.insert(old.clone(), new).is_some() {
+            return Err(RenameKeysError::DuplicateRename(old));
+        }
+    }
+
+    Ok(KeyRenames(renames))
+}
+
+/// Renames the keys in every translation file, keeping their position in the file
+/// A file already defining a new key is an error, unless that key is renamed too
+/// Keys of nested objects are renamed by their path joined with `nested_key_separator`, as `form.save`, which may
+/// move them to another nested object
+pub fn rename_translation_keys(
+    translation_files: &[PathBuf],
+    renames: &KeyRenames,
+    nested_key_separator: &str,
+    session: &mut WriteSession,
+) -> Result<RenameSummary, RenameKeysError> {
+    let mut summary = RenameSummary { missing_keys: renames.0.keys().cloned().collect(), ..RenameSummary::default() };
+
+    for path in translation_files {
+        let (mut entries, nested) = read_flattened_entries(path, nested_key_separator)?;
+        let mut final_keys: BTreeSet<&str> = entries
+            .0
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .filter(|key| !renames.0.contains_key(*key))
+            .collect();
+        for (old_key, new_key) in &renames.0 {
+            if entries.get(old_key).is_some() && !final_keys.insert(new_key.as_str()) {
+                return Err(RenameKeysError::TargetExists(new_key.clone(), path.to_string_lossy().to_string()));
+            }
+        }
+
+        let mut renamed_keys = 0;
+        for (key, _) in entries.0.iter_mut() {
+            if let Some(new_key) = renames.0.get(key) {
+                summary.missing_keys.remove(key);
+                *key = new_key.clone();
+                renamed_keys += 1;
+            }
+        }
+
+        if renamed_keys > 0 {
+            summary.renamed_keys += renamed_keys;
+            summary.changed_files += 1;
+            session.write(path, serialize_flattened_entries(entries, nested, nested_key_separator));
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Renames the string literals of the source files equal to a renamed key
+/// Returns the number of changed source files
+pub fn rename_source_references(source_files: &[SourceFile], renames: &KeyRenames, session: &mut WriteSession) -> usize {
+    let mut changed_files = 0;
+
+    for source_file in source_files {
+        let content = replace_string_literals(&source_file.content, |value| renames.0.get(value).cloned());
+        if content != source_file.content {
+            changed_files += 1;
+            session.write(&source_file.path, content);
+        }
+    }
+
+    changed_files
+}
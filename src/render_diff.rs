@@ -0,0 +1,115 @@
+use std::io::{stdout, IsTerminal};
+use clap::ValueEnum;
+
+/// Above this number of compared character pairs the values are shown as fully replaced
+const MAX_DIFF_MATRIX_SIZE: usize = 4_000_000;
+
+#[derive(Debug, PartialEq)]
+pub enum DiffOperation {
+    Equal(String),
+    Insert(String),
+    Delete(String),
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum DiffFormat {
+    /// Colored when printing to a terminal, plain otherwise
+    #[default]
+    Auto,
+    /// ANSI colors
+    Color,
+    /// `[-deleted-]{+inserted+}` markers
+    Plain,
+    /// `<del>` and `<ins>` tags
+    Html,
+}
+
+/// Character-level diff between two values, based on their longest common subsequence
+pub fn diff_chars(old: &str, new: &str) -> Vec<DiffOperation> {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+
+    diff_sequences(&old, &new, |c| c.to_string())
+}
+
+/// Line-level diff between two texts, each operation holding whole lines with their line breaks
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffOperation> {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+
+    diff_sequences(&old, &new, |line| line.to_string())
+}
+
+fn diff_sequences<T: PartialEq>(old: &[T], new: &[T], to_text: impl Fn(&T) -> String) -> Vec<DiffOperation> {
+    if old.len() * new.len() > MAX_DIFF_MATRIX_SIZE {
+        return vec![
+            DiffOperation::Delete(old.iter().map(&to_text).collect()),
+            DiffOperation::Insert(new.iter().map(&to_text).collect()),
+        ];
+    }
+
+    // lcs[i][j] is the common subsequence length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut operations: Vec<DiffOperation> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push_operation(&mut operations, DiffOperation::Equal(to_text(&old[i])));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            push_operation(&mut operations, DiffOperation::Insert(to_text(&new[j])));
+            j += 1;
+        } else {
+            push_operation(&mut operations, DiffOperation::Delete(to_text(&old[i])));
+            i += 1;
+        }
+    }
+
+    operations
+}
+
+fn push_operation(operations: &mut Vec<DiffOperation>, operation: DiffOperation) {
+    match (operations.last_mut(), operation) {
+        (Some(DiffOperation::Equal(last)), DiffOperation::Equal(text))
+        | (Some(DiffOperation::Insert(last)), DiffOperation::Insert(text))
+        | (Some(DiffOperation::Delete(last)), DiffOperation::Delete(text)) => last.push_str(&text),
+        (_, operation) => operations.push(operation),
+    }
+}
+
+pub fn render_diff(operations: &[DiffOperation], format: DiffFormat) -> String {
+    let format = match format {
+        DiffFormat::Auto if stdout().is_terminal() => DiffFormat::Color,
+        DiffFormat::Auto => DiffFormat::Plain,
+        format => format,
+    };
+
+    operations
+        .iter()
+        .map(|operation| match (format, operation) {
+            (DiffFormat::Html, DiffOperation::Equal(text)) => escape_html(text),
+            (_, DiffOperation::Equal(text)) => text.clone(),
+            (DiffFormat::Color, DiffOperation::Insert(text)) => format!("\x1b[32;4m{}\x1b[0m", text),
+            (DiffFormat::Color, DiffOperation::Delete(text)) => format!("\x1b[31;9m{}\x1b[0m", text),
+            (DiffFormat::Html, DiffOperation::Insert(text)) => format!("<ins>{}</ins>", escape_html(text)),
+            (DiffFormat::Html, DiffOperation::Delete(text)) => format!("<del>{}</del>", escape_html(text)),
+            (_, DiffOperation::Insert(text)) => format!("{{+{}+}}", text),
+            (_, DiffOperation::Delete(text)) => format!("[-{}-]", text),
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,95 @@
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::BuildHasher;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use thiserror::Error;
+
+/// Crockford base 32, as used by ULIDs
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Run ids are written in file names, XML comments and JSON, long CI build tags fitting in
+const MAX_RUN_ID_LENGTH: usize = 128;
+
+#[derive(Error, Debug)]
+pub enum RunStampError {
+    #[error("Invalid run id `{0}`, expected up to 128 letters, digits, `.`, `_` or `-`")]
+    InvalidRunId(String),
+}
+
+/// Identifier and date stamped on every artifact of a run, so the artifacts of a CI job can be correlated
+#[derive(Serialize, Clone, Debug)]
+pub struct RunStamp {
+    pub run_id: String,
+    /// UTC date of the run, left out for reproducible artifacts
+    pub generated_at: Option<String>,
+}
+
+impl RunStamp {
+    /// Stamp of a run, with the identifier given by CI or a new ULID
+    pub fn new(run_id: Option<String>, timestamped: bool) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        Self {
+            run_id: run_id.unwrap_or_else(|| generate_ulid(now.as_millis() as u64)),
+            generated_at: timestamped.then(|| format_utc_date(now.as_secs())),
+        }
+    }
+}
+
+/// Checks a run id given by CI, as a build number or a ULID
+pub fn parse_run_id(run_id: &str) -> Result<String, RunStampError> {
+    let valid = !run_id.is_empty()
+        && run_id.len() <= MAX_RUN_ID_LENGTH
+        && run_id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        return Err(RunStampError::InvalidRunId(run_id.to_string()));
+    }
+    Ok(run_id.to_string())
+}
+
+/// ULID of the given time: 48 bits of milliseconds then 80 random bits, in 26 base 32 characters
+pub fn generate_ulid(timestamp_ms: u64) -> String {
+    let random = random_bytes().iter().fold(0u128, |value, byte| (value << 8) | u128::from(*byte));
+    let value = ((timestamp_ms as u128 & 0xFFFF_FFFF_FFFF) << 80) | random;
+
+    (0..26).rev().map(|index| ULID_ALPHABET[((value >> (index * 5)) & 0x1F) as usize] as char).collect()
+}
+
+/// 80 random bits from the system random generator
+/// Where `/dev/urandom` is missing, randomly keyed hashers are the source of randomness available without a dependency
+fn random_bytes() -> [u8; 10] {
+    let mut bytes = [0u8; 10];
+    if File::open("/dev/urandom").and_then(|mut urandom| urandom.read_exact(&mut bytes)).is_err() {
+        let random = (u128::from(RandomState::new().hash_one(0u8)) << 64) | u128::from(RandomState::new().hash_one(1u8));
+        bytes.copy_from_slice(&random.to_be_bytes()[..10]);
+    }
+    bytes
+}
+
+/// RFC 3339 UTC date of a Unix timestamp, as `2024-05-01T12:30:00Z`
+fn format_utc_date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds_of_day = timestamp % 86_400;
+
+    // Civil date from days since 1970-01-01, after Howard Hinnant's algorithm
+    let shifted_days = days + 719_468;
+    let era = shifted_days.div_euclid(146_097);
+    let day_of_era = shifted_days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
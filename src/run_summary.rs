@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+use serde::Serialize;
+use thiserror::Error;
+use crate::analyse_project_duplication::DuplicationSummary;
+use crate::run_stamp::RunStamp;
+use crate::safe_write::write_output_file;
+
+#[derive(Error, Debug)]
+pub enum RunSummaryError {
+    #[error("Unable to serialize the run summary: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    #[error("Unable to write the run summary: {0}")]
+    UnableToWritePath(String, #[source] std::io::Error),
+}
+
+/// Outcome of a command, written as a small JSON file for CI steps whatever the human output
+#[derive(Serialize)]
+pub struct RunSummary {
+    #[serde(flatten)]
+    pub stamp: RunStamp,
+    pub command: String,
+    pub passed: bool,
+    pub error: Option<String>,
+    pub duration_ms: u128,
+    pub counts: BTreeMap<&'static str, usize>,
+    pub thresholds: BTreeMap<&'static str, usize>,
+    #[serde(skip)]
+    started_at: Instant,
+}
+
+impl RunSummary {
+    pub fn start(command: &str, stamp: RunStamp) -> Self {
+        Self {
+            stamp,
+            command: command.to_string(),
+            passed: true,
+            error: None,
+            duration_ms: 0,
+            counts: BTreeMap::new(),
+            thresholds: BTreeMap::new(),
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn record_count(&mut self, name: &'static str, count: usize) {
+        self.counts.insert(name, count);
+    }
+
+    pub fn record_threshold(&mut self, name: &'static str, threshold: usize) {
+        self.thresholds.insert(name, threshold);
+    }
+
+    pub fn record_duplications(&mut self, summary: &DuplicationSummary) {
+        self.record_count("inter_package_duplications", summary.inter_package);
+        self.record_count("common_translation_duplications", summary.common_translation);
+        self.record_count("external_projects_duplications", summary.external_projects);
+        self.record_count("test_fixture_duplications", summary.test_fixture);
+        self.record_count("total_duplications", summary.total());
+    }
+
+    pub fn finish(&mut self, error: Option<String>) {
+        self.passed = error.is_none();
+        self.error = error;
+        self.duration_ms = self.started_at.elapsed().as_millis();
+    }
+}
+
+pub fn write_run_summary(output_path: &Path, summary: &RunSummary) -> Result<(), RunSummaryError> {
+    let content = serde_json::to_string_pretty(summary).map_err(RunSummaryError::Serialize)?;
+
+    write_output_file(output_path, content + "\n")
+        .map_err(|e| RunSummaryError::UnableToWritePath(output_path.to_string_lossy().to_string(), e))
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const UNDO_JOURNAL_PATH: &str = ".translations-analyzer/undo.json";
+
+/// Set once for the whole process, write sessions and undo then fail instead of touching any file
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Makes every later write session, undo and output file of the process fail, for embedders that must never change the files
+pub fn enable_read_only() {
+    READ_ONLY.store(true, Ordering::SeqCst);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+/// Writes a file produced by a command, as a report, a baseline or a schema, refused in read-only mode as write
+/// sessions are
+pub fn write_output_file(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if is_read_only() {
+        return Err(std::io::Error::new(ErrorKind::PermissionDenied, format!("read-only mode, {} not written", path.display())));
+    }
+    fs::write(path, content)
+}
+
+#[derive(Error, Debug)]
+pub enum SafeWriteError {
+    #[error("Unable to write file: {0}")]
+    UnableToWrite(String, #[source] std::io::Error),
+
+    #[error("Unable to read file: {0}")]
+    UnableToRead(String, #[source] std::io::Error),
+
+    #[error("Invalid undo journal: {0}")]
+    InvalidJournal(String, #[source] serde_json::Error),
+
+    #[error("Nothing to undo in {0}")]
+    NothingToUndo(String),
+
+    #[error("Read-only mode, {0} files not written")]
+    ReadOnly(usize),
+}
+
+#[derive(Serialize, Deserialize)]
+struct UndoEntry {
+    path: PathBuf,
+    previous_content: Option<String>,
+}
+
+enum PendingChange {
+    Write(String),
+    Remove,
+}
+
+/// Groups file changes so they are applied together and can be reverted with `undo`
+pub struct WriteSession {
+    root_path: PathBuf,
+    dry_run: bool,
+    pending: Vec<(PathBuf, PendingChange)>,
+}
+
+impl WriteSession {
+    pub fn new(root_path: &Path, dry_run: bool) -> Self {
+        Self { root_path: root_path.to_path_buf(), dry_run, pending: Vec::new() }
+    }
+
+    pub fn write(&mut self, path: &Path, content: String) {
+        self.pending.push((path.to_path_buf(), PendingChange::Write(content)));
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        self.pending.push((path.to_path_buf(), PendingChange::Remove));
+    }
+
+    /// Records the previous state of every touched file in the undo journal, then applies the changes
+    /// Returns the number of changed files
+    pub fn commit(self) -> Result<usize, SafeWriteError> {
+        for (path, change) in &self.pending {
+            let action = match change {
+                PendingChange::Write(_) if path.exists() => "Update",
+                PendingChange::Write(_) => "Create",
+                PendingChange::Remove => "Remove",
+            };
+            let prefix = if self.dry_run { "[dry-run] " } else { "" };
+            println!("{}{} {}", prefix, action, self.display_path(path));
+        }
+
+        if self.dry_run {
+            return Ok(self.pending.len());
+        }
+        if is_read_only() {
+            return Err(SafeWriteError::ReadOnly(self.pending.len()));
+        }
+
+        let journal = self
+            .pending
+            .iter()
+            .map(|(path, _)| Ok(UndoEntry { path: path.clone(), previous_content: read_optional(path)? }))
+            .collect::<Result<Vec<UndoEntry>, SafeWriteError>>()?;
+        let journal_path = self.root_path.join(UNDO_JOURNAL_PATH);
+        write_atomically(&journal_path, &serde_json::to_string_pretty(&journal).unwrap())?;
+
+        for (path, change) in &self.pending {
+            match change {
+                PendingChange::Write(content) => write_atomically(path, content)?,
+                PendingChange::Remove => remove_if_exists(path)?,
+            }
+        }
+
+        Ok(self.pending.len())
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root_path).unwrap_or(path).to_string_lossy().to_string()
+    }
+}
+
+/// Restores the files touched by the last write session
+pub fn undo_last_session(root_path: &Path) -> Result<usize, SafeWriteError> {
+    if is_read_only() {
+        return Err(SafeWriteError::ReadOnly(0));
+    }
+    let journal_path = root_path.join(UNDO_JOURNAL_PATH);
+    let journal_content = read_optional(&journal_path)?
+        .ok_or_else(|| SafeWriteError::NothingToUndo(root_path.to_string_lossy().to_string()))?;
+    let journal: Vec<UndoEntry> = serde_json::from_str(&journal_content)
+        .map_err(|e| SafeWriteError::InvalidJournal(journal_path.to_string_lossy().to_string(), e))?;
+
+    // Revert in reverse order so a file touched twice ends up in its original state
+    for entry in journal.iter().rev() {
+        match &entry.previous_content {
+            Some(content) => write_atomically(&entry.path, content)?,
+            None => remove_if_exists(&entry.path)?,
+        }
+        println!("Restore {}", entry.path.strip_prefix(root_path).unwrap_or(&entry.path).to_string_lossy());
+    }
+
+    remove_if_exists(&journal_path)?;
+
+    Ok(journal.len())
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, SafeWriteError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(SafeWriteError::UnableToRead(path.to_string_lossy().to_string(), e)),
+    }
+}
+
+fn write_atomically(path: &Path, content: &str) -> Result<(), SafeWriteError> {
+    let to_error = |e| SafeWriteError::UnableToWrite(path.to_string_lossy().to_string(), e);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(to_error)?;
+    }
+
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+    fs::write(&temporary_path, content).map_err(to_error)?;
+    fs::rename(&temporary_path, path).map_err(to_error)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), SafeWriteError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            Err(SafeWriteError::UnableToWrite(path.to_string_lossy().to_string(), e))
+        }
+        _ => Ok(()),
+    }
+}
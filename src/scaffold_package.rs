@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use crate::safe_write::WriteSession;
+use crate::translation_file::{serialize_translation_entries, TranslationEntries};
+
+const LOCALE_PLACEHOLDER: &str = "{locale}";
+const STARTER_KEY: &str = "title";
+
+#[derive(Error, Debug)]
+pub enum ScaffoldError {
+    #[error("No locale to scaffold, set `locales` in the settings")]
+    NoLocales,
+
+    #[error("Translation file {0} already exists")]
+    FileExists(String),
+}
+
+/// Where the translation files of a package are created, matching `locales_file_regex`
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct ScaffoldLayout {
+    /// Directory of the translation files, relative to the package
+    pub translations_directory: String,
+    /// File name of the translation files, `{locale}` being replaced by each locale
+    pub file_name_template: String,
+}
+
+impl Default for ScaffoldLayout {
+    fn default() -> Self {
+        Self {
+            translations_directory: "translations".to_string(),
+            file_name_template: "Messages_{locale}.json".to_string(),
+        }
+    }
+}
+
+/// Path of the translation file of a locale in a package folder
+pub fn scaffold_file_path(package_folder: &Path, layout: &ScaffoldLayout, locale: &str) -> PathBuf {
+    package_folder
+        .join(&layout.translations_directory)
+        .join(layout.file_name_template.replace(LOCALE_PLACEHOLDER, locale))
+}
+
+/// Key namespace of a package, its folder name
+pub fn starter_namespace(package_folder: &Path) -> String {
+    package_folder.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Creates a translation file per locale holding a single empty key under the package namespace
+/// Returns the created files, none being written when one of them already exists
+pub fn scaffold_package(
+    package_folder: &Path,
+    locales: &BTreeSet<String>,
+    layout: &ScaffoldLayout,
+    segment_separator: &str,
+    session: &mut WriteSession,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if locales.is_empty() {
+        return Err(ScaffoldError::NoLocales);
+    }
+
+    let paths: Vec<PathBuf> = locales.iter().map(|locale| scaffold_file_path(package_folder, layout, locale)).collect();
+    if let Some(existing_path) = paths.iter().find(|path| path.exists()) {
+        return Err(ScaffoldError::FileExists(existing_path.to_string_lossy().to_string()));
+    }
+
+    let starter_key = format!("{}{}{}", starter_namespace(package_folder), segment_separator, STARTER_KEY);
+    let entries = TranslationEntries(vec![(starter_key, Value::String(String::new()))]);
+    for path in &paths {
+        session.write(path, serialize_translation_entries(&entries));
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use regex::{Captures, Regex};
+use crate::search_recursive_regex::{search_recursive_regex, SearchAllTranslationsFilesError};
+
+static STRING_LITERAL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"'((?:[^'\\\n]|\\.)*)'|"((?:[^"\\\n]|\\.)*)"|`((?:[^`\\]|\\.)*)`"#).unwrap()
+});
+
+/// A string literal found in a source file
+pub struct SourceStringLiteral {
+    pub path: PathBuf,
+    pub line: usize,
+    pub value: String,
+}
+
+/// Source files of the monorepo, loaded once so several analyses can scan them
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Finds every source file with one of the extensions, skipping the configured directories
+pub fn find_source_files(
+    root_path: &Path,
+    extensions: &[String],
+    paths_to_skip: &[String],
+) -> Result<Vec<SourceFile>, SearchAllTranslationsFilesError> {
+    let escaped_extensions: Vec<String> = extensions.iter().map(|extension| regex::escape(extension)).collect();
+    let pattern = format!(r"\.({})$", escaped_extensions.join("|"));
+
+    let paths = search_recursive_regex(root_path, &pattern, paths_to_skip)?;
+
+    Ok(paths
+        .into_par_iter()
+        .filter_map(|path| fs::read_to_string(&path).ok().map(|content| SourceFile { path, content }))
+        .collect())
+}
+
+/// Extracts the quoted, double quoted and template string literals of the source files
+pub fn scan_string_literals(source_files: &[SourceFile]) -> Vec<SourceStringLiteral> {
+    source_files
+        .par_iter()
+        .flat_map_iter(|source_file| {
+            let line_index = LineIndex::new(&source_file.content);
+            STRING_LITERAL_REGEX.captures_iter(&source_file.content).filter_map(move |captures| {
+                let literal = captures.get(1).or(captures.get(2)).or(captures.get(3))?;
+                Some(SourceStringLiteral {
+                    path: source_file.path.clone(),
+                    line: line_index.line_number(literal.start()),
+                    value: literal.as_str().to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Replaces the content of the string literals for which `replacement` gives a new value, keeping their quotes
+pub fn replace_string_literals(content: &str, replacement: impl Fn(&str) -> Option<String>) -> String {
+    STRING_LITERAL_REGEX
+        .replace_all(content, |captures: &Captures| {
+            let whole = captures.get(0).map_or("", |whole| whole.as_str());
+            match captures.get(1).or(captures.get(2)).or(captures.get(3)) {
+                Some(literal) => match replacement(literal.as_str()) {
+                    Some(new_value) => format!("{}{}{}", &whole[..1], new_value, &whole[whole.len() - 1..]),
+                    None => whole.to_string(),
+                },
+                None => whole.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Offsets of the line breaks of a content, built once so the line of each of its offsets is found by a binary search
+pub struct LineIndex(Vec<usize>);
+
+impl LineIndex {
+    pub fn new(content: &str) -> Self {
+        Self(content.match_indices('\n').map(|(offset, _)| offset).collect())
+    }
+
+    /// Line of an offset, starting at 1
+    pub fn line_number(&self, offset: usize) -> usize {
+        self.0.partition_point(|line_break| *line_break < offset) + 1
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::suggest_common_translations::CommonModulePath;
+use crate::allowed_duplications::AllowedDuplications;
+use crate::analyse_project_duplication::{analyse_duplication, DuplicationType};
+use crate::cluster_id::translation_cluster_id;
+use crate::entities::Translation;
+use crate::i18next_keys::I18nextKeys;
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::package_dependencies::PackageDependencies;
+use crate::settings::ValueNormalization;
+use crate::test_fixtures::TestFixtures;
+use crate::locales::LocaleMatcher;
+
+/// Outcome of adding a translation, for tools creating new strings
+#[derive(Serialize, Debug)]
+pub struct ProposedTranslationScore {
+    pub duplicated: bool,
+    pub duplication_type: Option<DuplicationType>,
+    /// Cluster the value would join, or create when it is not duplicated
+    pub cluster_id: String,
+    /// Existing key to use instead of adding the translation
+    pub recommended_key: Option<String>,
+    /// Translations already using the value
+    pub occurrences: usize,
+}
+
+/// Classifies a translation not yet written to its file as the duplication analysis would once it is,
+/// `proposed.path` being the translation file it would be added to
+#[allow(clippy::too_many_arguments)]
+pub fn score_proposed_translation(
+    proposed: &Translation,
+    all_translations: &HashMap<String, Vec<&Translation>>,
+    normalization: &ValueNormalization,
+    allowed_duplications: &AllowedDuplications,
+    dependencies: &PackageDependencies,
+    common_modules_path: &[CommonModulePath],
+    test_fixtures: &TestFixtures,
+    i18next_keys: &I18nextKeys,
+    locale_matcher: &LocaleMatcher,
+    package_path_pattern: &PackagePathPattern,
+) -> ProposedTranslationScore {
+    let normalized_value = proposed.translations.normalized(normalization);
+    let mut occurrences: Vec<&Translation> = all_translations.get(&normalized_value).cloned().unwrap_or_default();
+    let existing_occurrences = occurrences.len();
+    occurrences.push(proposed);
+
+    let index = HashMap::from([(normalized_value, occurrences)]);
+    let package_path = package_path_pattern.package_path(&proposed.path.to_string_lossy());
+
+    match analyse_duplication(&package_path, &[proposed], &index, normalization, allowed_duplications, dependencies, common_modules_path, test_fixtures, i18next_keys, locale_matcher, package_path_pattern).pop() {
+        Some(report) => ProposedTranslationScore {
+            duplicated: true,
+            duplication_type: Some(report.duplication_type),
+            cluster_id: report.cluster_id,
+            recommended_key: Some(report.canonical_key),
+            occurrences: existing_occurrences,
+        },
+        None => ProposedTranslationScore {
+            duplicated: false,
+            duplication_type: None,
+            cluster_id: translation_cluster_id(proposed, normalization, locale_matcher),
+            recommended_key: None,
+            occurrences: existing_occurrences,
+        },
+    }
+}
+
+pub fn print_proposed_translation_score(score: &ProposedTranslationScore) {
+    println!("Proposed translation score :");
+    match &score.duplication_type {
+        Some(duplication_type) => println!("Duplicated : yes, {:?} ({} existing usages)", duplication_type, score.occurrences),
+        None => println!("Duplicated : no"),
+    }
+    println!("Cluster : #{}", score.cluster_id);
+    if let Some(recommended_key) = &score.recommended_key {
+        println!("Recommended key : {}", recommended_key);
+    }
+}
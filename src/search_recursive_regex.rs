@@ -1,10 +1,19 @@
-use std::io::Error;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use regex::Regex;
 use std::fs;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
+use crate::glob_pattern::compile_glob;
+use crate::ignore_file::{IgnoreFileError, IgnoreRules};
+
+static STRICT_IO: AtomicBool = AtomicBool::new(false);
+static SKIPPED_DIRECTORIES: Lazy<parking_lot::Mutex<BTreeSet<PathBuf>>> = Lazy::new(|| parking_lot::Mutex::new(BTreeSet::new()));
 
 #[derive(Error, Debug)]
 pub enum SearchAllTranslationsFilesError {
@@ -16,22 +25,135 @@ pub enum SearchAllTranslationsFilesError {
 
     #[error("Invalid regex pattern: {0} - {1}")]
     InvalidRegexPattern(String, String),
+
+    #[error("Invalid glob pattern: {0} - {1}")]
+    InvalidGlobPattern(String, String),
+
+    #[error(transparent)]
+    IgnoreFile(#[from] IgnoreFileError),
+}
+
+/// How the walked files are selected
+#[derive(Clone, Debug)]
+pub enum FilePattern {
+    /// Regex matched against the file name
+    Regex(String),
+    /// Glob matched against the path relative to the root path, as `**/Messages_*.json`
+    Glob(String),
+}
+
+impl FilePattern {
+    /// The regex of the pattern, which also captures the locale of the file names
+    pub fn regex(&self) -> Option<&str> {
+        match self {
+            FilePattern::Regex(regex) => Some(regex),
+            FilePattern::Glob(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for FilePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilePattern::Regex(regex) => write!(f, "{}", regex),
+            FilePattern::Glob(glob) => write!(f, "{}", glob),
+        }
+    }
+}
+
+/// Compiled `FilePattern`, globs being matched against the path relative to their root path
+enum FileMatcher {
+    FileName(Regex),
+    RelativePath(PathBuf, Regex),
+    /// Files matched by any of the matchers, to select the files of several patterns in a single walk
+    Any(Arc<Vec<FileMatcher>>),
+}
+
+impl FileMatcher {
+    fn compile(root_path: &Path, pattern: &FilePattern) -> Result<Self, SearchAllTranslationsFilesError> {
+        Ok(match pattern {
+            FilePattern::Regex(regex) => FileMatcher::FileName(
+                Regex::new(regex).map_err(|e| SearchAllTranslationsFilesError::InvalidRegexPattern(regex.clone(), e.to_string()))?,
+            ),
+            FilePattern::Glob(glob) => FileMatcher::RelativePath(
+                root_path.to_path_buf(),
+                compile_glob(glob).map_err(|e| SearchAllTranslationsFilesError::InvalidGlobPattern(glob.clone(), e.to_string()))?,
+            ),
+        })
+    }
+
+    fn is_match(&self, path: &Path) -> bool {
+        match self {
+            FileMatcher::Any(matchers) => matchers.iter().any(|matcher| matcher.is_match(path)),
+            FileMatcher::FileName(regex) => path.file_name().is_some_and(|file_name| regex.is_match(&file_name.to_string_lossy())),
+            FileMatcher::RelativePath(root_path, regex) => path.strip_prefix(root_path).is_ok_and(|relative| {
+                let relative: Vec<_> = relative.components().map(|component| component.as_os_str().to_string_lossy()).collect();
+                regex.is_match(&relative.join("/"))
+            }),
+        }
+    }
+}
+
+/// Makes the walks fail on the first unreadable directory, instead of skipping it
+pub fn enable_strict_io() {
+    STRICT_IO.store(true, Ordering::Relaxed);
+}
+
+/// Directories skipped by the walks of the run because reading them was not permitted
+pub fn skipped_directories() -> BTreeSet<PathBuf> {
+    SKIPPED_DIRECTORIES.lock().clone()
 }
 
 /// Recursively searches for regex matches in all files within a path
+/// Sub-directories that cannot be read for lack of permission are skipped and recorded in
+/// `skipped_directories`, unless `enable_strict_io` was called
+/// Paths matched by the `.translationsignore` files of the walked directories are left out
 /// Returns a vector of tuples: (file_path, line_number, matched_text)
 pub fn search_recursive_regex(
     root_path: &Path,
     regex_pattern: &str,
     paths_to_skip: &[String],
-) -> Result<Vec<Box<PathBuf>>, SearchAllTranslationsFilesError> {
-    let regex = Regex::new(regex_pattern)
-        .map_err(|e| SearchAllTranslationsFilesError::InvalidRegexPattern(regex_pattern.to_string(), e.to_string()))?;
+) -> Result<Vec<PathBuf>, SearchAllTranslationsFilesError> {
+    search_recursive_pattern(root_path, root_path, &FilePattern::Regex(regex_pattern.to_string()), paths_to_skip)
+}
+
+/// Recursively searches the files of `search_path` matching the pattern, globs being matched against the paths
+/// relative to `root_path`, as in `search_recursive_regex`
+pub fn search_recursive_pattern(
+    root_path: &Path,
+    search_path: &Path,
+    pattern: &FilePattern,
+    paths_to_skip: &[String],
+) -> Result<Vec<PathBuf>, SearchAllTranslationsFilesError> {
+    search_recursive_matcher(search_path, FileMatcher::compile(root_path, pattern)?, paths_to_skip)
+}
+
+/// Files of `search_path` matching each of the patterns, in the order of the patterns, the directories being walked
+/// once for all of them
+pub fn search_recursive_patterns(
+    root_path: &Path,
+    search_path: &Path,
+    patterns: &[FilePattern],
+    paths_to_skip: &[String],
+) -> Result<Vec<Vec<PathBuf>>, SearchAllTranslationsFilesError> {
+    let matchers = Arc::new(patterns.iter().map(|pattern| FileMatcher::compile(root_path, pattern)).collect::<Result<Vec<_>, _>>()?);
+    let files = search_recursive_matcher(search_path, FileMatcher::Any(matchers.clone()), paths_to_skip)?;
+
+    Ok(matchers.iter().map(|matcher| files.iter().filter(|path| matcher.is_match(path)).cloned().collect()).collect())
+}
 
-    let regex = Arc::new(regex);
+fn search_recursive_matcher(
+    search_path: &Path,
+    matcher: FileMatcher,
+    paths_to_skip: &[String],
+) -> Result<Vec<PathBuf>, SearchAllTranslationsFilesError> {
+    let matcher = Arc::new(matcher);
     let results = Arc::new(parking_lot::Mutex::new(Vec::new()));
 
-    search_recursive_parallel(root_path, regex, paths_to_skip, results.clone())?;
+    let entries = fs::read_dir(search_path)
+        .map_err(|e| SearchAllTranslationsFilesError::UnableToReadPath(search_path.to_string_lossy().to_string(), e))?;
+    let ignore_rules = Arc::new(IgnoreRules::default()).with_directory(search_path)?;
+    search_recursive_parallel(entries, matcher, paths_to_skip, ignore_rules, results.clone())?;
 
     let final_results = results.lock().clone();
 
@@ -39,43 +161,46 @@ pub fn search_recursive_regex(
 }
 
 fn search_recursive_parallel(
-    path: &Path,
-    regex: Arc<Regex>,
+    entries: fs::ReadDir,
+    matcher: Arc<FileMatcher>,
     paths_to_skip: &[String],
-    results: Arc<parking_lot::Mutex<Vec<Box<PathBuf>>>>,
+    ignore_rules: Arc<IgnoreRules>,
+    results: Arc<parking_lot::Mutex<Vec<PathBuf>>>,
 ) -> Result<(), SearchAllTranslationsFilesError> {
-    let entries = fs::read_dir(path)
-        .map_err(|e| SearchAllTranslationsFilesError::UnableToReadPath(
-            path.to_string_lossy().to_string(),
-            e,
-        ))?;
-
     let paths: Vec<_> = entries
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
         .collect();
 
-    paths.par_iter().for_each(|entry_path| {
-        process_entry(&entry_path, regex.clone(), paths_to_skip, results.clone()).expect(&format!("Unable to process: {}", entry_path.to_string_lossy()));
-    });
-
-    Ok(())
+    paths.par_iter().try_for_each(|entry_path| process_entry(entry_path, matcher.clone(), paths_to_skip, ignore_rules.clone(), results.clone()))
 }
 
 fn process_entry(
     path: &Path,
-    regex: Arc<Regex>,
+    matcher: Arc<FileMatcher>,
     paths_to_skip: &[String],
-    results: Arc<parking_lot::Mutex<Vec<Box<PathBuf>>>>
+    ignore_rules: Arc<IgnoreRules>,
+    results: Arc<parking_lot::Mutex<Vec<PathBuf>>>
 ) -> Result<(), SearchAllTranslationsFilesError> {
     if path.is_dir() {
         // Skip hidden directories and common non-source directories
-        if should_skip_directory(path, paths_to_skip) {
+        if should_skip_directory(path, paths_to_skip) || ignore_rules.is_ignored(path, true) {
             return Ok(());
         }
-        search_recursive_parallel(path, regex, paths_to_skip, results)?;
-    } else if path.is_file() && regex.is_match(path.file_name().unwrap().to_string_lossy().as_ref()) {
-        results.lock().push(Box::new(path.to_owned()))
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::PermissionDenied && !STRICT_IO.load(Ordering::Relaxed) => {
+                SKIPPED_DIRECTORIES.lock().insert(path.to_owned());
+                return Ok(());
+            }
+            Err(e) => return Err(SearchAllTranslationsFilesError::UnableToReadPath(path.to_string_lossy().to_string(), e)),
+        };
+        let ignore_rules = ignore_rules.with_directory(path)?;
+        search_recursive_parallel(entries, matcher, paths_to_skip, ignore_rules, results)?;
+    } else if path.is_file()
+        && matcher.is_match(path)
+        && !ignore_rules.is_ignored(path, false) {
+        results.lock().push(path.to_owned())
     }
     Ok(())
 }
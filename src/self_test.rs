@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{json, Value};
+use thiserror::Error;
+use crate::analyse_untranslated::UntranslatedReport;
+use crate::lint_keys::KeyLintReport;
+use crate::render_diff::{diff_lines, DiffOperation};
+
+/// Report a fixture is expected to produce, in the JSON report format
+pub const EXPECTED_REPORT_FILE: &str = "expected-report.json";
+/// Results of the other analyses a fixture is expected to produce, as built by `build_analyses_report`
+pub const EXPECTED_ANALYSES_FILE: &str = "expected-analyses.json";
+/// Settings of a fixture, the settings of the run being used when a fixture has none
+pub const FIXTURE_SETTINGS_FILE: &str = "settings.json";
+/// Unchanged lines shown around each difference
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Error, Debug)]
+pub enum SelfTestError {
+    #[error("Unable to read fixtures directory: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("No fixture found in {0}")]
+    NoFixture(String),
+
+    #[error("Invalid expected results in {0}: {1}")]
+    InvalidExpectedResults(String, String),
+}
+
+/// A monorepo checked in with the report the analyzer is expected to produce on it
+pub struct SelfTestFixture {
+    pub name: String,
+    /// Root path of the fixture monorepo
+    pub root_path: PathBuf,
+    pub settings_file_path: Option<PathBuf>,
+    pub expected_report_path: PathBuf,
+    pub expected_analyses_path: PathBuf,
+}
+
+/// Every subdirectory of the fixtures directory is a fixture, in name order
+pub fn find_self_test_fixtures(fixtures_path: &Path) -> Result<Vec<SelfTestFixture>, SelfTestError> {
+    let unable_to_read = |e| SelfTestError::UnableToReadPath(fixtures_path.to_string_lossy().to_string(), e);
+
+    let mut fixtures = Vec::new();
+    for entry in fs::read_dir(fixtures_path).map_err(unable_to_read)? {
+        let root_path = entry.map_err(unable_to_read)?.path();
+        if !root_path.is_dir() {
+            continue;
+        }
+
+        let settings_file_path = root_path.join(FIXTURE_SETTINGS_FILE);
+        fixtures.push(SelfTestFixture {
+            name: root_path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            settings_file_path: settings_file_path.is_file().then_some(settings_file_path),
+            expected_report_path: root_path.join(EXPECTED_REPORT_FILE),
+            expected_analyses_path: root_path.join(EXPECTED_ANALYSES_FILE),
+            root_path,
+        });
+    }
+
+    if fixtures.is_empty() {
+        return Err(SelfTestError::NoFixture(fixtures_path.to_string_lossy().to_string()));
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(fixtures)
+}
+
+/// Leaves out what changes from one run to another: the run stamp, and the order of the duplications
+/// which follows the order files are parsed in
+pub fn normalize_report(mut report: Value) -> Value {
+    if let Some(report) = report.as_object_mut() {
+        report.remove("run_id");
+        report.remove("generated_at");
+    }
+
+    let packages = report.get_mut("packages").and_then(Value::as_array_mut).into_iter().flatten();
+    for duplications in packages.filter_map(|package| package.get_mut("duplications").and_then(Value::as_array_mut)) {
+        let sort_key = |duplication: &Value| {
+            ["path", "key", "cluster_id"].map(|field| duplication.get(field).and_then(Value::as_str).unwrap_or_default().to_string())
+        };
+        duplications.sort_by_key(sort_key);
+    }
+
+    report
+}
+
+/// Results of the analyses other than the duplications, with `/` separated paths relative to the fixture root so
+/// the expected file is the same on every platform
+pub fn build_analyses_report(root_path: &Path, key_counts: &BTreeMap<String, usize>, untranslated: &UntranslatedReport, key_lint: &[KeyLintReport]) -> Value {
+    let relative = |path: &Path| {
+        let relative = path.strip_prefix(root_path).unwrap_or(path);
+        relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+    };
+
+    let completeness: BTreeMap<&String, BTreeMap<&String, Value>> = untranslated.completeness
+        .iter()
+        .map(|(package_path, locales)| {
+            let locales = locales.iter().map(|(locale, completeness)| (locale, json!({
+                "reference_keys": completeness.reference_keys,
+                "translated": completeness.translated,
+                "untranslated": completeness.untranslated,
+                "missing": completeness.missing,
+            }))).collect();
+            (package_path, locales)
+        })
+        .collect();
+
+    let mut untranslated_values: Vec<Value> = untranslated.values
+        .iter()
+        .map(|value| json!({ "folder": relative(&value.folder), "key": value.key, "locale": value.locale }))
+        .collect();
+    untranslated_values.sort_by_key(Value::to_string);
+
+    let mut key_lint: Vec<Value> = key_lint
+        .iter()
+        .map(|report| json!({
+            "path": relative(&report.translation.path),
+            "key": report.translation.key,
+            "violations": report.violations.iter().map(ToString::to_string).collect::<Vec<String>>(),
+            "suggested_key": report.suggested_key,
+        }))
+        .collect();
+    key_lint.sort_by_key(Value::to_string);
+
+    json!({
+        "key_counts": key_counts,
+        "completeness": completeness,
+        "untranslated": untranslated_values,
+        "key_lint": key_lint,
+    })
+}
+
+/// Reads the expected results of the analyses of a fixture, as written by `build_analyses_report`
+pub fn read_expected_analyses(path: &Path) -> Result<Value, SelfTestError> {
+    let display_path = path.to_string_lossy().to_string();
+    let content = fs::read_to_string(path).map_err(|e| SelfTestError::UnableToReadPath(display_path.clone(), e))?;
+
+    serde_json::from_str(&content).map_err(|e| SelfTestError::InvalidExpectedResults(display_path, e.to_string()))
+}
+
+/// Differences between the expected and the actual report, as `-` and `+` prefixed lines around
+/// a few unchanged ones, `None` when the reports are identical
+pub fn diff_reports(expected: &Value, actual: &Value) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let pretty = |report: &Value| serde_json::to_string_pretty(report).unwrap_or_default() + "\n";
+    let mut operations = diff_lines(&pretty(expected), &pretty(actual));
+    // Expected lines first, as in unified diffs
+    for position in 1..operations.len() {
+        if matches!((&operations[position - 1], &operations[position]), (DiffOperation::Insert(_), DiffOperation::Delete(_))) {
+            operations.swap(position - 1, position);
+        }
+    }
+
+    let mut diff = String::new();
+    for (position, operation) in operations.iter().enumerate() {
+        match operation {
+            DiffOperation::Delete(lines) => lines.lines().for_each(|line| diff.push_str(&format!("- {}\n", line))),
+            DiffOperation::Insert(lines) => lines.lines().for_each(|line| diff.push_str(&format!("+ {}\n", line))),
+            DiffOperation::Equal(lines) => {
+                let lines: Vec<&str> = lines.lines().collect();
+                let leading = if position > 0 { DIFF_CONTEXT_LINES } else { 0 };
+                let trailing = if position + 1 < operations.len() { DIFF_CONTEXT_LINES } else { 0 };
+
+                if lines.len() > leading + trailing {
+                    lines[..leading].iter().for_each(|line| diff.push_str(&format!("  {}\n", line)));
+                    diff.push_str(&format!("  ... {} unchanged lines\n", lines.len() - leading - trailing));
+                    lines[lines.len() - trailing..].iter().for_each(|line| diff.push_str(&format!("  {}\n", line)));
+                } else {
+                    lines.iter().for_each(|line| diff.push_str(&format!("  {}\n", line)));
+                }
+            }
+        }
+    }
+
+    Some(diff)
+}
@@ -1,27 +1,287 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use thiserror::Error;
+use crate::allowed_duplications::AllowedDuplication;
+use crate::analyse_formatted_values::default_formatted_value_patterns;
+use crate::analyse_quote_styles::{default_quote_styles, QuoteStyle};
+use crate::auto_triage::TriageRule;
+use crate::key_count_limits::KeyCountLimits;
+use crate::duplication_thresholds::DuplicationThresholds;
+use crate::i18next_keys::I18nextKeys;
+use crate::lint_keys::KeyRules;
+use crate::load_translations::LoadErrorPolicy;
+use crate::locales::any_locale_file_regex;
+use crate::map_translations_by_project::{PackagePathPattern, DEFAULT_PACKAGE_PATH_REGEX};
+use crate::search_recursive_regex::FilePattern;
+use crate::pipeline::PipelineTask;
+use crate::scaffold_package::ScaffoldLayout;
+use crate::settings_overrides::SettingsOverride;
+use crate::suggest_common_translations::CommonModulePath;
+use crate::test_fixtures::TestFixtureRules;
+use crate::toml_file::parse_toml_document;
+use crate::yaml_file::parse_yaml_document;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[cfg_attr(test, derive(Debug))]
+#[serde(default)]
 pub struct Settings {
     pub common_translations_modules_path: Vec<String>,
+    /// Finds the package of a translation file path, its `type` and `name` groups giving the package type and the end
+    /// of the package path
+    pub package_path_regex: String,
+    /// Matches the translation files of the reference locale, capturing their locale like `locales_file_regex`,
+    /// unused when `locales` is a map of file name regexes
     pub translation_file_regex: String,
+    /// Matches the translation files of every locale, the first capture group being the locale, unused when `locales`
+    /// is a map of file name regexes
+    pub locales_file_regex: String,
+    /// Glob selecting the translation files of the reference locale by their path relative to the root path, as
+    /// `**/Messages_fr_FR.json`, instead of `translation_file_regex`, its files being of the reference locale
+    pub translation_file_glob: Option<String>,
+    /// Glob selecting the translation files of every locale by their path relative to the root path, instead of
+    /// `locales_file_regex`, its `{locale}` placeholder capturing the locales unless `locales` is set
+    pub locales_file_glob: Option<String>,
+    /// Name or number of the capture group holding the locale in the file name regexes,
+    /// the group named `locale` then the first group being used when unset
+    pub locale_capture_group: Option<String>,
     pub skip_directories: Vec<String>,
+    /// Joins the keys of nested objects in translation files, as `form.save`
+    pub nested_key_separator: String,
+    /// `fail-fast` stops the analysis on the first translation file which cannot be loaded,
+    /// `continue` reports it and analyses the other files
+    pub load_error_policy: LoadErrorPolicy,
+    /// Translation files larger than this many bytes are skipped with a warning, `null` for no limit
+    pub max_translation_file_size: Option<u64>,
+    pub near_duplicate_algorithm: SimilarityAlgorithm,
+    pub near_duplicate_threshold: f64,
+    pub normalization: ValueNormalization,
+    pub duplication_filters: DuplicationFilters,
+    /// Translation files of tests, excluded from the duplication analysis or reported apart
+    pub test_fixtures: TestFixtureRules,
+    /// Values allowed to be duplicated between some keys or packages, not reported as duplications there
+    pub allowed_duplications: Vec<AllowedDuplication>,
+    /// Values or key/value pairs never reported as duplications, relative to the root path
+    pub allowlist_file_path: String,
+    /// Extensions of the source files scanned for translation keys usages
+    pub source_file_extensions: Vec<String>,
+    /// Deprecated keys sidecar file, relative to the root path
+    pub deprecations_file_path: String,
+    /// Reviewers triage decisions on duplicate clusters, relative to the root path
+    pub annotations_file_path: String,
+    /// Rules tagging duplications before they are reported and checked, the first matching rule winning
+    pub triage_rules: Vec<TriageRule>,
+    /// Settings of some packages, as legacy ones, the first override matching a package applying to it
+    pub overrides: Vec<SettingsOverride>,
+    /// Duplications known when `baseline create` was run, not reported again, relative to the root path
+    pub duplication_baseline_file_path: Option<String>,
+    /// Caps on the duplications enforced by the `check` command
+    pub thresholds: DuplicationThresholds,
+    pub key_rules: KeyRules,
+    pub i18next_keys: I18nextKeys,
+    pub key_count_limits: KeyCountLimits,
+    /// Layout of the translation files created by the `scaffold` command
+    pub scaffold: ScaffoldLayout,
+    /// Locales every package is expected to provide, the locales found by `locales_file_regex` being used when empty
+    pub locales: Locales,
+    /// Locale the other locales are compared to, as captured by `locales_file_regex` or a key of `locales`
+    pub reference_locale: String,
+    /// Maximum length ratio between a value and its reference locale value, in both directions
+    pub length_anomaly_ratio: f64,
+    /// Patterns of hardcoded formatted numbers and dates indexed by locale, `*` applying to every locale
+    pub formatted_value_patterns: BTreeMap<String, Vec<String>>,
+    /// Apostrophe and quotes expected in the values of each locale, locales without style not being checked
+    pub quote_styles: BTreeMap<String, QuoteStyle>,
+    /// Named pipelines runnable with the `task` command
+    pub tasks: BTreeMap<String, PipelineTask>,
+    /// Writes a JSON summary of every run (counts, thresholds, pass/fail, duration) to this path
+    pub summary_file_path: Option<String>,
+    /// Curated catalog of canonical strings (key, then locale, then value), relative to the root path
+    pub canonical_catalog_file_path: Option<String>,
+    /// Glossary of terms with their approved translation and variants per locale, relative to the root path
+    pub glossary_file_path: Option<String>,
+    /// Named sets of settings, as `ci` or `local`, applied over the other settings when selected with `--profile`
+    pub profiles: BTreeMap<String, Value>,
+}
+
+impl Settings {
+    /// Matches the translation files of the reference locale, its `locales` pattern when set
+    pub fn reference_file_regex(&self) -> String {
+        self.locales.patterns().get(&self.reference_locale).cloned().unwrap_or_else(|| self.translation_file_regex.clone())
+    }
+
+    /// Matches the translation files of every locale, any of the `locales` patterns when set
+    /// The locale of a file is found by a `LocaleMatcher`, the captures of this regex not telling it with patterns
+    pub fn locale_files_regex(&self) -> String {
+        if self.locales.patterns().is_empty() {
+            return self.locales_file_regex.clone();
+        }
+        any_locale_file_regex(self.locales.patterns())
+    }
+
+    pub fn package_path_pattern(&self) -> Result<PackagePathPattern, regex::Error> {
+        PackagePathPattern::compile(&self.package_path_regex)
+    }
+
+    /// Common translations modules, joined to the root path
+    pub fn common_modules_paths(&self, root_path: &Path) -> Vec<CommonModulePath> {
+        self.common_translations_modules_path
+            .iter()
+            .map(|path| CommonModulePath { configured: path.clone(), path: root_path.join(path) })
+            .collect()
+    }
+
+    /// Selects the translation files of the reference locale, by glob when one is set
+    pub fn reference_file_pattern(&self) -> FilePattern {
+        match &self.translation_file_glob {
+            Some(glob) => FilePattern::Glob(glob.clone()),
+            None => FilePattern::Regex(self.reference_file_regex()),
+        }
+    }
+
+    /// Selects the translation files of every locale, by glob when one is set
+    pub fn locale_files_pattern(&self) -> FilePattern {
+        match &self.locales_file_glob {
+            Some(glob) => FilePattern::Glob(glob.clone()),
+            None => FilePattern::Regex(self.locale_files_regex()),
+        }
+    }
+}
+
+static NO_LOCALE_PATTERNS: BTreeMap<String, String> = BTreeMap::new();
+
+/// Locales every package is expected to provide
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(untagged)]
+pub enum Locales {
+    /// Locales alone, their files being found by `locales_file_regex` as in earlier settings files
+    List(Vec<String>),
+    /// File name regex of each locale, replacing `translation_file_regex` and `locales_file_regex`
+    Patterns(BTreeMap<String, String>),
+}
+
+impl Default for Locales {
+    fn default() -> Self {
+        Locales::Patterns(BTreeMap::new())
+    }
+}
+
+impl Locales {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Locales::List(locales) => locales.is_empty(),
+            Locales::Patterns(patterns) => patterns.is_empty(),
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            Locales::List(locales) => locales.clone(),
+            Locales::Patterns(patterns) => patterns.keys().cloned().collect(),
+        }
+    }
+
+    /// File name regex of each locale, empty for a list of locales
+    pub fn patterns(&self) -> &BTreeMap<String, String> {
+        match self {
+            Locales::List(_) => &NO_LOCALE_PATTERNS,
+            Locales::Patterns(patterns) => patterns,
+        }
+    }
+}
+
+/// Normalization steps applied to values before they are indexed for duplication detection
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct ValueNormalization {
+    pub trim: bool,
+    pub collapse_whitespace: bool,
+    pub case_fold: bool,
+    pub strip_trailing_punctuation: bool,
+}
+
+/// Values left out of the duplication index, so trivially short strings are never reported
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct DuplicationFilters {
+    pub min_characters: usize,
+    pub min_words: usize,
+    /// Common words as "Oui" or "Non", compared once normalized
+    pub ignored_values: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityAlgorithm {
+    JaroWinkler,
+    Levenshtein,
 }
 
 #[derive(Error, Debug)]
 pub enum SettingsFileManagerError {
     #[error("Unable to read settings file: {0}")]
     UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Invalid settings in {0}, {1}")]
+    InvalidSettings(String, String),
 }
 
+/// Default duplication baseline, also written by `baseline create` when the settings disable the baseline
+pub const DEFAULT_DUPLICATION_BASELINE_FILE_PATH: &str = "translations-baseline.json";
+
+/// Settings file names looked up when none is given, in order
+pub const SETTINGS_FILE_NAMES: [&str; 4] = ["settings.json", "settings.toml", "settings.yaml", "settings.yml"];
+
+/// Looks for a settings file in the directory then in its parents, as `.eslintrc` files are, up to the monorepo
+/// root, or up to the repository root holding `.git` when the directory is outside of the monorepo
+pub fn find_settings_file(directory: &Path, monorepo_path: &Path) -> Option<PathBuf> {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let directory = canonical(directory);
+    let monorepo_path = canonical(monorepo_path);
+    let inside_monorepo = directory.starts_with(&monorepo_path);
+
+    for ancestor in directory.ancestors() {
+        let found = SETTINGS_FILE_NAMES.iter().map(|name| ancestor.join(name)).find(|path| path.is_file());
+        if found.is_some() {
+            return found;
+        }
+
+        let last = if inside_monorepo { ancestor == monorepo_path } else { ancestor.join(".git").exists() };
+        if last {
+            break;
+        }
+    }
+    None
+}
+
+/// Reads the settings from a JSON, TOML or YAML file, the format being told by the extension
 pub fn get_settings(
     settings_file_path: &Path,
 ) -> Result<Settings, SettingsFileManagerError> {
+    merge_settings(vec![read_settings_file(settings_file_path)?])
+}
+
+/// Settings set by a JSON, TOML or YAML file, to be merged with the other configuration layers
+pub fn read_settings_file(settings_file_path: &Path) -> Result<Value, SettingsFileManagerError> {
+    let invalid = |reason: String| SettingsFileManagerError::InvalidSettings(settings_file_path.to_string_lossy().to_string(), reason);
+
     match fs::read_to_string(settings_file_path) {
-        Ok(invoice_data) => Ok(serde_json::from_str(&invoice_data).unwrap()),
+        Ok(invoice_data) => {
+            let extension = settings_file_path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+            let layer = match extension {
+                "toml" => parse_toml_document(&invoice_data).map_err(invalid)?,
+                "yaml" | "yml" => parse_yaml_document(&invoice_data).map_err(invalid)?,
+                _ => serde_json::from_str(&invoice_data).map_err(|e| invalid(e.to_string()))?,
+            };
+            // Checked alone, so a wrong setting is reported with the file it comes from
+            serde_json::from_value::<Settings>(layer.clone()).map_err(|e| invalid(e.to_string()))?;
+            Ok(layer)
+        }
         Err(e) => Err(SettingsFileManagerError::UnableToReadPath(
             settings_file_path.to_string_lossy().to_string(),
             e,
@@ -29,11 +289,89 @@ pub fn get_settings(
     }
 }
 
+/// Settings of a profile of the settings file, to be merged over the file
+pub fn read_settings_profile(file_layer: &Value, name: &str) -> Result<Value, SettingsFileManagerError> {
+    let invalid = |reason: String| SettingsFileManagerError::InvalidSettings(format!("profile {}", name), reason);
+    let profiles = file_layer.get("profiles").and_then(Value::as_object);
+
+    let Some(layer) = profiles.and_then(|profiles| profiles.get(name)) else {
+        let available: Vec<&str> = profiles.into_iter().flat_map(|profiles| profiles.keys()).map(String::as_str).collect();
+        return Err(invalid(format!("unknown profile, the profiles being : {}", available.join(", "))));
+    };
+    serde_json::from_value::<Settings>(layer.clone()).map_err(|e| invalid(e.to_string()))?;
+
+    Ok(layer.clone())
+}
+
+/// Prefix of the environment variables setting a setting, as `TRANSLATIONS_ANALYZER_REFERENCE_LOCALE`
+pub const SETTINGS_ENV_PREFIX: &str = "TRANSLATIONS_ANALYZER_";
+
+/// Settings set by the `TRANSLATIONS_ANALYZER_*` environment variables, named after the settings in upper case,
+/// the variables naming no setting being left out
+/// Text settings take the variable as is, the others being JSON encoded, as `TRANSLATIONS_ANALYZER_SKIP_DIRECTORIES='["dist"]'`
+pub fn read_settings_env(vars: impl IntoIterator<Item = (String, String)>) -> Result<Value, SettingsFileManagerError> {
+    let defaults = serde_json::to_value(Settings::default()).expect("settings are serializable");
+    let defaults = defaults.as_object().expect("settings are serialized as an object");
+
+    let mut layer = Map::new();
+    for (name, raw_value) in vars {
+        let Some(setting) = name.strip_prefix(SETTINGS_ENV_PREFIX).map(str::to_lowercase) else {
+            continue;
+        };
+        let invalid = |reason: String| SettingsFileManagerError::InvalidSettings(name.clone(), reason);
+        // Reported by the settings validation, as the unknown fields of the settings file
+        let Some(default) = defaults.get(&setting) else {
+            continue;
+        };
+
+        let value = match default {
+            Value::String(_) => Value::String(raw_value),
+            // Optional settings may be texts, which are not JSON encoded
+            Value::Null => serde_json::from_str(&raw_value).unwrap_or(Value::String(raw_value)),
+            _ => serde_json::from_str(&raw_value).map_err(|e| invalid(e.to_string()))?,
+        };
+        let setting_layer = Value::Object(Map::from_iter([(setting.clone(), value.clone())]));
+        serde_json::from_value::<Settings>(setting_layer).map_err(|e| invalid(e.to_string()))?;
+        layer.insert(setting, value);
+    }
+
+    Ok(Value::Object(layer))
+}
+
+/// Settings of the configuration layers, each one overriding the previous ones: objects are merged,
+/// any other value replaced. The default settings apply to what no layer sets, a null layer setting nothing
+pub fn merge_settings(layers: Vec<Value>) -> Result<Settings, SettingsFileManagerError> {
+    let merged = layers.into_iter().filter(|layer| !layer.is_null()).fold(Value::Object(Map::new()), merge_values);
+
+    serde_json::from_value(merged).map_err(|e| SettingsFileManagerError::InvalidSettings("configuration layers".to_string(), e.to_string()))
+}
+
+fn merge_values(base: Value, layer: Value) -> Value {
+    match (base, layer) {
+        (Value::Object(mut base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                let merged = match base.remove(&key) {
+                    Some(base_value) => merge_values(base_value, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, layer) => layer,
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
             common_translations_modules_path: vec!["packages/manager/modules/common-translations".to_string()],
-            translation_file_regex: r#"^Messages_fr_FR\.json$"# .to_string(),
+            package_path_regex: DEFAULT_PACKAGE_PATH_REGEX.to_string(),
+            translation_file_regex: r#"^Messages_(fr_FR)\.json$"#.to_string(),
+            locales_file_regex: r#"^Messages_([a-z]{2}_[A-Z]{2})\.json$"#.to_string(),
+            translation_file_glob: None,
+            locales_file_glob: None,
+            locale_capture_group: None,
             skip_directories: vec![
                 ".git".to_string(),
                 "node_modules".to_string(),
@@ -43,6 +381,44 @@ impl Default for Settings {
                 "dist".to_string(),
                 "build".to_string(),
                 "manager-tools".to_string()],
+            nested_key_separator: ".".to_string(),
+            load_error_policy: LoadErrorPolicy::default(),
+            max_translation_file_size: Some(10 * 1024 * 1024),
+            near_duplicate_algorithm: SimilarityAlgorithm::JaroWinkler,
+            near_duplicate_threshold: 0.85,
+            normalization: ValueNormalization::default(),
+            duplication_filters: DuplicationFilters::default(),
+            test_fixtures: TestFixtureRules::default(),
+            allowed_duplications: Vec::new(),
+            allowlist_file_path: "translations-allow.json".to_string(),
+            source_file_extensions: vec![
+                "js".to_string(),
+                "jsx".to_string(),
+                "ts".to_string(),
+                "tsx".to_string(),
+                "html".to_string(),
+                "vue".to_string(),
+            ],
+            deprecations_file_path: "translations-deprecations.json".to_string(),
+            annotations_file_path: "translations-annotations.json".to_string(),
+            triage_rules: Vec::new(),
+            overrides: Vec::new(),
+            duplication_baseline_file_path: Some(DEFAULT_DUPLICATION_BASELINE_FILE_PATH.to_string()),
+            thresholds: DuplicationThresholds::default(),
+            key_rules: KeyRules::default(),
+            i18next_keys: I18nextKeys::default(),
+            key_count_limits: KeyCountLimits::default(),
+            scaffold: ScaffoldLayout::default(),
+            locales: Locales::default(),
+            reference_locale: "fr_FR".to_string(),
+            length_anomaly_ratio: 3.0,
+            formatted_value_patterns: default_formatted_value_patterns(),
+            quote_styles: default_quote_styles(),
+            tasks: BTreeMap::new(),
+            summary_file_path: None,
+            canonical_catalog_file_path: None,
+            glossary_file_path: None,
+            profiles: BTreeMap::new(),
         }
     }
 }
\ No newline at end of file
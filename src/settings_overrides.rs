@@ -0,0 +1,121 @@
+use std::path::{Component, Path, PathBuf};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::glob_pattern::compile_glob;
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::search_recursive_regex::FilePattern;
+
+/// Analyses a package can opt out of
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageAnalysis {
+    /// Duplications of the package values, which stay visible to the other packages
+    Duplication,
+    /// Health score of the global report
+    Health,
+}
+
+/// Settings of the packages matching a glob, as legacy packages laid out differently,
+/// the settings of the monorepo applying to everything left unset
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SettingsOverride {
+    /// Glob on the package path, as `packages/manager/apps/legacy-*`
+    pub packages: String,
+    pub translation_file_regex: Option<String>,
+    pub locales_file_regex: Option<String>,
+    /// Skipped within the matching packages, in addition to the skipped directories of the monorepo
+    #[serde(default)]
+    pub skip_directories: Vec<String>,
+    #[serde(default)]
+    pub disabled_analyses: Vec<PackageAnalysis>,
+}
+
+/// Overrides of the settings, the first one matching a package applying to it
+pub struct SettingsOverrides {
+    overrides: Vec<(Regex, SettingsOverride)>,
+    /// Finds the package of the files to select
+    package_path_pattern: PackagePathPattern,
+}
+
+impl SettingsOverrides {
+    pub fn compile(overrides: &[SettingsOverride], package_path_pattern: &PackagePathPattern) -> Result<Self, regex::Error> {
+        overrides
+            .iter()
+            .map(|settings_override| Ok((compile_glob(&settings_override.packages)?, settings_override.clone())))
+            .collect::<Result<Vec<_>, regex::Error>>()
+            .map(|overrides| Self { overrides, package_path_pattern: package_path_pattern.clone() })
+    }
+
+    pub fn find(&self, package_path: &str) -> Option<&SettingsOverride> {
+        self.overrides.iter().find(|(glob, _)| glob.is_match(package_path)).map(|(_, settings_override)| settings_override)
+    }
+
+    pub fn is_disabled(&self, package_path: &str, analysis: PackageAnalysis) -> bool {
+        self.find(package_path).is_some_and(|settings_override| settings_override.disabled_analyses.contains(&analysis))
+    }
+
+    /// Patterns selecting the files of the monorepo, its own pattern followed by the regex of each override having
+    /// one, to be walked at once by `search_recursive_patterns`
+    pub fn file_patterns(&self, default_pattern: &FilePattern, override_regex: impl Fn(&SettingsOverride) -> Option<&String>) -> Vec<FilePattern> {
+        std::iter::once(default_pattern.clone())
+            .chain(self.overrides.iter().filter_map(|(_, settings_override)| override_regex(settings_override)).map(|regex| FilePattern::Regex(regex.clone())))
+            .collect()
+    }
+
+    /// Translation files of the monorepo once the overrides are applied, `files_by_pattern` being the files matched
+    /// by each of the `file_patterns`. Files in the skipped directories of their package override are left out
+    pub fn select_files(
+        &self,
+        files_by_pattern: Vec<Vec<PathBuf>>,
+        override_regex: impl Fn(&SettingsOverride) -> Option<&String>,
+    ) -> Vec<PathBuf> {
+        let mut files_by_pattern = files_by_pattern.into_iter();
+        let default_files = files_by_pattern.next().unwrap_or_default();
+        if self.overrides.is_empty() {
+            return default_files;
+        }
+
+        let keeps_default_regex = |path: &Path| {
+            self.find(&self.package_path_pattern.package_path(&path.to_string_lossy())).is_none_or(|settings_override| override_regex(settings_override).is_none())
+        };
+        let mut files: Vec<PathBuf> = default_files.into_iter().filter(|path| keeps_default_regex(path)).collect();
+
+        for (position, (_, settings_override)) in self.overrides.iter().enumerate() {
+            if override_regex(settings_override).is_none() {
+                continue;
+            }
+            // A package only takes the files of the first override it matches
+            let matched = files_by_pattern.next().unwrap_or_default().into_iter().filter(|path| {
+                let package_path = self.package_path_pattern.package_path(&path.to_string_lossy());
+                self.overrides.iter().position(|(glob, _)| glob.is_match(&package_path)) == Some(position)
+            });
+            files.extend(matched);
+        }
+
+        files.retain(|path| {
+            let package_path = self.package_path_pattern.package_path(&path.to_string_lossy());
+            self.find(&package_path).is_none_or(|settings_override| !is_in_skipped_directory(path, &package_path, &settings_override.skip_directories))
+        });
+        files.sort();
+        files.dedup();
+
+        files
+    }
+}
+
+/// Whether a directory of the path below the package is one of the skipped ones
+fn is_in_skipped_directory(path: &Path, package_path: &str, skip_directories: &[String]) -> bool {
+    let path = path.to_string_lossy();
+    let Some(position) = path.find(package_path) else {
+        return false;
+    };
+
+    let below_package = Path::new(&path[position + package_path.len()..]);
+    let directories = below_package.parent().into_iter().flat_map(Path::components);
+    directories
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .any(|name| skip_directories.iter().any(|skipped| skipped == name))
+}
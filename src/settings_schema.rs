@@ -0,0 +1,253 @@
+use serde_json::{json, Map, Value};
+use crate::settings::Settings;
+
+/// Schema file written by the `schema` command
+pub const DEFAULT_SETTINGS_SCHEMA_OUTPUT: &str = "settings.schema.json";
+/// Version of JSON Schema the schema is written in, the one editors support best
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+/// Fields of the settings file read before the settings, as the profiles applied over them
+const FILE_ONLY_FIELDS: [&str; 2] = ["$schema", "profiles"];
+
+/// JSON Schema of the settings file, so editors validate and complete it, the defaults being the ones of `Settings`
+/// Settings files refer to it with a `$schema` field, which the analyzer ignores
+pub fn settings_json_schema() -> Value {
+    let mut schema = object(json!({
+        "$schema": string("Schema of the settings file, ignored by the analyzer"),
+        "common_translations_modules_path": strings("Common translations modules, relative to the root path"),
+        "package_path_regex": string("Finds the package of a translation file path, the package path ending with the `name` group and the `type` group telling `apps` from `modules`, as `(?P<type>apps|libs)/(?P<name>[^/]+)` for Nx"),
+        "translation_file_regex": string("Matches the translation files of the reference locale, capturing their locale like `locales_file_regex`, unused when `locales` is a map of file name regexes"),
+        "locales_file_regex": string("Matches the translation files of every locale, the first capture group being the locale, unused when `locales` is a map of file name regexes"),
+        "translation_file_glob": nullable_string("Selects the translation files of the reference locale by their path relative to the root path, as `**/Messages_fr_FR.json`, instead of `translation_file_regex`, its files being of the reference locale"),
+        "locales_file_glob": nullable_string("Selects the translation files of every locale by their path relative to the root path, as `**/Messages_{locale}.json`, instead of `locales_file_regex`, its `{locale}` placeholder capturing the locales unless `locales` is set"),
+        "locale_capture_group": nullable_string("Name or number of the capture group holding the locale in the file name regexes, the group named `locale` then the first group being used when unset"),
+        "skip_directories": strings("Directories never walked, as `node_modules`"),
+        "nested_key_separator": string("Joins the keys of nested objects in translation files, as `form.save`"),
+        "load_error_policy": one_of(&["fail-fast", "continue"], "`fail-fast` stops the analysis on the first translation file which cannot be loaded, `continue` reports it and analyses the other files"),
+        "max_translation_file_size": nullable_integer("Translation files larger than this many bytes are skipped with a warning, `null` for no limit"),
+        "near_duplicate_algorithm": one_of(&["jaro_winkler", "levenshtein"], "Similarity measure of the near-duplicate values"),
+        "near_duplicate_threshold": number("Similarity from which values are near duplicates, between 0 and 1"),
+        "normalization": described(object(json!({
+            "trim": boolean("Leaves out leading and trailing whitespace"),
+            "collapse_whitespace": boolean("Turns whitespace sequences into a single space"),
+            "case_fold": boolean("Compares values whatever their case"),
+            "strip_trailing_punctuation": boolean("Leaves out trailing punctuation"),
+        })), "How values are normalized before being compared"),
+        "duplication_filters": described(object(json!({
+            "min_characters": integer("Values shorter than this are never reported"),
+            "min_words": integer("Values with fewer words than this are never reported"),
+            "ignored_values": strings("Common words as \"Oui\" or \"Non\", compared once normalized"),
+        })), "Values too short or too common to be reported as duplications"),
+        "test_fixtures": described(object(json!({
+            "globs": strings("Globs matched against the translation file paths relative to the root path"),
+            "mode": one_of(&["exclude", "report"], "`exclude` leaves the fixtures out of the analysis, `report` reports them under their own duplication type"),
+        })), "Translation files of tests, excluded from the duplication analysis or reported apart"),
+        "allowed_duplications": array(required_object(&["value"], json!({
+            "value": string("Value allowed to be duplicated"),
+            "key_pattern": nullable_string("Regex on the keys the value is allowed under"),
+            "package_pattern": nullable_string("Regex on the package paths the value is allowed in, as `packages/manager/apps/zimbra`"),
+        })), "Values allowed to be duplicated between some keys or packages, not reported as duplications there"),
+        "allowlist_file_path": string("Values or key/value pairs never reported as duplications, relative to the root path"),
+        "source_file_extensions": strings("Extensions of the source files scanned for translation keys usages"),
+        "deprecations_file_path": string("Deprecated keys sidecar file, relative to the root path"),
+        "annotations_file_path": string("Reviewers triage decisions on duplicate clusters, relative to the root path"),
+        "triage_rules": array(required_object(&["name", "action"], json!({
+            "name": string("Shown next to the tag in the reports"),
+            "action": one_of(&["auto-ignore", "auto-priority-high", "auto-priority-low"], "Tag given to the matching duplications"),
+            "max_value_length": nullable_integer("Maximum length of the value in characters"),
+            "min_value_length": nullable_integer("Minimum length of the value in characters"),
+            "min_packages": nullable_integer("Minimum number of packages using the value"),
+            "max_packages": nullable_integer("Maximum number of packages using the value"),
+            "duplication_type": nullable(one_of(&["inter_package", "common_translation", "external_projects", "test_fixture"], "Type of the matching duplications")),
+            "key_pattern": nullable_string("Regex on the key"),
+            "value_pattern": nullable_string("Regex on the value"),
+        })), "Rules tagging duplications before they are reported and checked, the first matching rule winning"),
+        "overrides": array(required_object(&["packages"], json!({
+            "packages": string("Glob on the package path, as `packages/manager/apps/legacy-*`"),
+            "translation_file_regex": nullable_string("Replaces `translation_file_regex` in the matching packages"),
+            "locales_file_regex": nullable_string("Replaces `locales_file_regex` in the matching packages"),
+            "skip_directories": strings("Skipped within the matching packages, in addition to `skip_directories`"),
+            "disabled_analyses": array(one_of(&["duplication", "health"], "Analysis not run on the matching packages"), "Analyses not run on the matching packages"),
+        })), "Settings of some packages, as legacy ones, the first override matching a package applying to it"),
+        "duplication_baseline_file_path": nullable_string("Duplications known when `baseline create` was run, not reported again, relative to the root path, `null` to report every duplication"),
+        "thresholds": described(object(json!({
+            "max_duplications": nullable_integer("Fails `check` when the monorepo has more duplications, the ones of the baseline included"),
+            "max_duplications_by_package": nullable_integer("Fails `check` when a package has more duplications, the ones of the baseline included"),
+            "max_new_duplications": nullable_integer("Fails `check` when more duplications are missing from the baseline"),
+        })), "Caps on the duplications enforced by the `check` command"),
+        "key_rules": described(object(json!({
+            "segment_separator": string("Separator between the segments of a key"),
+            "max_depth": nullable_integer("Maximum number of segments of a key"),
+            "allowed_segment_characters": nullable_string("Regex character class a segment character must match, as `[a-zA-Z0-9_]`"),
+            "forbid_leading_digits": boolean("Forbids segments starting with a digit"),
+            "naming_preset": nullable(one_of(&["lower.dot.case", "snake_case", "camelCase", "kebab-case", "dot.camelCase"], "Naming convention preset the whole key must follow")),
+            "naming_pattern": nullable_string("Regex the whole key must match, checked in addition to the preset"),
+        })), "Structure rules of the keys checked by `lint-keys`"),
+        "i18next_keys": described(object(json!({
+            "enabled": boolean("Groups the suffixed variants of a key, `false` for projects where `_one` or `_other` are plain words of keys"),
+            "contexts": strings("Context suffixes used with `t(key, { context })`, plural suffixes being always recognized"),
+        })), "Recognition of the i18next plural and context suffixes of the keys"),
+        "key_count_limits": described(object(json!({
+            "max_keys": nullable_integer("Maximum number of keys of a package"),
+            "max_keys_by_package": map(integer("Maximum number of keys of the package"), "Caps of some packages, overriding `max_keys`"),
+            "max_growth_percent": nullable(number("Maximum growth of the key count of a package since the baseline, in percent")),
+            "baseline_file_path": string("Key counts of the packages at the last baseline update, relative to the root path"),
+        })), "Caps on the number of keys of the packages enforced by `key-counts`"),
+        "scaffold": described(object(json!({
+            "translations_directory": string("Directory of the translation files, relative to the package"),
+            "file_name_template": string("File name of the translation files, `{locale}` being replaced by each locale"),
+        })), "Layout of the translation files created by the `scaffold` command"),
+        "locales": described(json!({ "anyOf": [
+            array(json!({ "type": "string" }), "Locales alone, their files being found by `locales_file_regex`"),
+            map(string("Regex on the file names of the locale, as `^Messages_fr_FR\\.json$`"), "File name regex of each locale, replacing `translation_file_regex` and `locales_file_regex`"),
+        ] }), "Locales every package is expected to provide, the locales found being used when empty"),
+        "reference_locale": string("Locale the other locales are compared to, as captured by `locales_file_regex` or a key of `locales`"),
+        "length_anomaly_ratio": number("Maximum length ratio between a value and its reference locale value, in both directions"),
+        "formatted_value_patterns": map(strings("Regexes of the locale"), "Patterns of hardcoded formatted numbers and dates indexed by locale, `*` applying to every locale"),
+        "quote_styles": map(required_object(&["apostrophe", "opening_quote", "closing_quote"], json!({
+            "apostrophe": character("Apostrophe of the locale"),
+            "opening_quote": character("Opening quote of the locale"),
+            "closing_quote": character("Closing quote of the locale"),
+        })), "Apostrophe and quotes expected in the values of each locale, locales without style not being checked"),
+        "tasks": map(object(json!({
+            "pipeline": string("Steps of the pipeline, as `check + export-json + export-badge`"),
+            "package_path": nullable_string("Package the pipeline is run on, every package when unset"),
+            "json_output": string("JSON report written by the `export-json` step"),
+            "badge_output": string("Badge written by the `export-badge` step"),
+            "max_duplications": nullable_integer("Fails the task when the total duplication count is above"),
+        })), "Named pipelines runnable with the `task` command"),
+        "summary_file_path": nullable_string("Writes a JSON summary of every run (counts, thresholds, pass/fail, duration) to this path"),
+        "canonical_catalog_file_path": nullable_string("Curated catalog of canonical strings (key, then locale, then value), relative to the root path"),
+        "glossary_file_path": nullable_string("Glossary of terms with their approved translation and variants per locale, relative to the root path"),
+        "profiles": map(json!({ "$ref": "#" }), "Named sets of settings, as `ci` or `local`, applied over the other settings when selected with `--profile`"),
+    }));
+
+    let defaults = serde_json::to_value(Settings::default()).expect("settings are serializable");
+    add_defaults(&mut schema, &defaults);
+
+    let schema = schema.as_object_mut().expect("the schema is an object");
+    schema.insert("$schema".to_string(), json!(JSON_SCHEMA_DRAFT));
+    schema.insert("title".to_string(), json!("Translations analyzer settings"));
+    Value::Object(std::mem::take(schema))
+}
+
+/// Settings without a documented schema property and schema properties which are not settings, nested settings
+/// included, so the schema cannot drift from `Settings` unnoticed
+pub fn settings_schema_drift() -> Vec<String> {
+    let defaults = serde_json::to_value(Settings::default()).expect("settings are serializable");
+    let mut drift = Vec::new();
+    find_schema_drift(&settings_json_schema(), &defaults, "", &mut drift);
+    drift
+}
+
+fn find_schema_drift(schema: &Value, defaults: &Value, prefix: &str, drift: &mut Vec<String>) {
+    let (Some(properties), Some(defaults)) = (schema.get("properties").and_then(Value::as_object), defaults.as_object()) else {
+        return;
+    };
+
+    for name in defaults.keys().filter(|name| !properties.contains_key(*name)) {
+        drift.push(format!("setting {}{} has no schema property", prefix, name));
+    }
+    for (name, property) in properties {
+        // Fields of the settings file which are not settings themselves
+        if prefix.is_empty() && FILE_ONLY_FIELDS.contains(&name.as_str()) {
+            continue;
+        }
+        let Some(default) = defaults.get(name) else {
+            drift.push(format!("schema property {}{} is not a setting", prefix, name));
+            continue;
+        };
+        if property.get("description").and_then(Value::as_str).is_none_or(str::is_empty) {
+            drift.push(format!("schema property {}{} has no description", prefix, name));
+        }
+        find_schema_drift(property, default, &format!("{}{}.", prefix, name), drift);
+    }
+}
+
+/// Gives the properties of the schema their default value, nested objects included
+fn add_defaults(schema: &mut Value, defaults: &Value) {
+    let (Some(properties), Some(defaults)) = (schema.get_mut("properties").and_then(Value::as_object_mut), defaults.as_object()) else {
+        return;
+    };
+
+    for (name, property) in properties.iter_mut() {
+        let Some(default) = defaults.get(name) else {
+            continue;
+        };
+        if default.is_object() && property.get("properties").is_some() {
+            add_defaults(property, default);
+        } else if let Some(property) = property.as_object_mut() {
+            property.insert("default".to_string(), default.clone());
+        }
+    }
+}
+
+fn described(mut schema: Value, description: &str) -> Value {
+    schema.as_object_mut().expect("schemas are objects").insert("description".to_string(), json!(description));
+    schema
+}
+
+fn string(description: &str) -> Value {
+    described(json!({ "type": "string" }), description)
+}
+
+fn character(description: &str) -> Value {
+    described(json!({ "type": "string", "minLength": 1, "maxLength": 1 }), description)
+}
+
+fn boolean(description: &str) -> Value {
+    described(json!({ "type": "boolean" }), description)
+}
+
+fn integer(description: &str) -> Value {
+    described(json!({ "type": "integer", "minimum": 0 }), description)
+}
+
+fn number(description: &str) -> Value {
+    described(json!({ "type": "number" }), description)
+}
+
+fn strings(description: &str) -> Value {
+    described(json!({ "type": "array", "items": { "type": "string" } }), description)
+}
+
+fn one_of(values: &[&str], description: &str) -> Value {
+    described(json!({ "enum": values }), description)
+}
+
+fn nullable_string(description: &str) -> Value {
+    described(json!({ "type": ["string", "null"] }), description)
+}
+
+fn nullable_integer(description: &str) -> Value {
+    described(json!({ "type": ["integer", "null"], "minimum": 0 }), description)
+}
+
+/// Accepts `null` in addition to the values of the schema
+fn nullable(schema: Value) -> Value {
+    let description = schema.get("description").cloned();
+    let mut nullable = Map::from_iter([("anyOf".to_string(), json!([schema, { "type": "null" }]))]);
+    if let Some(description) = description {
+        nullable.insert("description".to_string(), description);
+    }
+    Value::Object(nullable)
+}
+
+fn array(items: Value, description: &str) -> Value {
+    described(json!({ "type": "array", "items": items }), description)
+}
+
+/// Object with free keys, as locales or package paths
+fn map(values: Value, description: &str) -> Value {
+    described(json!({ "type": "object", "additionalProperties": values }), description)
+}
+
+/// Object with the given properties only, every one being optional
+fn object(properties: Value) -> Value {
+    json!({ "type": "object", "properties": properties, "additionalProperties": false })
+}
+
+fn required_object(required: &[&str], properties: Value) -> Value {
+    let mut schema = object(properties);
+    schema.as_object_mut().expect("schemas are objects").insert("required".to_string(), json!(required));
+    schema
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::safe_write::WriteSession;
+use crate::translation_file::{read_flattened_entries, serialize_flattened_entries, TranslationEntries};
+
+#[derive(Error, Debug)]
+pub enum SplitTranslationFileError {
+    #[error("Invalid mapping `{0}`, expected `prefix=target-file`")]
+    InvalidMapping(String),
+
+    #[error("The target of the mapping `{0}` is the source file {1}")]
+    TargetIsSource(String, String),
+
+    #[error("Key {0} already exists in {1} with a different value")]
+    KeyConflict(String, String),
+
+    #[error(transparent)]
+    Load(#[from] LoadTranslationsFilesError),
+}
+
+/// Parses a `prefix=target-file` command line mapping
+pub fn parse_split_mapping(mapping: &str) -> Result<(String, PathBuf), SplitTranslationFileError> {
+    match mapping.split_once('=') {
+        Some((prefix, target)) if !prefix.is_empty() && !target.is_empty() => {
+            Ok((prefix.to_string(), PathBuf::from(target)))
+        }
+        _ => Err(SplitTranslationFileError::InvalidMapping(mapping.to_string())),
+    }
+}
+
+/// Moves the keys of `source_path` matching a prefix into the mapped target file
+/// Targets are resolved relative to the source file folder, the longest matching prefix wins,
+/// and keys already present in a target with the same value are not duplicated
+/// Keys of nested objects are matched by their path joined with `nested_key_separator`, a created target file being
+/// nested as the source file
+pub fn split_translation_file(
+    source_path: &Path,
+    mappings: &[(String, PathBuf)],
+    nested_key_separator: &str,
+    session: &mut WriteSession,
+) -> Result<(), SplitTranslationFileError> {
+    let source_folder = source_path.parent().unwrap_or(Path::new(""));
+    // The keys moved to the source file would be lost when the remaining keys are written over it
+    let canonical_source = source_path.canonicalize().ok();
+    for (prefix, target) in mappings {
+        let target_path = source_folder.join(target);
+        if target_path == source_path || (canonical_source.is_some() && target_path.canonicalize().ok() == canonical_source) {
+            return Err(SplitTranslationFileError::TargetIsSource(prefix.clone(), source_path.to_string_lossy().to_string()));
+        }
+    }
+
+    let (source_entries, source_nested) = read_flattened_entries(source_path, nested_key_separator)?;
+
+    let mut remaining_entries = TranslationEntries::default();
+    let mut moved_entries: BTreeMap<PathBuf, TranslationEntries> = BTreeMap::new();
+
+    for (key, value) in source_entries.0 {
+        let target = mappings
+            .iter()
+            .filter(|(prefix, _)| key.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, target)| source_folder.join(target));
+
+        match target {
+            Some(target) => moved_entries.entry(target).or_default().0.push((key, value)),
+            None => remaining_entries.0.push((key, value)),
+        }
+    }
+
+    for (target_path, entries) in moved_entries {
+        let (mut target_entries, target_nested) = if target_path.exists() {
+            read_flattened_entries(&target_path, nested_key_separator)?
+        } else {
+            (TranslationEntries::default(), source_nested)
+        };
+
+        for (key, value) in entries.0 {
+            match target_entries.get(&key) {
+                Some(existing) if *existing == value => continue,
+                Some(_) => {
+                    return Err(SplitTranslationFileError::KeyConflict(
+                        key,
+                        target_path.to_string_lossy().to_string(),
+                    ));
+                }
+                None => target_entries.0.push((key, value)),
+            }
+        }
+
+        println!("{} keys in {}", target_entries.0.len(), target_path.to_string_lossy());
+        session.write(&target_path, serialize_flattened_entries(target_entries, target_nested, nested_key_separator));
+    }
+
+    println!("{} keys left in {}", remaining_entries.0.len(), source_path.to_string_lossy());
+    session.write(source_path, serialize_flattened_entries(remaining_entries, source_nested, nested_key_separator));
+
+    Ok(())
+}
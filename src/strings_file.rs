@@ -0,0 +1,164 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use serde_json::Value;
+use crate::translation_file::TranslationEntries;
+
+/// Text of a `.strings` file, which Xcode writes in UTF-8 or in UTF-16 with a byte order mark
+pub fn decode_strings_content(bytes: &[u8]) -> Result<String, String> {
+    let decode_utf16 = |bytes: &[u8], to_unit: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| to_unit([pair[0], pair[1]])).collect();
+        String::from_utf16(&units).map_err(|e| e.to_string())
+    };
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).map_err(|e| e.to_string()),
+        _ => String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Parses the `"key" = "value";` entries of an Apple `.strings` file, comments being skipped
+/// Keys and values may also be unquoted words, as allowed by the old-style property list syntax
+pub fn parse_strings_entries(content: &str) -> Result<TranslationEntries, String> {
+    let mut entries = TranslationEntries::default();
+    let mut parser = StringsParser { chars: content.chars().peekable(), line: 1 };
+
+    loop {
+        parser.skip_blanks()?;
+        if parser.chars.peek().is_none() {
+            break;
+        }
+
+        let key = parser.read_token()?;
+        parser.expect('=')?;
+        let value = parser.read_token()?;
+        parser.expect(';')?;
+
+        entries.0.push((key, Value::String(value)));
+    }
+
+    Ok(entries)
+}
+
+struct StringsParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl StringsParser<'_> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error(&self, reason: &str) -> String {
+        format!("line {} : {}", self.line, reason)
+    }
+
+    /// Skips whitespace, `//` line comments and `/* */` block comments
+    fn skip_blanks(&mut self) -> Result<(), String> {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.next();
+                continue;
+            }
+            if c != '/' {
+                break;
+            }
+
+            self.next();
+            match self.next() {
+                Some('/') => while self.next().is_some_and(|c| c != '\n') {},
+                Some('*') => {
+                    let mut previous = ' ';
+                    loop {
+                        match self.next() {
+                            Some('/') if previous == '*' => break,
+                            Some(c) => previous = c,
+                            None => return Err(self.error("unterminated comment")),
+                        }
+                    }
+                }
+                _ => return Err(self.error("unexpected `/`")),
+            }
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_blanks()?;
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected `{}`", expected))),
+        }
+    }
+
+    fn read_token(&mut self) -> Result<String, String> {
+        self.skip_blanks()?;
+        match self.chars.peek() {
+            Some('"') => {
+                self.next();
+                self.read_quoted()
+            }
+            Some(c) if is_word_char(*c) => {
+                let mut word = String::new();
+                while let Some(&c) = self.chars.peek().filter(|c| is_word_char(**c)) {
+                    word.push(c);
+                    self.next();
+                }
+                Ok(word)
+            }
+            _ => Err(self.error("expected a string")),
+        }
+    }
+
+    /// Reads the 4 hex digits of a `\U` escape, characters out of the BMP being written as a UTF-16 surrogate pair
+    /// of two escapes, as `\UD83D\UDE00`
+    fn read_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.read_utf16_unit()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error(&format!("invalid unicode escape `{:04X}`", high)));
+        }
+
+        let low = match (self.next(), self.next()) {
+            (Some('\\'), Some('U' | 'u')) => self.read_utf16_unit()?,
+            _ => return Err(self.error(&format!("unpaired surrogate `{:04X}`", high))),
+        };
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error(&format!("unpaired surrogate `{:04X}`", high)));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).ok_or_else(|| self.error("invalid surrogate pair"))
+    }
+
+    fn read_utf16_unit(&mut self) -> Result<u32, String> {
+        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+        u32::from_str_radix(&hex, 16).map_err(|_| self.error(&format!("invalid unicode escape `{}`", hex)))
+    }
+
+    fn read_quoted(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => match self.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('U') | Some('u') => text.push(self.read_unicode_escape()?),
+                    Some(escaped) => text.push(escaped),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '$' | ':')
+}
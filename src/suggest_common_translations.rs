@@ -0,0 +1,127 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use crate::annotations::Annotations;
+use crate::analyse_project_duplication::{most_used_key, DuplicationReport, DuplicationType};
+use crate::collation::compare_text;
+use crate::entities::{Translation, TranslationValue};
+use crate::map_translations_by_project::PackagePathPattern;
+use crate::settings::ValueNormalization;
+use crate::value_provenance::ValueOrigin;
+
+const COMMON_KEY_PREFIX: &str = "common_";
+
+pub struct CommonTranslationSuggestion<'a> {
+    pub cluster_id: String,
+    pub value: &'a TranslationValue,
+    pub proposed_key: String,
+    pub existing_common_translation: Option<&'a Translation>,
+    pub affected_packages: BTreeSet<String>,
+    /// Package the value seems to come from, when traced in the git history
+    pub origin: Option<ValueOrigin>,
+}
+
+/// Common translations module of the settings, as configured and joined to the root path
+#[derive(Clone, Debug)]
+pub struct CommonModulePath {
+    /// Path as written in the settings, relative to the root path
+    pub configured: String,
+    pub path: PathBuf,
+}
+
+/// Common translations module holding a translation file, the deepest one when modules are nested
+pub fn find_common_module<'a>(path: &Path, common_modules_path: &'a [CommonModulePath]) -> Option<&'a CommonModulePath> {
+    common_modules_path
+        .iter()
+        .filter(|common_module| path.starts_with(&common_module.path))
+        .max_by_key(|common_module| common_module.path.components().count())
+}
+
+/// Whether a translation file belongs to one of the common translations modules
+pub fn is_common_translation(path: &Path, common_modules_path: &[CommonModulePath]) -> bool {
+    find_common_module(path, common_modules_path).is_some()
+}
+
+/// Suggests, for each duplicated value, the common key to use and the packages it would affect
+/// An existing common translation with the same value is always preferred to a new key
+pub fn suggest_common_translations<'a>(
+    duplications: &[&DuplicationReport<'a>],
+    all_translations: &'a HashMap<String, Vec<&'a Translation>>,
+    normalization: &ValueNormalization,
+    common_modules_path: &[CommonModulePath],
+    package_path_pattern: &PackagePathPattern,
+) -> Vec<CommonTranslationSuggestion<'a>> {
+    let mut suggested_clusters: BTreeSet<&str> = BTreeSet::new();
+    let mut suggestions: Vec<CommonTranslationSuggestion<'a>> = Vec::new();
+
+    for duplication in duplications {
+        // Test fixtures copy values on purpose, they are never moved to a common module
+        if duplication.duplication_type == DuplicationType::TestFixture || !suggested_clusters.insert(duplication.cluster_id.as_str()) {
+            continue;
+        }
+
+        let Some(occurrences) = all_translations.get(&duplication.translation.translations.normalized(normalization)) else {
+            continue;
+        };
+
+        let existing_common_translation = occurrences
+            .iter()
+            .find(|occurrence| is_common_translation(&occurrence.path, common_modules_path))
+            .copied();
+
+        let affected_packages: BTreeSet<String> = occurrences
+            .iter()
+            .filter(|occurrence| !is_common_translation(&occurrence.path, common_modules_path))
+            .map(|occurrence| package_path_pattern.package_path(&occurrence.path.to_string_lossy()))
+            .collect();
+
+        let proposed_key = match existing_common_translation {
+            Some(common_translation) => common_translation.key.clone(),
+            None => propose_common_key(occurrences),
+        };
+
+        suggestions.push(CommonTranslationSuggestion {
+            cluster_id: duplication.cluster_id.clone(),
+            value: &duplication.translation.translations,
+            proposed_key,
+            existing_common_translation,
+            affected_packages,
+            origin: None,
+        });
+    }
+
+    suggestions.sort_by(|a, b| compare_text(&a.value.to_string(), &b.value.to_string()));
+    suggestions
+}
+
+/// Most used key of the cluster (shortest one on ties), prefixed as a common key
+fn propose_common_key(occurrences: &[&Translation]) -> String {
+    let key = most_used_key(occurrences);
+
+    if key.starts_with(COMMON_KEY_PREFIX) {
+        key.to_string()
+    } else {
+        format!("{}{}", COMMON_KEY_PREFIX, key)
+    }
+}
+
+pub fn print_common_translations_suggestions(suggestions: &[CommonTranslationSuggestion], annotations: &Annotations, package_path_pattern: &PackagePathPattern) {
+    println!("Common translations suggestions :");
+    println!("Values to move to common translations : {}", suggestions.iter().filter(|suggestion| suggestion.existing_common_translation.is_none()).count());
+    println!("Values already in common translations : {}", suggestions.iter().filter(|suggestion| suggestion.existing_common_translation.is_some()).count());
+
+    for suggestion in suggestions {
+        println!("\n");
+        println!(" ========= Cluster #{} - {} ==========", suggestion.cluster_id, suggestion.value);
+        match suggestion.existing_common_translation {
+            Some(common_translation) => println!("Use existing common key : {} ({})", suggestion.proposed_key, package_path_pattern.package_path(&common_translation.path.to_string_lossy())),
+            None => println!("Create common key : {}", suggestion.proposed_key),
+        }
+        println!("Affected packages : {}", suggestion.affected_packages.iter().cloned().collect::<Vec<String>>().join(", "));
+        if let Some(origin) = &suggestion.origin {
+            println!("Original source : {}", origin);
+        }
+        if let Some(annotation) = annotations.get(&suggestion.cluster_id) {
+            println!("Annotation : {}", annotation);
+        }
+    }
+}
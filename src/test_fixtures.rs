@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::glob_pattern::compile_glob;
+
+/// What the duplication analysis does with the translations of test fixtures
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TestFixtureMode {
+    /// Left out of the analysis, as if the files did not exist
+    Exclude,
+    /// Reported under their own duplication type, out of the other counts
+    #[default]
+    Report,
+}
+
+/// Translation files belonging to tests, which copy real values without being duplications to fix
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(default)]
+pub struct TestFixtureRules {
+    /// Globs matched against the translation file paths relative to the root path
+    pub globs: Vec<String>,
+    pub mode: TestFixtureMode,
+}
+
+impl Default for TestFixtureRules {
+    fn default() -> Self {
+        Self {
+            globs: vec![
+                "**/test/**".to_string(),
+                "**/tests/**".to_string(),
+                "**/__tests__/**".to_string(),
+                "**/fixtures/**".to_string(),
+                "**/__fixtures__/**".to_string(),
+                "**/__mocks__/**".to_string(),
+            ],
+            mode: TestFixtureMode::Report,
+        }
+    }
+}
+
+/// Test fixture globs, ready to be evaluated during classification
+#[derive(Default)]
+pub struct TestFixtures {
+    monorepo_path: PathBuf,
+    globs: Vec<Regex>,
+    pub mode: TestFixtureMode,
+}
+
+impl TestFixtures {
+    pub fn compile(monorepo_path: &Path, rules: &TestFixtureRules) -> Result<Self, regex::Error> {
+        Ok(Self {
+            monorepo_path: monorepo_path.to_path_buf(),
+            globs: rules.globs.iter().map(|glob| compile_glob(glob)).collect::<Result<Vec<Regex>, regex::Error>>()?,
+            mode: rules.mode,
+        })
+    }
+
+    pub fn is_fixture(&self, path: &Path) -> bool {
+        let relative_path = path.strip_prefix(&self.monorepo_path).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        self.globs.iter().any(|glob| glob.is_match(&relative_path))
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::str::Chars;
+use serde_json::{Map, Number, Value};
+use crate::translation_file::TranslationEntries;
+
+/// Parses the key/value pairs of a TOML file, as `title = "Accueil"` or `[home]` then `title = "Accueil"`
+/// Keys of tables and dotted keys are returned nested, to be joined with the nested key separator as
+/// nested JSON objects are. Dates are kept as strings and arrays of tables are rejected
+pub fn parse_toml_entries(content: &str) -> Result<TranslationEntries, String> {
+    let mut entries = TranslationEntries::default();
+    let mut parser = TomlParser { chars: content.chars().peekable(), line: 1 };
+    let mut table: Vec<String> = Vec::new();
+
+    loop {
+        parser.skip_blanks(true);
+        let Some(&c) = parser.chars.peek() else {
+            break;
+        };
+
+        if c == '[' {
+            parser.next();
+            if parser.chars.peek() == Some(&'[') {
+                return Err(parser.error("arrays of tables are not supported"));
+            }
+            table = parser.read_key()?;
+            parser.expect(']')?;
+        } else {
+            let key = parser.read_key()?;
+            parser.expect('=')?;
+            parser.skip_blanks(false);
+            let value = parser.read_value()?;
+
+            let mut path = table.iter().chain(&key).cloned();
+            let first = path.next().expect("keys have at least one part");
+            let nested: Vec<String> = path.collect();
+            entries.0.push((first, nest(&nested, value)));
+        }
+
+        parser.expect_end_of_line()?;
+    }
+
+    Ok(entries)
+}
+
+/// Parses a whole TOML document, as a settings file, into the matching JSON value
+/// Unlike translation files, arrays of tables are supported, `[[triage_rules]]` adding an object to `triage_rules`
+/// Keys and tables defined twice are rejected, as a TOML parser does, instead of the last definition winning
+pub fn parse_toml_document(content: &str) -> Result<Value, String> {
+    read_toml_document(content).map(|(value, _)| value)
+}
+
+/// Line of each table and key of a TOML document, by its path as `triage_rules[3].key_pattern`
+/// The fields of arrays and inline tables are left out, their key giving their line
+pub fn toml_field_lines(content: &str) -> Result<Vec<(String, usize)>, String> {
+    read_toml_document(content).map(|(_, field_lines)| field_lines)
+}
+
+fn read_toml_document(content: &str) -> Result<(Value, Vec<(String, usize)>), String> {
+    let mut document = Map::new();
+    let mut field_lines: Vec<(String, usize)> = Vec::new();
+    let mut defined_tables: HashSet<Vec<String>> = HashSet::new();
+    let mut parser = TomlParser { chars: content.chars().peekable(), line: 1 };
+    let mut table: Vec<String> = Vec::new();
+
+    loop {
+        parser.skip_blanks(true);
+        let Some(&c) = parser.chars.peek() else {
+            break;
+        };
+
+        let line = parser.line;
+        if c == '[' {
+            parser.next();
+            let array = parser.chars.peek() == Some(&'[');
+            if array {
+                parser.next();
+            }
+            table = parser.read_key()?;
+            parser.expect(']')?;
+
+            if array {
+                parser.expect(']')?;
+                let (last, parents) = table.split_last().expect("keys have at least one part");
+                let parent = table_at(&mut document, parents).map_err(|reason| parser.error(&reason))?;
+                match parent.entry(last.clone()).or_insert_with(|| Value::Array(Vec::new())) {
+                    Value::Array(tables) => tables.push(Value::Object(Map::new())),
+                    _ => return Err(parser.error(&format!("`{}` is not an array of tables", last))),
+                }
+            } else {
+                if !defined_tables.insert(table.clone()) {
+                    return Err(parser.error(&format!("table `{}` is defined twice", table.join("."))));
+                }
+                table_at(&mut document, &table).map_err(|reason| parser.error(&reason))?;
+            }
+            field_lines.push((table_field_path(&document, &table), line));
+        } else {
+            let key = parser.read_key()?;
+            parser.expect('=')?;
+            parser.skip_blanks(false);
+            let value = parser.read_value()?;
+            let target = table_at(&mut document, &table).map_err(|reason| parser.error(&reason))?;
+            insert_nested(target, &key, value).map_err(|reason| parser.error(&reason))?;
+            let field = table.iter().chain(&key).cloned().collect::<Vec<_>>();
+            field_lines.push((table_field_path(&document, &field), line));
+        }
+
+        parser.expect_end_of_line()?;
+    }
+
+    Ok((Value::Object(document), field_lines))
+}
+
+/// Path of a table or key, the arrays of tables it goes through being indexed by their last table, as `a[1].b`
+fn table_field_path(document: &Map<String, Value>, path: &[String]) -> String {
+    let mut field = String::new();
+    let mut table = Some(document);
+    for part in path {
+        if !field.is_empty() {
+            field.push('.');
+        }
+        field.push_str(part);
+        let mut value = table.and_then(|table| table.get(part));
+        if let Some(Value::Array(tables)) = value
+            && tables.last().is_some_and(Value::is_object)
+        {
+            field.push_str(&format!("[{}]", tables.len() - 1));
+            value = tables.last();
+        }
+        table = value.and_then(Value::as_object);
+    }
+    field
+}
+
+/// Table at the path, created when missing, the path going through the last table of arrays of tables
+fn table_at<'a>(document: &'a mut Map<String, Value>, path: &[String]) -> Result<&'a mut Map<String, Value>, String> {
+    let mut table = document;
+    for part in path {
+        let mut value = table.entry(part.clone()).or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Array(tables) = value {
+            value = tables.last_mut().ok_or_else(|| format!("`{}` is an empty array", part))?;
+        }
+        table = value.as_object_mut().ok_or_else(|| format!("`{}` is not a table", part))?;
+    }
+    Ok(table)
+}
+
+/// Wraps the value in an object for each part of the key
+fn nest(key: &[String], value: Value) -> Value {
+    key.iter().rev().fold(value, |value, part| Value::Object(Map::from_iter([(part.clone(), value)])))
+}
+
+struct TomlParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl TomlParser<'_> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error(&self, reason: &str) -> String {
+        format!("line {} : {}", self.line, reason)
+    }
+
+    fn starts_with(&self, expected: &str) -> bool {
+        self.chars.clone().take(expected.len()).eq(expected.chars())
+    }
+
+    /// Skips spaces and `#` comments, and line breaks when `newlines` is set
+    fn skip_blanks(&mut self, newlines: bool) {
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                ' ' | '\t' | '\r' => {
+                    self.next();
+                }
+                '\n' if newlines => {
+                    self.next();
+                }
+                '#' => while self.chars.peek().is_some_and(|c| *c != '\n') {
+                    self.next();
+                },
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_blanks(false);
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected `{}`", expected))),
+        }
+    }
+
+    fn expect_end_of_line(&mut self) -> Result<(), String> {
+        self.skip_blanks(false);
+        match self.chars.peek().copied() {
+            None | Some('\n') => Ok(()),
+            Some(c) => Err(self.error(&format!("unexpected `{}` after the value", c))),
+        }
+    }
+
+    /// Reads a key and its dotted parts, as `home."page.title"`
+    fn read_key(&mut self) -> Result<Vec<String>, String> {
+        let mut parts = Vec::new();
+        loop {
+            self.skip_blanks(false);
+            let part = match self.chars.peek() {
+                Some('"') => {
+                    self.next();
+                    self.read_basic_string()?
+                }
+                Some('\'') => {
+                    self.next();
+                    self.read_literal_string()?
+                }
+                Some(c) if is_bare_key_char(*c) => {
+                    let mut part = String::new();
+                    while let Some(&c) = self.chars.peek().filter(|c| is_bare_key_char(**c)) {
+                        part.push(c);
+                        self.next();
+                    }
+                    part
+                }
+                _ => return Err(self.error("expected a key")),
+            };
+            parts.push(part);
+
+            self.skip_blanks(false);
+            if self.chars.peek() != Some(&'.') {
+                return Ok(parts);
+            }
+            self.next();
+        }
+    }
+
+    fn read_value(&mut self) -> Result<Value, String> {
+        if self.starts_with("\"\"\"") {
+            (0..3).for_each(|_| { self.next(); });
+            return self.read_multiline_basic_string().map(Value::String);
+        }
+        if self.starts_with("'''") {
+            (0..3).for_each(|_| { self.next(); });
+            return self.read_multiline_literal_string().map(Value::String);
+        }
+
+        match self.chars.peek() {
+            Some('"') => {
+                self.next();
+                self.read_basic_string().map(Value::String)
+            }
+            Some('\'') => {
+                self.next();
+                self.read_literal_string().map(Value::String)
+            }
+            Some('[') => {
+                self.next();
+                self.read_array()
+            }
+            Some('{') => {
+                self.next();
+                self.read_inline_table()
+            }
+            Some(_) => self.read_scalar(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn read_array(&mut self) -> Result<Value, String> {
+        let mut values = Vec::new();
+        loop {
+            self.skip_blanks(true);
+            if self.chars.peek() == Some(&']') {
+                self.next();
+                return Ok(Value::Array(values));
+            }
+            values.push(self.read_value()?);
+
+            self.skip_blanks(true);
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(self.error("expected `,` or `]` in array")),
+            }
+        }
+    }
+
+    fn read_inline_table(&mut self) -> Result<Value, String> {
+        let mut table = Map::new();
+        self.skip_blanks(false);
+        if self.chars.peek() == Some(&'}') {
+            self.next();
+            return Ok(Value::Object(table));
+        }
+
+        loop {
+            let key = self.read_key()?;
+            self.expect('=')?;
+            self.skip_blanks(false);
+            let value = self.read_value()?;
+            insert_nested(&mut table, &key, value).map_err(|reason| self.error(&reason))?;
+
+            self.skip_blanks(false);
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(table)),
+                _ => return Err(self.error("expected `,` or `}` in inline table")),
+            }
+        }
+    }
+
+    /// Booleans, numbers and dates, the latter being kept as written
+    fn read_scalar(&mut self) -> Result<Value, String> {
+        let mut token = String::new();
+        while let Some(&c) = self.chars.peek().filter(|c| !matches!(c, ',' | ']' | '}' | '#' | '\n' | '\r')) {
+            // Dates and times may be separated by a single space, as `1979-05-27 07:32:00`
+            if c == ' ' && !(token.len() == 10 && token.as_bytes()[4] == b'-') {
+                break;
+            }
+            token.push(c);
+            self.next();
+        }
+        let token = token.trim_end();
+
+        match token {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            _ => {}
+        }
+
+        let digits = token.replace('_', "");
+        let unsigned = digits.trim_start_matches(['+', '-']);
+        let radix = match unsigned.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            Some("0b") => Some(2),
+            _ => None,
+        };
+        let number = match radix {
+            Some(radix) => i64::from_str_radix(&unsigned[2..], radix).ok().map(Number::from),
+            None => digits.parse::<i64>().ok().map(Number::from)
+                .or_else(|| digits.parse::<f64>().ok().and_then(Number::from_f64)),
+        };
+        if let Some(number) = number {
+            return Ok(Value::Number(number));
+        }
+
+        if token.starts_with(|c: char| c.is_ascii_digit()) || matches!(unsigned, "inf" | "nan") {
+            return Ok(Value::String(token.to_string()));
+        }
+        Err(self.error(&format!("invalid value `{}`", token)))
+    }
+
+    fn read_basic_string(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => self.read_escape(&mut text)?,
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn read_literal_string(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(text),
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn read_multiline_basic_string(&mut self) -> Result<String, String> {
+        self.skip_first_newline();
+        let mut text = String::new();
+        loop {
+            if self.starts_with("\"\"\"") && !self.starts_with("\"\"\"\"") {
+                (0..3).for_each(|_| { self.next(); });
+                return Ok(text);
+            }
+            match self.next() {
+                Some('\\') if self.chars.peek().is_some_and(|c| c.is_whitespace()) => {
+                    // A line ending backslash trims the line break and the leading whitespace of the next line
+                    while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        self.next();
+                    }
+                }
+                Some('\\') => self.read_escape(&mut text)?,
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated multi-line string")),
+            }
+        }
+    }
+
+    fn read_multiline_literal_string(&mut self) -> Result<String, String> {
+        self.skip_first_newline();
+        let mut text = String::new();
+        loop {
+            if self.starts_with("'''") && !self.starts_with("''''") {
+                (0..3).for_each(|_| { self.next(); });
+                return Ok(text);
+            }
+            match self.next() {
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated multi-line string")),
+            }
+        }
+    }
+
+    /// A line break right after the opening delimiter is not part of a multi-line string
+    fn skip_first_newline(&mut self) {
+        if self.starts_with("\r\n") {
+            self.next();
+        }
+        if self.chars.peek() == Some(&'\n') {
+            self.next();
+        }
+    }
+
+    fn read_escape(&mut self, text: &mut String) -> Result<(), String> {
+        match self.next() {
+            Some('b') => text.push('\u{8}'),
+            Some('t') => text.push('\t'),
+            Some('n') => text.push('\n'),
+            Some('f') => text.push('\u{c}'),
+            Some('r') => text.push('\r'),
+            Some('e') => text.push('\u{1b}'),
+            Some('"') => text.push('"'),
+            Some('\\') => text.push('\\'),
+            Some(marker @ ('u' | 'U')) => {
+                let length = if marker == 'u' { 4 } else { 8 };
+                let hex: String = (0..length).filter_map(|_| self.next()).collect();
+                let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                text.push(c.ok_or_else(|| self.error(&format!("invalid unicode escape `{}`", hex)))?);
+            }
+            Some(escaped) => return Err(self.error(&format!("invalid escape `\\{}`", escaped))),
+            None => return Err(self.error("unterminated string")),
+        }
+        Ok(())
+    }
+}
+
+/// Inserts the value at its dotted key, creating the intermediate tables
+/// A key already holding a value, or going through one that is not a table, is an error
+fn insert_nested(table: &mut Map<String, Value>, key: &[String], value: Value) -> Result<(), String> {
+    let (last, parents) = key.split_last().expect("keys have at least one part");
+    let mut table = table;
+    for part in parents {
+        let entry = table.entry(part.clone()).or_insert_with(|| Value::Object(Map::new()));
+        table = entry.as_object_mut().ok_or_else(|| format!("`{}` is not a table", part))?;
+    }
+    if table.contains_key(last) {
+        return Err(format!("duplicated key `{}`", key.join(".")));
+    }
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+fn is_bare_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-')
+}
@@ -0,0 +1,93 @@
+use std::cell::Cell;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use crate::safe_write::write_output_file;
+
+static TRACING: AtomicBool = AtomicBool::new(false);
+static TRACE_START: Lazy<Instant> = Lazy::new(Instant::now);
+static TRACE_EVENTS: Lazy<Mutex<Vec<TraceEvent>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Small stable number of the thread, the `tid` of its spans
+    static THREAD_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Complete event (`"ph": "X"`) of the Chrome tracing format, times being in microseconds
+#[derive(Serialize, Debug)]
+struct TraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: u64,
+    dur: u64,
+    pid: u32,
+    tid: u64,
+    args: Value,
+}
+
+/// Starts recording the spans of the run, until written by `write_trace`
+pub fn enable_tracing() {
+    Lazy::force(&TRACE_START);
+    TRACING.store(true, Ordering::Relaxed);
+}
+
+pub fn is_tracing() -> bool {
+    TRACING.load(Ordering::Relaxed)
+}
+
+/// Span recorded when dropped, lasting from its creation
+pub struct TraceSpan {
+    name: String,
+    category: &'static str,
+    args: Value,
+    start: Option<Instant>,
+}
+
+/// Opens a span of the category (`walk`, `parse`, `index` or `analyze`), doing nothing when tracing is disabled
+/// `args` are shown by the trace viewers, as the package or file the span is about
+pub fn trace_span(category: &'static str, name: impl Into<String>, args: Value) -> TraceSpan {
+    let start = is_tracing().then(Instant::now);
+    TraceSpan { name: if start.is_some() { name.into() } else { String::new() }, category, args, start }
+}
+
+impl Drop for TraceSpan {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+
+        let tid = THREAD_ID.with(|thread_id| {
+            if thread_id.get() == 0 {
+                thread_id.set(NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed));
+            }
+            thread_id.get()
+        });
+        let event = TraceEvent {
+            name: std::mem::take(&mut self.name),
+            cat: self.category,
+            ph: "X",
+            ts: start.duration_since(*TRACE_START).as_micros() as u64,
+            dur: start.elapsed().as_micros() as u64,
+            pid: std::process::id(),
+            tid,
+            args: self.args.take(),
+        };
+        TRACE_EVENTS.lock().push(event);
+    }
+}
+
+/// Writes the recorded spans as a Chrome trace, openable in `chrome://tracing` or Perfetto
+pub fn write_trace(path: &Path) -> io::Result<usize> {
+    let events = std::mem::take(&mut *TRACE_EVENTS.lock());
+    let count = events.len();
+    let trace = json!({ "traceEvents": events, "displayTimeUnit": "ms" });
+    write_output_file(path, serde_json::to_string(&trace)?)?;
+    Ok(count)
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::jsonc::{has_json_comments, strip_json_comments};
+use crate::load_translations::LoadTranslationsFilesError;
+
+/// Top-level entries of a translation file, kept in file order and including repeated keys
+#[derive(Clone, Debug, Default)]
+pub struct TranslationEntries(pub Vec<(String, Value)>);
+
+impl TranslationEntries {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.iter().rev().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value)
+    }
+
+    /// Whether an entry holds a nested object, as the files written by i18next
+    pub fn is_nested(&self) -> bool {
+        self.0.iter().any(|(_, value)| value.is_object())
+    }
+
+    /// Replaces the nested objects by their entries, recursively, their keys being joined with the separator
+    pub fn flattened(self, separator: &str) -> Self {
+        fn flatten_into(key: String, value: Value, separator: &str, flattened: &mut Vec<(String, Value)>) {
+            match value {
+                Value::Object(object) => {
+                    for (nested_key, nested_value) in object {
+                        flatten_into(format!("{}{}{}", key, separator, nested_key), nested_value, separator, flattened);
+                    }
+                }
+                value => flattened.push((key, value)),
+            }
+        }
+
+        let mut flattened = Vec::with_capacity(self.0.len());
+        for (key, value) in self.0 {
+            flatten_into(key, value, separator, &mut flattened);
+        }
+        TranslationEntries(flattened)
+    }
+
+    /// Reverse of `flattened`, the keys being split on the separator into nested objects, top-level entries keeping
+    /// the position of their first key. A key going through a value that is not an object is kept whole
+    pub fn nested(self, separator: &str) -> Self {
+        let mut nested: Vec<(String, Value)> = Vec::with_capacity(self.0.len());
+        for (key, value) in self.0 {
+            let mut parts = key.split(separator);
+            let first = parts.next().unwrap_or_default();
+            let rest: Vec<&str> = parts.collect();
+            let Some((last, parents)) = rest.split_last() else {
+                nested.push((key, value));
+                continue;
+            };
+
+            let position = match nested.iter().position(|(entry_key, entry_value)| entry_key == first && entry_value.is_object()) {
+                Some(position) => position,
+                None => {
+                    nested.push((first.to_string(), Value::Object(Map::new())));
+                    nested.len() - 1
+                }
+            };
+            let mut object = nested[position].1.as_object_mut();
+            for part in parents {
+                object = object.and_then(|object| object.entry(part.to_string()).or_insert_with(|| Value::Object(Map::new())).as_object_mut());
+            }
+            match object {
+                Some(object) if !object.contains_key(*last) => {
+                    object.insert(last.to_string(), value);
+                }
+                _ => nested.push((key, value)),
+            }
+        }
+        TranslationEntries(nested)
+    }
+}
+
+impl<'de> Deserialize<'de> for TranslationEntries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = TranslationEntries;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry::<String, Value>()? {
+                    entries.push(entry);
+                }
+                Ok(TranslationEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+impl Serialize for TranslationEntries {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+pub fn read_translation_entries(path: &Path) -> Result<TranslationEntries, LoadTranslationsFilesError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| LoadTranslationsFilesError::FileReadError(
+            format!("Cannot read file: {}", path.display()),
+            e,
+        ))?;
+
+    // The entries are read to be written back as plain JSON, refusing the files whose comments would be lost
+    if has_json_comments(&content) {
+        return Err(LoadTranslationsFilesError::CommentedJson(path.display().to_string()));
+    }
+
+    serde_json::from_str(&strip_json_comments(&content))
+        .map_err(|e| LoadTranslationsFilesError::JsonError(
+            format!("Invalid JSON format in {}", path.display()),
+            e,
+        ))
+}
+
+/// Entries of a translation file keyed as the analyses key them, the keys of nested objects being joined with the
+/// separator, and whether the file nests its keys. An empty separator leaves the entries as written
+pub fn read_flattened_entries(path: &Path, separator: &str) -> Result<(TranslationEntries, bool), LoadTranslationsFilesError> {
+    let entries = read_translation_entries(path)?;
+    if separator.is_empty() || !entries.is_nested() {
+        return Ok((entries, false));
+    }
+    Ok((entries.flattened(separator), true))
+}
+
+/// Serializes entries read by `read_flattened_entries`, nesting their keys again when the file nests them
+pub fn serialize_flattened_entries(entries: TranslationEntries, nested: bool, separator: &str) -> String {
+    if nested {
+        serialize_translation_entries(&entries.nested(separator))
+    } else {
+        serialize_translation_entries(&entries)
+    }
+}
+
+/// Serializes entries the way translation files are written in the monorepo (2 spaces, final newline)
+pub fn serialize_translation_entries(entries: &TranslationEntries) -> String {
+    let mut content = serde_json::to_string_pretty(entries).expect("Translation entries are always serializable");
+    content.push('\n');
+    content
+}
@@ -0,0 +1,202 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use crate::android_xml_file::parse_android_xml_entries;
+use crate::csv_file::parse_csv_entries;
+use crate::js_module_file::parse_js_module_entries;
+use crate::jsonc::strip_json_comments;
+use crate::load_translations::LoadTranslationsFilesError;
+use crate::po_file::parse_po_entries;
+use crate::strings_file::{decode_strings_content, parse_strings_entries};
+use crate::toml_file::parse_toml_entries;
+use crate::translation_file::TranslationEntries;
+
+/// Entries of a translation file for one of its locales
+pub struct LoadedEntries {
+    /// Locale given by the file content, as a CSV column, `None` when the file name tells it
+    pub locale: Option<String>,
+    /// Every entry in file order, keys defined twice included so they can be reported
+    pub entries: TranslationEntries,
+}
+
+impl LoadedEntries {
+    fn single(entries: TranslationEntries) -> Vec<Self> {
+        vec![Self { locale: None, entries }]
+    }
+}
+
+/// Parser of a translation file format, registered in `TranslationLoaders` to read the files it handles
+/// Values are stored JSON encoded whatever the format, nested objects being flattened by the caller
+pub trait TranslationLoader: Send + Sync {
+    /// Extensions of the handled files, without the leading dot
+    fn extensions(&self) -> &[&str];
+
+    /// Whether the loader reads the file, by default when its extension is one of `extensions`
+    /// Loaders keyed by file name pattern, as `messages.*.txt`, implement this instead
+    fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| self.extensions().contains(&extension))
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError>;
+}
+
+/// Loaders tried in turn for each file, the ones registered last being tried first so they can
+/// take over a built-in format
+#[derive(Clone)]
+pub struct TranslationLoaders(Vec<Arc<dyn TranslationLoader>>);
+
+impl TranslationLoaders {
+    /// No loader at all, `default()` giving the built-in formats
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn register(&mut self, loader: impl TranslationLoader + 'static) -> &mut Self {
+        self.0.insert(0, Arc::new(loader));
+        self
+    }
+
+    pub fn find(&self, path: &Path) -> Option<&dyn TranslationLoader> {
+        self.0.iter().find(|loader| loader.handles(path)).map(|loader| loader.as_ref())
+    }
+
+    /// Extensions of every registered loader, for error messages
+    pub fn extensions(&self) -> Vec<&str> {
+        self.0.iter().flat_map(|loader| loader.extensions().iter().copied()).collect()
+    }
+}
+
+impl Default for TranslationLoaders {
+    /// JSON, gettext, Apple strings, Android XML, JavaScript modules, CSV exports and TOML
+    fn default() -> Self {
+        let mut loaders = Self::empty();
+        loaders
+            .register(TomlLoader)
+            .register(CsvLoader)
+            .register(JsModuleLoader)
+            .register(AndroidXmlLoader)
+            .register(StringsLoader)
+            .register(PoLoader)
+            .register(JsonLoader);
+        loaders
+    }
+}
+
+fn utf8<'a>(path: &Path, content: &'a [u8]) -> Result<&'a str, LoadTranslationsFilesError> {
+    std::str::from_utf8(content).map_err(|e| LoadTranslationsFilesError::FileReadError(
+        format!("Cannot read file: {}", path.display()),
+        io::Error::new(io::ErrorKind::InvalidData, e),
+    ))
+}
+
+/// JSON files, JSONC comments and trailing commas being accepted, JSON5 not being supported
+pub struct JsonLoader;
+
+impl TranslationLoader for JsonLoader {
+    fn extensions(&self) -> &[&str] {
+        &["json", "jsonc"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        serde_json::from_str(&strip_json_comments(utf8(path, content)?))
+            .map(LoadedEntries::single)
+            .map_err(|e| LoadTranslationsFilesError::JsonError(
+                format!("Invalid JSON format in {}", path.display()),
+                e,
+            ))
+    }
+}
+
+/// Gettext catalogs, `.pot` templates having no translated value
+pub struct PoLoader;
+
+impl TranslationLoader for PoLoader {
+    fn extensions(&self) -> &[&str] {
+        &["po", "pot"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        let template = path.extension().is_some_and(|extension| extension == "pot");
+        parse_po_entries(utf8(path, content)?, template)
+            .map(LoadedEntries::single)
+            .map_err(|reason| LoadTranslationsFilesError::PoError(path.display().to_string(), reason))
+    }
+}
+
+/// Apple `.strings` files, possibly UTF-16
+pub struct StringsLoader;
+
+impl TranslationLoader for StringsLoader {
+    fn extensions(&self) -> &[&str] {
+        &["strings"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        decode_strings_content(content)
+            .and_then(|content| parse_strings_entries(&content))
+            .map(LoadedEntries::single)
+            .map_err(|reason| LoadTranslationsFilesError::StringsError(path.display().to_string(), reason))
+    }
+}
+
+/// Android `strings.xml` resources
+pub struct AndroidXmlLoader;
+
+impl TranslationLoader for AndroidXmlLoader {
+    fn extensions(&self) -> &[&str] {
+        &["xml"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        parse_android_xml_entries(utf8(path, content)?)
+            .map(LoadedEntries::single)
+            .map_err(|reason| LoadTranslationsFilesError::AndroidXmlError(path.display().to_string(), reason))
+    }
+}
+
+/// JavaScript and TypeScript modules exporting an object literal by default
+pub struct JsModuleLoader;
+
+impl TranslationLoader for JsModuleLoader {
+    fn extensions(&self) -> &[&str] {
+        &["js", "mjs", "cjs", "ts", "mts", "cts"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        parse_js_module_entries(utf8(path, content)?)
+            .map(LoadedEntries::single)
+            .map_err(|reason| LoadTranslationsFilesError::JsModuleError(path.display().to_string(), reason))
+    }
+}
+
+/// Spreadsheet exports, giving the entries of each of their locale columns
+pub struct CsvLoader;
+
+impl TranslationLoader for CsvLoader {
+    fn extensions(&self) -> &[&str] {
+        &["csv"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        let locales = parse_csv_entries(utf8(path, content)?)
+            .map_err(|reason| LoadTranslationsFilesError::CsvError(path.display().to_string(), reason))?;
+        Ok(locales.into_iter().map(|(locale, entries)| LoadedEntries { locale: Some(locale), entries }).collect())
+    }
+}
+
+/// TOML files, the keys of tables and dotted keys being nested
+pub struct TomlLoader;
+
+impl TranslationLoader for TomlLoader {
+    fn extensions(&self) -> &[&str] {
+        &["toml"]
+    }
+
+    fn load(&self, path: &Path, content: &[u8]) -> Result<Vec<LoadedEntries>, LoadTranslationsFilesError> {
+        parse_toml_entries(utf8(path, content)?)
+            .map(LoadedEntries::single)
+            .map_err(|reason| LoadTranslationsFilesError::TomlError(path.display().to_string(), reason))
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+use std::path::Path;
+use regex::Regex;
+use serde_json::{Map, Value};
+use crate::analyse_deprecated_keys::{get_deprecations, IsoDate};
+use crate::glob_pattern::{compile_glob, LOCALE_PLACEHOLDER};
+use crate::jsonc::json_field_lines;
+use crate::settings::{Settings, SETTINGS_ENV_PREFIX};
+use crate::toml_file::toml_field_lines;
+use crate::yaml_file::yaml_field_lines;
+
+/// Suggestions are only given for names close enough to a known setting
+const SUGGESTION_MIN_SIMILARITY: f64 = 0.8;
+/// Settings keyed by locale or by name, whose fields are not settings
+const MAP_SETTINGS: [&str; 4] = ["locales", "formatted_value_patterns", "quote_styles", "tasks"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingsIssueSeverity {
+    /// The settings cannot be used, as an invalid regex
+    Error,
+    /// The settings can be used but are likely not what was meant, as a misspelled field
+    Warning,
+}
+
+/// Problem found in the settings, `field` being its path as `triage_rules[0].key_pattern`
+#[derive(Debug)]
+pub struct SettingsIssue {
+    pub severity: SettingsIssueSeverity,
+    pub field: String,
+    /// Line of the field in the settings file, when it comes from it
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for SettingsIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} (line {}) : {}", self.field, line, self.message),
+            None => write!(f, "{} : {}", self.field, self.message),
+        }
+    }
+}
+
+/// Checks the settings before any analysis: unknown fields of the settings file and unknown
+/// `TRANSLATIONS_ANALYZER_*` environment variables, invalid regexes and globs, common translations modules missing
+/// from the monorepo and deprecation deadlines that are not dates
+pub fn validate_settings(settings: &Settings, file_layer: &Value, env_names: &[String], monorepo_path: &Path) -> Vec<SettingsIssue> {
+    let mut issues = Vec::new();
+
+    let defaults = serde_json::to_value(Settings::default()).expect("settings are serializable");
+    find_unknown_fields(file_layer, &defaults, "", &mut issues);
+    for (name, profile) in file_layer.get("profiles").and_then(Value::as_object).into_iter().flatten() {
+        find_unknown_fields(profile, &defaults, &format!("profiles.{}.", name), &mut issues);
+    }
+    for name in env_names {
+        let Some(setting) = name.strip_prefix(SETTINGS_ENV_PREFIX).map(str::to_lowercase) else {
+            continue;
+        };
+        let known_settings = defaults.as_object().expect("settings are serialized as an object");
+        if !known_settings.contains_key(&setting) {
+            let message = match closest_setting(&setting, known_settings) {
+                Some(known) => format!("unknown setting, did you mean `{}{}`?", SETTINGS_ENV_PREFIX, known.to_uppercase()),
+                None => "unknown setting".to_string(),
+            };
+            issues.push(SettingsIssue { severity: SettingsIssueSeverity::Warning, field: name.clone(), line: None, message });
+        }
+    }
+
+    let mut check_regex = |field: String, pattern: &str| {
+        if let Err(error) = Regex::new(pattern) {
+            issues.push(error_issue(field, format!("invalid regex `{}`, {}", pattern, error)));
+        }
+    };
+    check_regex("package_path_regex".to_string(), &settings.package_path_regex);
+    check_regex("translation_file_regex".to_string(), &settings.translation_file_regex);
+    check_regex("locales_file_regex".to_string(), &settings.locales_file_regex);
+    for (locale, pattern) in settings.locales.patterns() {
+        check_regex(format!("locales.{}", locale), pattern);
+    }
+    for (position, rule) in settings.triage_rules.iter().enumerate() {
+        if let Some(pattern) = &rule.key_pattern {
+            check_regex(format!("triage_rules[{}].key_pattern", position), pattern);
+        }
+        if let Some(pattern) = &rule.value_pattern {
+            check_regex(format!("triage_rules[{}].value_pattern", position), pattern);
+        }
+    }
+    for (position, settings_override) in settings.overrides.iter().enumerate() {
+        if let Some(pattern) = &settings_override.translation_file_regex {
+            check_regex(format!("overrides[{}].translation_file_regex", position), pattern);
+        }
+        if let Some(pattern) = &settings_override.locales_file_regex {
+            check_regex(format!("overrides[{}].locales_file_regex", position), pattern);
+        }
+    }
+    for (locale, patterns) in &settings.formatted_value_patterns {
+        for (position, pattern) in patterns.iter().enumerate() {
+            check_regex(format!("formatted_value_patterns.{}[{}]", locale, position), pattern);
+        }
+    }
+
+    if !settings.locales.patterns().is_empty() && !settings.locales.patterns().contains_key(&settings.reference_locale) {
+        issues.push(error_issue("reference_locale".to_string(), format!("no `locales` pattern for {}", settings.reference_locale)));
+    }
+
+    for (field, glob) in [("translation_file_glob", &settings.translation_file_glob), ("locales_file_glob", &settings.locales_file_glob)] {
+        if let Some(glob) = glob
+            && let Err(error) = compile_glob(glob)
+        {
+            issues.push(error_issue(field.to_string(), format!("invalid glob `{}`, {}", glob, error)));
+        }
+    }
+
+    // Globs match whole paths, the locale of their files cannot be captured from their name by `locales_file_regex`
+    if let Some(glob) = &settings.locales_file_glob
+        && !glob.contains(LOCALE_PLACEHOLDER)
+        && settings.locales.patterns().is_empty()
+    {
+        issues.push(error_issue("locales_file_glob".to_string(), format!("no {} placeholder in `{}` nor `locales` patterns telling the locale of its files", LOCALE_PLACEHOLDER, glob)));
+    }
+
+    for (position, settings_override) in settings.overrides.iter().enumerate() {
+        if let Err(error) = compile_glob(&settings_override.packages) {
+            issues.push(error_issue(format!("overrides[{}].packages", position), format!("invalid glob `{}`, {}", settings_override.packages, error)));
+        }
+    }
+
+    // The default module only exists in the monorepos laid out as the default settings expect
+    let default_modules = Settings::default().common_translations_modules_path;
+    let modules = settings.common_translations_modules_path.iter().enumerate().filter(|(_, path)| !default_modules.contains(path));
+    for (position, path) in modules {
+        if !monorepo_path.join(path).is_dir() {
+            issues.push(SettingsIssue {
+                severity: SettingsIssueSeverity::Warning,
+                field: format!("common_translations_modules_path[{}]", position),
+                line: None,
+                message: format!("no directory {} in {}", path, monorepo_path.display()),
+            });
+        }
+    }
+
+    // An unreadable deprecations file is reported by the deprecated keys check itself
+    let deprecations = get_deprecations(&monorepo_path.join(&settings.deprecations_file_path)).unwrap_or_default();
+    for deprecation in &deprecations {
+        if let Some(deadline) = deprecation.deadline.as_deref().filter(|deadline| IsoDate::parse(deadline).is_none()) {
+            issues.push(error_issue(
+                "deprecations_file_path".to_string(),
+                format!("deadline `{}` of the deprecated key {} in {} is not an ISO date as 2026-01-31", deadline, deprecation.key, settings.deprecations_file_path),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// Gives the issues the line of their field in the settings file, walking the path of the field through the
+/// document, as `triage_rules[3].key_pattern`
+/// A field missing from the file, as a default value, gets the line of its closest parent written in it
+pub fn locate_settings_issues(issues: &mut [SettingsIssue], settings_file_path: &Path, content: &str) {
+    let extension = settings_file_path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+    let field_lines = match extension {
+        "toml" => toml_field_lines(content).unwrap_or_default(),
+        "yaml" | "yml" => yaml_field_lines(content).unwrap_or_default(),
+        _ => json_field_lines(content),
+    };
+
+    for issue in issues {
+        let mut field = issue.field.as_str();
+        issue.line = loop {
+            if let Some((_, line)) = field_lines.iter().find(|(path, _)| path == field) {
+                break Some(*line);
+            }
+            match field.rfind(['.', '[']) {
+                Some(end) => field = &field[..end],
+                None => break None,
+            }
+        };
+    }
+}
+
+/// Fields of the layer missing from the default settings, the maps and the objects left empty by default
+/// accepting any field
+fn find_unknown_fields(layer: &Value, defaults: &Value, prefix: &str, issues: &mut Vec<SettingsIssue>) {
+    let (Some(layer), Some(defaults)) = (layer.as_object(), defaults.as_object()) else {
+        return;
+    };
+    if defaults.is_empty() {
+        return;
+    }
+
+    for (name, value) in layer {
+        let field = format!("{}{}", prefix, name);
+        match defaults.get(name) {
+            // Refers to the schema of the settings file, for editors
+            None if field == "$schema" => {}
+            Some(_) if MAP_SETTINGS.contains(&field.as_str()) => {}
+            Some(default) => find_unknown_fields(value, default, &format!("{}.", field), issues),
+            None => {
+                let message = match closest_setting(name, defaults) {
+                    Some(known) => format!("unknown setting, did you mean `{}`?", known),
+                    None => "unknown setting".to_string(),
+                };
+                issues.push(SettingsIssue { severity: SettingsIssueSeverity::Warning, field, line: None, message });
+            }
+        }
+    }
+}
+
+/// Known setting whose name is the closest to an unknown one, when close enough
+fn closest_setting<'a>(name: &str, known_settings: &'a Map<String, Value>) -> Option<&'a String> {
+    known_settings
+        .keys()
+        .map(|known| (known, strsim::jaro_winkler(name, known)))
+        .filter(|(_, similarity)| *similarity >= SUGGESTION_MIN_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(known, _)| known)
+}
+
+fn error_issue(field: String, message: String) -> SettingsIssue {
+    SettingsIssue { severity: SettingsIssueSeverity::Error, field, line: None, message }
+}
@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+use crate::entities::Translation;
+use crate::map_translations_by_project::PackagePathPattern;
+
+/// Starts each commit of the `git log` outputs, written by the `%x00` of their format, paths never holding it
+const COMMIT_SEPARATOR: char = '\0';
+
+#[derive(Error, Debug)]
+pub enum ProvenanceError {
+    #[error("Unable to run git: {0}")]
+    GitUnavailable(#[source] std::io::Error),
+
+    #[error("git log failed in {0}: {1}")]
+    GitFailed(String, String),
+}
+
+/// Occurrence of a value added first to the history, whose package is assumed to be the original source
+#[derive(Debug, Clone)]
+pub struct ValueOrigin {
+    pub package_path: String,
+    pub key: String,
+    pub commit: String,
+    pub date: String,
+    timestamp: i64,
+}
+
+impl fmt::Display for ValueOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (key {}, commit {} on {})", self.package_path, self.key, self.commit, self.date)
+    }
+}
+
+/// Commit of the history, as its short hash, timestamp and date
+struct Commit {
+    hash: String,
+    timestamp: i64,
+    date: String,
+}
+
+/// Git history of the monorepo, with the former names of its renamed files so values are followed across renames
+pub struct ValueHistory {
+    monorepo_path: PathBuf,
+    /// Former names of each renamed file, relative to the monorepo
+    former_names: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl ValueHistory {
+    /// History of the monorepo, `None` when git is missing or the monorepo is not in a git repository with commits
+    pub fn open(monorepo_path: &Path) -> Result<Option<Self>, ProvenanceError> {
+        let has_history = Command::new("git")
+            .current_dir(monorepo_path)
+            .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !has_history {
+            return Ok(None);
+        }
+
+        let mut former_names: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        let renames = git_log(monorepo_path, &["-M", "--diff-filter=R", "--name-status", "--format="])?;
+        for line in renames.lines() {
+            let mut fields = line.split('\t');
+            if let (Some(status), Some(old), Some(new)) = (fields.next(), fields.next(), fields.next())
+                && status.starts_with('R')
+            {
+                former_names.entry(PathBuf::from(new)).or_default().push(PathBuf::from(old));
+            }
+        }
+
+        Ok(Some(Self { monorepo_path: monorepo_path.to_path_buf(), former_names }))
+    }
+
+    /// Finds, among the occurrences of a value, the one whose file received the value in the earliest commit
+    /// A single `git log` is run for each distinct text of the occurrences, over their files and former names
+    /// Occurrences not committed yet are ignored, `None` meaning none of them is
+    pub fn find_value_origin(&self, occurrences: &[&Translation], package_path_pattern: &PackagePathPattern) -> Result<Option<ValueOrigin>, ProvenanceError> {
+        let mut occurrences_by_text: BTreeMap<String, Vec<&Translation>> = BTreeMap::new();
+        for occurrence in occurrences {
+            // The text as written in the files, not its JSON encoding
+            let text = occurrence.text().map_or_else(|| occurrence.translations.to_string(), str::to_string);
+            if !text.is_empty() {
+                occurrences_by_text.entry(text).or_default().push(occurrence);
+            }
+        }
+
+        let mut origin: Option<ValueOrigin> = None;
+        for (text, occurrences) in occurrences_by_text {
+            let mut occurrence_by_name: HashMap<PathBuf, &Translation> = HashMap::new();
+            for occurrence in &occurrences {
+                let relative_path = occurrence.path.strip_prefix(&self.monorepo_path).unwrap_or(&occurrence.path);
+                for name in self.names(relative_path) {
+                    occurrence_by_name.entry(name).or_insert(occurrence);
+                }
+            }
+
+            for (occurrence, commit) in self.first_commits_adding(&text, &occurrence_by_name)? {
+                let package_path = package_path_pattern.package_path(&occurrence.path.to_string_lossy());
+                if origin.as_ref().is_none_or(|origin| (commit.timestamp, &package_path) < (origin.timestamp, &origin.package_path)) {
+                    origin = Some(ValueOrigin { package_path, key: occurrence.key.clone(), commit: commit.hash, date: commit.date, timestamp: commit.timestamp });
+                }
+            }
+        }
+
+        Ok(origin)
+    }
+
+    /// Current name of a file followed by its former names, the renames being followed transitively
+    fn names(&self, relative_path: &Path) -> Vec<PathBuf> {
+        let mut names = vec![relative_path.to_path_buf()];
+        let mut position = 0;
+        while position < names.len() {
+            for former_name in self.former_names.get(&names[position]).into_iter().flatten() {
+                if !names.contains(former_name) {
+                    names.push(former_name.clone());
+                }
+            }
+            position += 1;
+        }
+        names
+    }
+
+    /// First commit changing the number of occurrences of the text in the file of each occurrence, under any of its
+    /// names
+    fn first_commits_adding<'a>(&self, text: &str, occurrence_by_name: &HashMap<PathBuf, &'a Translation>) -> Result<Vec<(&'a Translation, Commit)>, ProvenanceError> {
+        let mut args: Vec<&str> = vec!["--reverse", "--name-only", "--format=%x00%h %at %as", "-S", text, "--"];
+        let names: Vec<String> = occurrence_by_name.keys().map(|name| name.to_string_lossy().to_string()).collect();
+        args.extend(names.iter().map(String::as_str));
+        let log = git_log(&self.monorepo_path, &args)?;
+
+        let mut first_commits: Vec<(&'a Translation, Commit)> = Vec::new();
+        for commit in log.split(COMMIT_SEPARATOR).filter(|commit| !commit.trim().is_empty()) {
+            let mut lines = commit.lines();
+            let mut fields = lines.next().unwrap_or_default().split(' ');
+            let (Some(hash), Some(timestamp), Some(date)) = (fields.next(), fields.next().and_then(|timestamp| timestamp.parse::<i64>().ok()), fields.next()) else {
+                continue;
+            };
+
+            for name in lines.filter(|line| !line.is_empty()) {
+                let Some(occurrence) = occurrence_by_name.get(Path::new(name)) else {
+                    continue;
+                };
+                if !first_commits.iter().any(|(found, _)| std::ptr::eq(*found, *occurrence)) {
+                    first_commits.push((occurrence, Commit { hash: hash.to_string(), timestamp, date: date.to_string() }));
+                }
+            }
+        }
+
+        Ok(first_commits)
+    }
+}
+
+/// Output of `git log` with the paths relative to the monorepo, as the paths given to it
+fn git_log(monorepo_path: &Path, args: &[&str]) -> Result<String, ProvenanceError> {
+    let output = Command::new("git")
+        .current_dir(monorepo_path)
+        .args(["log", "--relative"])
+        .args(args)
+        .output()
+        .map_err(ProvenanceError::GitUnavailable)?;
+
+    if !output.status.success() {
+        return Err(ProvenanceError::GitFailed(
+            monorepo_path.to_string_lossy().to_string(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -0,0 +1,461 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use serde_json::{Map, Number, Value};
+
+/// Parses a YAML document, as a settings file, into the matching JSON value
+/// Block and flow mappings and sequences, quoted and plain scalars and `|` / `>` block scalars are supported,
+/// anchors, aliases, tags, directives, complex keys and multi-document streams are rejected rather than read as text
+pub fn parse_yaml_document(content: &str) -> Result<Value, String> {
+    read_yaml_document(content).map(|(value, _)| value)
+}
+
+/// Line of each field of a YAML document, by its path as `triage_rules[3].key_pattern`
+/// The fields of flow collections are left out, their collection giving their line
+pub fn yaml_field_lines(content: &str) -> Result<Vec<(String, usize)>, String> {
+    read_yaml_document(content).map(|(_, field_lines)| field_lines)
+}
+
+fn read_yaml_document(content: &str) -> Result<(Value, Vec<(String, usize)>), String> {
+    let mut lines = Vec::new();
+    let mut has_content = false;
+    for (position, raw) in content.lines().enumerate() {
+        let line = YamlLine { number: position + 1, raw, indent: 0, text: "" }.parsed()?;
+        match line.raw.trim_end() {
+            // A single document may be started by `---` and ended by `...`
+            "---" if !has_content => continue,
+            "---" | "..." if has_content => {
+                if content.lines().skip(position + 1).any(|raw| !strip_comment(raw).trim().is_empty()) {
+                    return Err(line.error("multi-document streams are not supported"));
+                }
+                break;
+            }
+            _ => {}
+        }
+        if line.text.starts_with('%') && line.indent == 0 {
+            return Err(line.error("directives are not supported"));
+        }
+        if line.text == "?" || line.text.starts_with("? ") {
+            return Err(line.error("complex keys are not supported"));
+        }
+        has_content |= !line.text.is_empty();
+        lines.push(line);
+    }
+    let mut parser = YamlParser { lines, position: 0, field_lines: Vec::new() };
+
+    let value = parser.read_node(0, "")?;
+    match parser.next_line() {
+        Some(line) => Err(line.error("unexpected indentation")),
+        None => Ok((value, parser.field_lines)),
+    }
+}
+
+struct YamlLine<'a> {
+    number: usize,
+    raw: &'a str,
+    indent: usize,
+    /// Content after the indentation, comments left out, empty for blank lines
+    text: &'a str,
+}
+
+impl<'a> YamlLine<'a> {
+    fn parsed(mut self) -> Result<Self, String> {
+        let content = self.raw.trim_start_matches(' ');
+        self.indent = self.raw.len() - content.len();
+        self.text = strip_comment(content).trim_end();
+        if self.text.starts_with('\t') {
+            return Err(self.error("tabs are not allowed in indentation"));
+        }
+        Ok(self)
+    }
+
+    fn error(&self, reason: &str) -> String {
+        format!("line {} : {}", self.number, reason)
+    }
+}
+
+struct YamlParser<'a> {
+    lines: Vec<YamlLine<'a>>,
+    position: usize,
+    /// Line of each field read, by its path
+    field_lines: Vec<(String, usize)>,
+}
+
+impl<'a> YamlParser<'a> {
+    /// Next line holding content, blank lines being skipped
+    fn next_line(&mut self) -> Option<&YamlLine<'a>> {
+        while self.lines.get(self.position).is_some_and(|line| line.text.is_empty()) {
+            self.position += 1;
+        }
+        self.lines.get(self.position)
+    }
+
+    /// Value whose lines are indented by at least `min_indent`, null when there is none, `path` being the path of
+    /// its field
+    fn read_node(&mut self, min_indent: usize, path: &str) -> Result<Value, String> {
+        let Some(line) = self.next_line() else {
+            return Ok(Value::Null);
+        };
+        if line.indent < min_indent {
+            return Ok(Value::Null);
+        }
+
+        let indent = line.indent;
+        if is_sequence_item(line.text) {
+            self.read_sequence(indent, path)
+        } else if split_mapping_entry(line.text).is_some() {
+            self.read_mapping(indent, path)
+        } else {
+            let line = &self.lines[self.position];
+            let value = parse_inline_value(line.text).map_err(|reason| line.error(&reason))?;
+            self.position += 1;
+            Ok(value)
+        }
+    }
+
+    fn read_sequence(&mut self, indent: usize, path: &str) -> Result<Value, String> {
+        let mut items = Vec::new();
+        while let Some(line) = self.next_line().filter(|line| line.indent == indent && is_sequence_item(line.text)) {
+            let (number, text) = (line.number, line.text);
+            let item_path = format!("{}[{}]", path, items.len());
+            self.field_lines.push((item_path.clone(), number));
+            let rest = text[1..].trim_start();
+            if rest.is_empty() {
+                self.position += 1;
+                items.push(self.read_node(indent + 1, &item_path)?);
+            } else {
+                // The item content is read as a line of its own, so `- name: x` starts a mapping at its column
+                let offset = text.len() - rest.len();
+                let line = &mut self.lines[self.position];
+                line.indent += offset;
+                line.text = rest;
+                let item_indent = line.indent;
+                items.push(self.read_node(item_indent, &item_path)?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn read_mapping(&mut self, indent: usize, path: &str) -> Result<Value, String> {
+        let mut mapping = Map::new();
+        while let Some(line) = self.next_line().filter(|line| line.indent == indent) {
+            let Some((key, rest)) = split_mapping_entry(line.text) else {
+                return Err(line.error("expected a `key: value` entry"));
+            };
+            let key = parse_key(key).map_err(|reason| line.error(&reason))?;
+            let number = line.number;
+            self.position += 1;
+            let field = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            self.field_lines.push((field.clone(), number));
+
+            let value = if rest.is_empty() {
+                // Sequences may be written at the indentation of their key
+                match self.next_line() {
+                    Some(next) if next.indent == indent && is_sequence_item(next.text) => self.read_sequence(indent, &field)?,
+                    _ => self.read_node(indent + 1, &field)?,
+                }
+            } else if rest.starts_with(['|', '>']) {
+                self.read_block_scalar(rest, indent).map_err(|reason| format!("line {} : {}", number, reason))?
+            } else {
+                parse_inline_value(rest).map_err(|reason| format!("line {} : {}", number, reason))?
+            };
+
+            if mapping.insert(key.clone(), value).is_some() {
+                return Err(format!("line {} : duplicated key `{}`", number, key));
+            }
+        }
+        Ok(Value::Object(mapping))
+    }
+
+    /// `|` keeps the line breaks, `>` folds the lines with spaces, a `-` or `+` indicator
+    /// stripping or keeping the trailing line breaks
+    fn read_block_scalar(&mut self, header: &str, parent_indent: usize) -> Result<Value, String> {
+        let folded = header.starts_with('>');
+        let chomping = header[1..].trim();
+        if !matches!(chomping, "" | "-" | "+") {
+            return Err(format!("unsupported block scalar header `{}`", header));
+        }
+
+        let mut lines: Vec<&str> = Vec::new();
+        let mut block_indent = None;
+        while let Some(line) = self.lines.get(self.position) {
+            let blank = line.raw.trim().is_empty();
+            if !blank && line.indent <= parent_indent {
+                break;
+            }
+            // The first line with content gives the indentation of the block
+            if blank {
+                lines.push("");
+            } else {
+                let indent = *block_indent.get_or_insert(line.indent);
+                lines.push(&line.raw[indent.min(line.indent)..]);
+            }
+            self.position += 1;
+        }
+
+        let mut text = String::new();
+        for (position, line) in lines.iter().enumerate() {
+            match (folded, position) {
+                (_, 0) => {}
+                (false, _) => text.push('\n'),
+                // Folded lines are joined with a space, a blank line giving a line break
+                (true, _) if line.is_empty() => text.push('\n'),
+                (true, _) if !lines[position - 1].is_empty() => text.push(' '),
+                (true, _) => {}
+            }
+            text.push_str(line);
+        }
+        let content_length = text.trim_end_matches('\n').len();
+        match chomping {
+            "-" => text.truncate(content_length),
+            "+" => text.push('\n'),
+            _ => {
+                text.truncate(content_length);
+                text.push('\n');
+            }
+        }
+        Ok(Value::String(text))
+    }
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Key and value of a `key: value` line, the value being empty when it follows on the next lines
+fn split_mapping_entry(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['[', '{']) {
+        return None;
+    }
+
+    let mut quote = None;
+    for (position, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if position == 0 => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, ':') => {
+                let rest = &text[position + 1..];
+                if rest.is_empty() || rest.starts_with(' ') {
+                    return Some((text[..position].trim_end(), rest.trim_start()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_key(key: &str) -> Result<String, String> {
+    match parse_inline_value(key)? {
+        Value::String(key) => Ok(key),
+        Value::Null => Ok(String::new()),
+        other => Ok(other.to_string()),
+    }
+}
+
+/// Leaves out a `#` comment, which starts the line or follows a space outside of quotes
+fn strip_comment(content: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (position, c) in content.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if matches!(previous, ' ' | '[' | '{' | ',' | ':' | '-') => quote = Some(c),
+            (Some('"'), '\\') => {}
+            (Some(open), _) if c == open && !(open == '"' && previous == '\\') => quote = None,
+            (None, '#') if previous == ' ' => return &content[..position],
+            _ => {}
+        }
+        previous = c;
+    }
+    content
+}
+
+/// Scalar or flow collection written on a single line
+fn parse_inline_value(text: &str) -> Result<Value, String> {
+    let mut parser = FlowParser { chars: text.chars().peekable() };
+    let value = parser.read_value(false)?;
+    parser.skip_spaces();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some(c) => Err(format!("unexpected `{}` after the value", c)),
+    }
+}
+
+struct FlowParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl FlowParser<'_> {
+    fn skip_spaces(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    /// `in_flow` is set inside `[]` and `{}`, where plain scalars end at `,`, `]` and `}`
+    fn read_value(&mut self, in_flow: bool) -> Result<Value, String> {
+        self.skip_spaces();
+        match self.chars.peek() {
+            Some('"') => {
+                self.chars.next();
+                self.read_double_quoted().map(Value::String)
+            }
+            Some('\'') => {
+                self.chars.next();
+                self.read_single_quoted().map(Value::String)
+            }
+            Some('[') => {
+                self.chars.next();
+                self.read_flow_sequence()
+            }
+            Some('{') => {
+                self.chars.next();
+                self.read_flow_mapping()
+            }
+            Some('&') => Err("anchors are not supported".to_string()),
+            Some('*') => Err("aliases are not supported".to_string()),
+            Some('!') => Err("tags are not supported".to_string()),
+            Some('@' | '`') => Err("plain scalars cannot start with `@` or `` ` ``, quote them".to_string()),
+            _ => Ok(plain_scalar(self.read_plain(in_flow, false).trim())),
+        }
+    }
+
+    /// Plain text up to the end of the line, or of the flow item, or of the key when `key` is set
+    fn read_plain(&mut self, in_flow: bool, key: bool) -> String {
+        let mut text = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if in_flow && matches!(c, ',' | ']' | '}') {
+                break;
+            }
+            if key && c == ':' {
+                break;
+            }
+            text.push(c);
+            self.chars.next();
+        }
+        text
+    }
+
+    fn read_flow_sequence(&mut self) -> Result<Value, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_spaces();
+            if self.chars.peek() == Some(&']') {
+                self.chars.next();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.read_value(true)?);
+
+            self.skip_spaces();
+            match self.chars.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                _ => return Err("expected `,` or `]` in flow sequence".to_string()),
+            }
+        }
+    }
+
+    fn read_flow_mapping(&mut self) -> Result<Value, String> {
+        let mut mapping = Map::new();
+        loop {
+            self.skip_spaces();
+            if self.chars.peek() == Some(&'}') {
+                self.chars.next();
+                return Ok(Value::Object(mapping));
+            }
+
+            let key = match self.chars.peek() {
+                Some('"') => {
+                    self.chars.next();
+                    self.read_double_quoted()?
+                }
+                Some('\'') => {
+                    self.chars.next();
+                    self.read_single_quoted()?
+                }
+                _ => self.read_plain(true, true).trim().to_string(),
+            };
+            self.skip_spaces();
+            let value = if self.chars.peek() == Some(&':') {
+                self.chars.next();
+                self.read_value(true)?
+            } else {
+                Value::Null
+            };
+            if mapping.contains_key(&key) {
+                return Err(format!("duplicated key `{}`", key));
+            }
+            mapping.insert(key, value);
+
+            self.skip_spaces();
+            match self.chars.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(mapping)),
+                _ => return Err("expected `,` or `}` in flow mapping".to_string()),
+            }
+        }
+    }
+
+    fn read_double_quoted(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('0') => text.push('\0'),
+                    Some(escaped @ ('"' | '\\' | '/' | ' ')) => text.push(escaped),
+                    Some(marker @ ('x' | 'u' | 'U')) => {
+                        let length = match marker {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let hex: String = (0..length).filter_map(|_| self.chars.next()).collect();
+                        let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                        text.push(c.ok_or_else(|| format!("invalid unicode escape `{}`", hex))?);
+                    }
+                    Some(escaped) => return Err(format!("invalid escape `\\{}`", escaped)),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(c) => text.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn read_single_quoted(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                // A quote is escaped by doubling it
+                Some('\'') if self.chars.peek() == Some(&'\'') => {
+                    self.chars.next();
+                    text.push('\'');
+                }
+                Some('\'') => return Ok(text),
+                Some(c) => text.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+}
+
+/// Null, boolean and number plain scalars as in the YAML core schema, anything else being a string
+fn plain_scalar(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => return Value::Null,
+        "true" | "True" | "TRUE" => return Value::Bool(true),
+        "false" | "False" | "FALSE" => return Value::Bool(false),
+        _ => {}
+    }
+
+    let number = text.parse::<i64>().ok().map(Number::from)
+        .or_else(|| {
+            let is_float = text.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+                && text.chars().any(|c| c.is_ascii_digit());
+            is_float.then(|| text.parse::<f64>().ok().and_then(Number::from_f64)).flatten()
+        });
+    number.map_or_else(|| Value::String(text.to_string()), Value::Number)
+}